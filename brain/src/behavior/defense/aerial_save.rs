@@ -0,0 +1,186 @@
+use crate::{
+    behavior::{movement::AerialLocTime, strike::GroundedHitTarget},
+    eeg::Drawable,
+    helpers::ball::BallFrame,
+    strategy::{Action, Behavior, Context, Game, Priority, Scenario},
+};
+use common::{prelude::*, rl, Time};
+use nalgebra::{Point3, Unit, Vector3};
+use nameof::name_of_type;
+
+/// Fly up and block a ball that's going over our heads into our goal,
+/// redirecting it wide of the post or over the crossbar.
+pub struct AerialSave {
+    flight: Option<Flight>,
+}
+
+pub struct AerialBlock {
+    /// Seconds from now.
+    pub time: f32,
+    pub ball_loc: Point3<f32>,
+    /// Where the car should be at `time`.
+    pub car_loc: Point3<f32>,
+}
+
+struct Flight {
+    /// Absolute game time.
+    block_time: f32,
+    child: AerialLocTime,
+}
+
+impl AerialSave {
+    /// If the ball prediction drifts this far from where we aimed, give up.
+    const MAX_TARGET_DRIFT: f32 = 150.0;
+
+    pub fn new() -> Self {
+        Self { flight: None }
+    }
+
+    /// Find the latest point on the ball's path into our goal where we can
+    /// still get in the way with an aerial. Waiting until the last moment
+    /// leaves the most time to get there.
+    pub fn viable(
+        game: &Game<'_>,
+        scenario: &Scenario<'_>,
+        car: &common::halfway_house::PlayerInfo,
+    ) -> Option<AerialBlock> {
+        let concede = scenario.impending_concede()?;
+        let own_goal = game.own_goal();
+        let car_loc = car.Physics.loc();
+        scenario
+            .ball_prediction()
+            .iter()
+            .rev()
+            .skip_while(|ball| ball.t >= concede.t)
+            .filter(|ball| ball.loc.z >= GroundedHitTarget::MAX_BALL_Z)
+            // Once it's across the line, it's too late.
+            .filter(|ball| !own_goal.is_y_within_range(ball.loc.y, ..0.0))
+            .find_map(|ball| {
+                let target_loc = contact_loc(game, car_loc, ball);
+                AerialLocTime::feasible(car, target_loc, ball.t).ok()?;
+                Some(AerialBlock {
+                    time: ball.t,
+                    ball_loc: ball.loc,
+                    car_loc: target_loc,
+                })
+            })
+    }
+}
+
+impl Behavior for AerialSave {
+    fn name(&self) -> &str {
+        name_of_type!(AerialSave)
+    }
+
+    fn priority(&self) -> Priority {
+        Priority::Save
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        let now = ctx.packet.GameInfo.TimeSeconds;
+
+        if self.flight.is_none() {
+            let block = some_or_else!(Self::viable(ctx.game, &ctx.scenario, ctx.me()), {
+                ctx.eeg.log(self.name(), "no viable block");
+                return Action::Abort;
+            });
+            ctx.eeg
+                .log_pretty(self.name(), "block_time", Time(block.time));
+            ctx.eeg.log_pretty(self.name(), "ball_loc", block.ball_loc);
+            self.flight = Some(Flight {
+                block_time: now + block.time,
+                child: AerialLocTime::new(block.car_loc, block.time),
+            });
+        }
+        let flight = self.flight.as_mut().unwrap();
+
+        let time_remaining = (flight.block_time - now).max(0.0);
+        let ball = ctx
+            .scenario
+            .ball_prediction()
+            .at_time_or_last(time_remaining);
+        let car_loc = contact_loc(ctx.game, ctx.me().Physics.loc(), ball);
+        if (car_loc - flight.child.target_loc()).norm() >= Self::MAX_TARGET_DRIFT {
+            ctx.eeg
+                .log(name_of_type!(AerialSave), "ball went somewhere else");
+            return Action::Abort;
+        }
+        flight.child.retarget(car_loc);
+        ctx.eeg.draw(Drawable::ghost_ball(ball.loc));
+
+        flight.child.execute_old(ctx)
+    }
+}
+
+/// Which way to knock the ball so it misses the goal. Up and over if it's
+/// already near the crossbar, otherwise out past the nearest post.
+fn redirect_dir(game: &Game<'_>, ball: &BallFrame) -> Unit<Vector3<f32>> {
+    let normal = game.own_goal().normal_2d.to_3d().into_inner();
+    if ball.loc.z >= rl::CROSSBAR_Z - rl::BALL_RADIUS {
+        Unit::new_normalize(normal + Vector3::z())
+    } else {
+        let side = if ball.loc.x >= 0.0 { 1.0 } else { -1.0 };
+        Unit::new_normalize(normal + Vector3::x() * side)
+    }
+}
+
+/// Where the car needs to be to knock `ball` away from the goal.
+fn contact_loc(game: &Game<'_>, car_loc: Point3<f32>, ball: &BallFrame) -> Point3<f32> {
+    let redirect = redirect_dir(game, ball);
+    // Approach from the side we're coming from, but never from behind the
+    // direction we want it to go.
+    let approach = (ball.loc - car_loc).to_axis();
+    let dir = if approach.dot(&redirect.into_inner()) > 0.0 {
+        Unit::new_normalize(approach.into_inner() + redirect.into_inner())
+    } else {
+        redirect
+    };
+    let contact_dist = game.ball_radius() + game.me_vehicle().pivot_to_front_dist();
+    ball.loc - dir.into_inner() * contact_dist
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        behavior::{defense::AerialSave, strike::GroundedHitTarget},
+        routing::test::{car_on_floor, with_offline_context},
+    };
+    use nalgebra::{Point2, Point3, Vector3};
+    use std::f32::consts::PI;
+
+    #[test]
+    fn blocks_a_lob_over_our_heads() {
+        let ball_loc = Point3::new(0.0, -2500.0, 1000.0);
+        let ball_vel = Vector3::new(0.0, -1500.0, 300.0);
+        // Our car waits in net, facing upfield.
+        let keeper = car_on_floor(Point2::new(0.0, -4800.0), PI / 2.0, 0.0, 100.0);
+        with_offline_context(&keeper, ball_loc, ball_vel, 100.0, |ctx| {
+            let concede = ctx.scenario.impending_concede().unwrap();
+            let block = AerialSave::viable(ctx.game, ctx.scenario, ctx.me()).unwrap();
+            assert!(block.time < concede.t);
+            assert!(block.ball_loc.z >= GroundedHitTarget::MAX_BALL_Z);
+            // Meet the ball from the goal side, to knock it back out.
+            assert!(block.car_loc.y < block.ball_loc.y);
+        });
+    }
+
+    #[test]
+    fn nothing_to_save() {
+        let ball_loc = Point3::new(0.0, 0.0, 1000.0);
+        let ball_vel = Vector3::new(0.0, 1000.0, 0.0);
+        let keeper = car_on_floor(Point2::new(0.0, -4800.0), PI / 2.0, 0.0, 100.0);
+        with_offline_context(&keeper, ball_loc, ball_vel, 100.0, |ctx| {
+            assert!(AerialSave::viable(ctx.game, ctx.scenario, ctx.me()).is_none());
+        });
+    }
+
+    #[test]
+    fn no_boost_no_block() {
+        let car = car_on_floor(Point2::new(0.0, -4800.0), PI / 2.0, 0.0, 0.0);
+        let ball_loc = Point3::new(0.0, -2500.0, 1000.0);
+        let ball_vel = Vector3::new(0.0, -1500.0, 300.0);
+        with_offline_context(&car, ball_loc, ball_vel, 100.0, |ctx| {
+            assert!(AerialSave::viable(ctx.game, ctx.scenario, ctx.me()).is_none());
+        });
+    }
+}
@@ -1,8 +1,8 @@
 use crate::{
     behavior::{
-        defense::{retreat::Retreat, retreating_save::RetreatingSave, PanicDefense},
+        defense::{retreat::Retreat, retreating_save::RetreatingSave, AerialSave, PanicDefense},
         offense::TepidHit,
-        strike::{GroundedHitAimContext, GroundedHitTarget, GroundedHitTargetAdjust},
+        strike::{AerialShot, GroundedHitAimContext, GroundedHitTarget, GroundedHitTargetAdjust},
     },
     eeg::Event,
     helpers::hit_angle::blocking_angle,
@@ -70,6 +70,25 @@ impl Defense {
         true
     }
 
+    /// Returns `true` if a ball is headed for our goal that we can't save on
+    /// the ground, but can reach in the air.
+    fn need_aerial_save(ctx: &mut Context<'_>) -> bool {
+        let concede = some_or_else!(ctx.scenario.impending_concede(), {
+            return false;
+        });
+        // The naive intercept ignores height, so it only counts if the ball is
+        // low enough to hit from the ground.
+        let ground_save = ctx
+            .scenario
+            .me_intercept()
+            .map(|i| i.time < concede.t && i.ball_loc.z < GroundedHitTarget::MAX_BALL_Z)
+            .unwrap_or_default();
+        if ground_save {
+            return false;
+        }
+        AerialSave::viable(ctx.game, &ctx.scenario, ctx.me()).is_some()
+    }
+
     pub fn enemy_can_shoot(ctx: &mut Context<'_>) -> bool {
         let (_enemy, intercept) = match ctx.scenario.enemy_intercept() {
             Some(i) => i,
@@ -119,6 +138,12 @@ impl Behavior for Defense {
     fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
         ctx.eeg.track(Event::Defense);
 
+        // If the ball is about to go in over our heads, go up and get it.
+        if Self::need_aerial_save(ctx) {
+            ctx.eeg.log(self.name(), "aerial save");
+            return Action::tail_call(AerialSave::new());
+        }
+
         // If we're not between the ball and our goal, get there.
         if !Self::is_between_ball_and_own_goal(ctx.game, ctx.me(), ctx.scenario) {
             ctx.eeg.log(self.name(), "not between ball and goal");
@@ -141,6 +166,14 @@ impl Behavior for Defense {
             return Action::tail_call(Retreat::new());
         }
 
+        // If the ball is out of reach on the ground, try to knock it away in the air.
+        if ctx.scenario.me_intercept().is_none()
+            && AerialShot::viable_clear(ctx.game, &ctx.scenario, ctx.me()).is_some()
+        {
+            ctx.eeg.log(self.name(), "aerial clear");
+            return Action::tail_call(AerialShot::clear());
+        }
+
         // If we're already in goal, try to take control of the ball.
         Action::tail_call(TepidHit::new())
     }
@@ -176,6 +209,45 @@ pub fn defensive_hit(ctx: &mut GroundedHitAimContext<'_, '_>) -> Result<Grounded
     Ok(GroundedHitTarget::new(ctx.intercept_time, adjust, aim_loc).dodge(dodge))
 }
 
+#[cfg(test)]
+mod tests {
+    use crate::{
+        behavior::defense::Defense,
+        routing::test::{car_on_floor, with_offline_context},
+    };
+    use common::rl;
+    use nalgebra::{Point2, Point3, Vector3};
+    use std::f32::consts::PI;
+
+    fn need_aerial_save(ball_loc: Point3<f32>, ball_vel: Vector3<f32>) -> bool {
+        let keeper = car_on_floor(Point2::new(0.0, -4800.0), PI / 2.0, 0.0, 100.0);
+        with_offline_context(&keeper, ball_loc, ball_vel, 100.0, |ctx| {
+            Defense::need_aerial_save(ctx)
+        })
+    }
+
+    #[test]
+    fn aerial_save_for_a_lob() {
+        let ball_loc = Point3::new(0.0, -2500.0, 1000.0);
+        let ball_vel = Vector3::new(0.0, -1500.0, 300.0);
+        assert!(need_aerial_save(ball_loc, ball_vel));
+    }
+
+    #[test]
+    fn ground_save_for_a_roller() {
+        let ball_loc = Point3::new(0.0, -3000.0, rl::BALL_RADIUS);
+        let ball_vel = Vector3::new(0.0, -1000.0, 0.0);
+        assert!(!need_aerial_save(ball_loc, ball_vel));
+    }
+
+    #[test]
+    fn no_save_when_the_ball_is_going_away() {
+        let ball_loc = Point3::new(0.0, 0.0, 1000.0);
+        let ball_vel = Vector3::new(0.0, 1000.0, 0.0);
+        assert!(!need_aerial_save(ball_loc, ball_vel));
+    }
+}
+
 #[cfg(test)]
 mod integration_tests {
     use crate::{
@@ -1,11 +1,14 @@
 pub use self::{
+    aerial_save::{AerialBlock, AerialSave},
     defense::{defensive_hit, Defense},
     hit_to_own_corner::HitToOwnCorner,
     panic_defense::PanicDefense,
     push_to_own_corner::PushToOwnCorner,
     retreat::Retreat,
+    shadow::{EnemyDribbling, Shadow},
 };
 
+mod aerial_save;
 #[allow(clippy::module_inception)]
 mod defense;
 mod hit_to_own_corner;
@@ -13,3 +16,4 @@ mod panic_defense;
 mod push_to_own_corner;
 mod retreat;
 mod retreating_save;
+mod shadow;
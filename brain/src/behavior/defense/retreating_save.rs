@@ -13,7 +13,7 @@ use crate::{
     },
     routing::{behavior::FollowRoute, models::CarState, plan::GroundIntercept},
     sim::{SimGroundDrive, SimJump},
-    strategy::{Action, Behavior, Context, Game, Priority},
+    strategy::{Action, Behavior, Context, Game, Intent, Priority},
     utils::{geometry::Line2, WallRayCalculator},
 };
 use common::{prelude::*, Distance, Speed};
@@ -136,13 +136,7 @@ impl Behavior for RetreatingSave {
         }
 
         if !self.chatted {
-            ctx.quick_chat(0.1, &[
-                rlbot::flat::QuickChatSelection::Information_TakeTheShot,
-                rlbot::flat::QuickChatSelection::Information_Defending,
-                rlbot::flat::QuickChatSelection::Information_GoForIt,
-                rlbot::flat::QuickChatSelection::Information_InPosition,
-                rlbot::flat::QuickChatSelection::Compliments_GreatPass,
-            ]);
+            ctx.declare_intent(Intent::Defending);
             self.chatted = true;
         }
 
@@ -0,0 +1,192 @@
+use crate::{
+    behavior::{higher_order::Predicate, movement::drive_towards, strike::FiftyFifty},
+    eeg::Drawable,
+    strategy::{Action, Behavior, Context, Game, Priority},
+    utils::Stopwatch,
+};
+use common::{prelude::*, rl, Speed};
+use nameof::name_of_type;
+
+/// Hang back goal-side of an enemy who is dribbling at us, matching their
+/// pace, and only challenge once they commit or lose the ball.
+pub struct Shadow {
+    time: Stopwatch,
+}
+
+impl Shadow {
+    const TIME_LIMIT: f32 = 10.0;
+    /// How far goal-side of the ball to stay.
+    const SHADOW_DISTANCE: f32 = 1200.0;
+    /// Aim this far past the shadow spot, so we keep driving parallel to the
+    /// carrier instead of turning back towards it.
+    const LOOKAHEAD: f32 = 1000.0;
+    /// How hard to correct for being too close or too far, in (uu/s) per uu.
+    const DISTANCE_GAIN: f32 = 2.0;
+    /// Once we're backed up this close to our own goal, there's no room left
+    /// to give.
+    const MIN_GOAL_DISTANCE: f32 = 1500.0;
+
+    pub fn new() -> Self {
+        Self {
+            time: Stopwatch::new(),
+        }
+    }
+}
+
+impl Behavior for Shadow {
+    fn name(&self) -> &str {
+        name_of_type!(Shadow)
+    }
+
+    fn priority(&self) -> Priority {
+        Priority::Defense
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        let elapsed = self.time.tick(ctx.packet.GameInfo.TimeSeconds);
+        if elapsed >= Self::TIME_LIMIT {
+            ctx.eeg.log(self.name(), "time limit exceeded");
+            return Action::Return;
+        }
+
+        // Don't go by `possession` here. The ball prediction doesn't know the
+        // ball is on the carrier's roof, so it drops it and we appear to win the
+        // race. Whether they still have the ball is the question that matters.
+        let carrier = some_or_else!(carrier(ctx.game, ctx.packet), {
+            ctx.eeg.log(self.name(), "carrier committed; challenging");
+            return Action::tail_call(FiftyFifty::new());
+        });
+
+        let me = ctx.me();
+        let own_goal = ctx.game.own_goal();
+        if own_goal.is_y_within_range(me.Physics.loc().y, ..Self::MIN_GOAL_DISTANCE) {
+            ctx.eeg.log(self.name(), "out of room; challenging");
+            return Action::tail_call(FiftyFifty::new());
+        }
+
+        let ball_loc = ctx.packet.GameBall.Physics.loc_2d();
+        let axis = (own_goal.center_2d - ball_loc).to_axis();
+        let shadow_loc = ball_loc + axis.into_inner() * Self::SHADOW_DISTANCE;
+        let target_loc = shadow_loc + axis.into_inner() * Self::LOOKAHEAD;
+
+        // Match the carrier's pace towards our goal, plus a correction to close
+        // the gap to the shadow spot.
+        let error = (shadow_loc - me.Physics.loc_2d()).dot(&axis.into_inner());
+        let carrier_speed = carrier.Physics.vel_2d().dot(&axis.into_inner());
+        let target_speed = (carrier_speed + error * Self::DISTANCE_GAIN)
+            .max(0.0)
+            .min(rl::CAR_MAX_SPEED);
+        let speed = me.Physics.vel_2d().dot(&me.Physics.forward_axis_2d());
+
+        ctx.eeg
+            .draw(Drawable::ghost_car_ground(shadow_loc, me.Physics.rot()));
+        ctx.eeg.print_value("target_speed", Speed(target_speed));
+
+        let mut input = drive_towards(ctx, target_loc);
+        if speed > target_speed + 100.0 {
+            input.Throttle = if speed > target_speed + 500.0 {
+                -1.0
+            } else {
+                0.0
+            };
+        } else {
+            input.Boost = target_speed > rl::CAR_NORMAL_SPEED && speed < target_speed - 100.0;
+        }
+        Action::Yield(input)
+    }
+}
+
+/// Is an enemy dribbling the ball on their roof?
+pub struct EnemyDribbling;
+
+impl Predicate for EnemyDribbling {
+    fn name(&self) -> &str {
+        name_of_type!(EnemyDribbling)
+    }
+
+    fn evaluate(&mut self, ctx: &mut Context<'_>) -> bool {
+        carrier(ctx.game, ctx.packet).is_some()
+    }
+}
+
+/// Returns the enemy with the ball on their roof, if any.
+fn carrier<'a>(
+    game: &'a Game<'_>,
+    packet: &common::halfway_house::LiveDataPacket,
+) -> Option<&'a common::halfway_house::PlayerInfo> {
+    const MIN_REL_Z: f32 = 100.0;
+    const MAX_REL_Z: f32 = 250.0;
+    const MAX_REL_XY: f32 = 150.0;
+    const MAX_REL_SPEED: f32 = 500.0;
+
+    let ball = &packet.GameBall.Physics;
+    game.cars(game.enemy_team).find(|car| {
+        let rel_loc = ball.loc() - car.Physics.loc();
+        car.OnGround
+            && rel_loc.z >= MIN_REL_Z
+            && rel_loc.z < MAX_REL_Z
+            && rel_loc.to_2d().norm() < MAX_REL_XY
+            && (ball.vel() - car.Physics.vel()).norm() < MAX_REL_SPEED
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        behavior::{
+            defense::{EnemyDribbling, Shadow},
+            higher_order::Predicate,
+        },
+        routing::{models::CarState, test::with_offline_context_and_enemies},
+        strategy::{Action, Behavior},
+    };
+    use common::{prelude::*, rl};
+    use nalgebra::{Point3, UnitComplex, Vector3};
+    use std::f32::consts::PI;
+
+    /// Us, waiting upfield of our goal.
+    fn me() -> CarState {
+        CarState {
+            loc: Point3::new(0.0, -2000.0, rl::OCTANE_NEUTRAL_Z),
+            rot: UnitComplex::new(PI / 2.0).around_z_axis(),
+            vel: Vector3::zeros(),
+            boost: 50.0,
+        }
+    }
+
+    /// An enemy driving at our goal.
+    fn enemy() -> CarState {
+        CarState {
+            loc: Point3::new(0.0, 1000.0, rl::OCTANE_NEUTRAL_Z),
+            rot: UnitComplex::new(-PI / 2.0).around_z_axis(),
+            vel: Vector3::new(0.0, -1000.0, 0.0),
+            boost: 50.0,
+        }
+    }
+
+    #[test]
+    fn shadows_a_dribbler() {
+        let enemies = [enemy()];
+        // The ball rides along on their roof.
+        let ball_loc = enemies[0].loc + Vector3::new(0.0, 0.0, 140.0);
+        let ball_vel = enemies[0].vel;
+        with_offline_context_and_enemies(&me(), &enemies, ball_loc, ball_vel, 100.0, |ctx| {
+            assert!(EnemyDribbling.evaluate(ctx));
+            // Hang back rather than tail-calling into a challenge.
+            match Shadow::new().execute_old(ctx) {
+                Action::Yield(_) => {}
+                _ => panic!("expected Yield"),
+            }
+        });
+    }
+
+    #[test]
+    fn ball_on_the_ground_is_not_a_dribble() {
+        let enemies = [enemy()];
+        let ball_loc = Point3::new(0.0, 800.0, rl::BALL_RADIUS);
+        let ball_vel = enemies[0].vel;
+        with_offline_context_and_enemies(&me(), &enemies, ball_loc, ball_vel, 100.0, |ctx| {
+            assert!(!EnemyDribbling.evaluate(ctx));
+        });
+    }
+}
@@ -1,12 +1,15 @@
 use crate::{
     behavior::{
-        higher_order::{Chain, TimeLimit, While},
+        higher_order::{Chain, Predicate, TimeLimit, TryChoose, While},
         movement::{drive_towards, QuickJumpAndDodge, Yielder},
     },
     routing::{
         behavior::FollowRoute,
         models::RoutePlanner,
-        plan::{ChainedPlanner, GroundIntercept, GroundStraightPlanner, TurnPlanner},
+        plan::{
+            ChainedPlanner, GetDollar, GroundArrive, GroundDrive, GroundIntercept, GroundSpeedFlip,
+            GroundStraightPlanner, TurnPlanner,
+        },
         recover::RoundIsNotActive,
         StraightMode,
     },
@@ -14,8 +17,9 @@ use crate::{
 };
 use common::{prelude::*, rl};
 use derive_new::new;
-use nalgebra::Point2;
+use nalgebra::{Point2, Vector2};
 use nameof::name_of_type;
+use ordered_float::NotNan;
 use std::f32::consts::PI;
 use vec_box::vec_box;
 
@@ -51,7 +55,7 @@ impl Behavior for PreKickoff {
 }
 
 fn kickoff_quick_chat(ctx: &mut Context<'_>) {
-    let quick_chat = if ctx.time_based_random() < 0.1 {
+    let quick_chat = if ctx.rng.gen_bool(0.1) {
         // I'm so funny
         rlbot::flat::QuickChatSelection::Information_AllYours
     } else {
@@ -93,58 +97,227 @@ impl Behavior for Kickoff {
             return Action::Abort;
         }
 
-        // Add a "random" component to kickoffs, to keep things unpredictable.
-        let [rand1, rand2, rand3, rand4] = ctx.time_based_randoms();
-        // Scale these to between -1 and 1.
-        let rand1 = rand1 * 2.0 - 1.0;
-        let rand2 = rand2 * 2.0 - 1.0;
-        let rand3 = rand3 * 2.0 - 1.0;
-        let rand4 = rand4 * 2.0 - 1.0;
-
-        let x_signum = ctx.me().Physics.loc().x.signum();
-        let y_signum = ctx.me().Physics.loc().y.signum();
-
-        let approach: Box<dyn RoutePlanner> = if is_diagonal_kickoff(ctx) {
-            let straight_loc = Point2::new(
-                (500.0 + rand1 * 25.0) * x_signum,
-                (950.0 + rand2 * 25.0) * y_signum,
-            );
-            let straight =
-                GroundStraightPlanner::new(straight_loc, StraightMode::Asap).allow_dodging(false);
-            let turn_loc = Point2::new((100.0 + rand3 * 25.0) * x_signum, 0.0);
-            let turn = TurnPlanner::new(turn_loc, None);
-            Box::new(ChainedPlanner::chain(vec![
-                Box::new(straight),
-                Box::new(turn),
-            ]))
-        } else if is_off_center_kickoff(ctx) {
-            let target_loc = Point2::new(
-                (100.0 + rand1 * 10.0) * x_signum,
-                (2500.0 + rand2 * 25.0) * y_signum,
-            );
-            Box::new(
-                GroundStraightPlanner::new(target_loc, StraightMode::Asap).allow_dodging(false),
-            )
-        } else {
-            // This is basically a nop since the segment runs with `StraightMode::Fake`.
-            Box::new(GroundIntercept::new().allow_dodging(false))
-        };
+        match kickoff_role(ctx) {
+            KickoffRole::Go => {}
+            KickoffRole::CheatUp => return Action::tail_call(cheat_up(ctx)),
+            KickoffRole::GrabBoost => return Action::tail_call(grab_corner_boost(ctx)),
+        }
 
-        Action::tail_call(Chain::new(Priority::Idle, vec![
-            Box::new(FollowRoute::new_boxed(approach)),
-            Box::new(KickoffStrike::new(rand4 * 25.0)),
+        // Add a random component to kickoffs, to keep things unpredictable.
+        let rand1 = ctx.rng.gen_range(-1.0, 1.0);
+        let rand2 = ctx.rng.gen_range(-1.0, 1.0);
+        let rand3 = ctx.rng.gen_range(-1.0, 1.0);
+        let rand4 = ctx.rng.gen_range(-1.0, 1.0);
+
+        // The speed flip gets there sooner, but it's picky about how we start.
+        // If it can't be planned, drive the whole way like we used to.
+        let car_loc = ctx.me().Physics.loc_2d();
+        let rand = [rand1, rand2, rand3];
+        Action::tail_call(TryChoose::new(Priority::Idle, vec![
+            Box::new(Chain::new(Priority::Idle, vec![
+                Box::new(
+                    FollowRoute::new_boxed(kickoff_approach(car_loc, rand, true))
+                        .never_recover(true),
+                ),
+                Box::new(KickoffStrike::new(rand4 * 25.0)),
+            ])),
+            Box::new(Chain::new(Priority::Idle, vec![
+                Box::new(FollowRoute::new_boxed(kickoff_approach(
+                    car_loc, rand, false,
+                ))),
+                Box::new(KickoffStrike::new(rand4 * 25.0)),
+            ])),
         ]))
     }
 }
 
-fn is_off_center_kickoff(ctx: &mut Context<'_>) -> bool {
-    let car_x = ctx.me().Physics.loc().x;
-    (car_x.abs() - 256.0).abs() < 50.0
+/// The route from the kickoff spot at `car_loc` to the ball, optionally
+/// opening with a speed flip. `rand` nudges the waypoints around.
+fn kickoff_approach(
+    car_loc: Point2<f32>,
+    rand: [f32; 3],
+    speed_flip: bool,
+) -> Box<dyn RoutePlanner> {
+    let [rand1, rand2, rand3] = rand;
+    let x_signum = car_loc.x.signum();
+    let y_signum = car_loc.y.signum();
+
+    let mut planners: Vec<Box<dyn RoutePlanner>> = Vec::new();
+    let flip_loc = if is_diagonal_kickoff(car_loc) {
+        let straight_loc = Point2::new(
+            (500.0 + rand1 * 25.0) * x_signum,
+            (950.0 + rand2 * 25.0) * y_signum,
+        );
+        let turn_loc = Point2::new((100.0 + rand3 * 25.0) * x_signum, 0.0);
+        planners.push(Box::new(
+            GroundStraightPlanner::new(straight_loc, StraightMode::Asap).allow_dodging(false),
+        ));
+        planners.push(Box::new(TurnPlanner::new(turn_loc, None)));
+        straight_loc
+    } else if is_off_center_kickoff(car_loc) {
+        let target_loc = Point2::new(
+            (100.0 + rand1 * 10.0) * x_signum,
+            (2500.0 + rand2 * 25.0) * y_signum,
+        );
+        planners.push(Box::new(
+            GroundStraightPlanner::new(target_loc, StraightMode::Asap).allow_dodging(false),
+        ));
+        target_loc
+    } else {
+        // This is basically a nop since the segment runs with `StraightMode::Fake`.
+        planners.push(Box::new(GroundIntercept::new().allow_dodging(false)));
+        Point2::origin()
+    };
+
+    if speed_flip {
+        planners.insert(0, Box::new(GroundSpeedFlip::new(flip_loc)));
+    }
+    Box::new(ChainedPlanner::chain(planners))
+}
+
+/// What a car should be doing during a kickoff, given where everyone on the
+/// team spawned.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum KickoffRole {
+    /// Go for the ball.
+    Go,
+    /// Drive up the middle and wait for the outcome of the 50/50.
+    CheatUp,
+    /// Pick up the nearest corner boost on our side.
+    GrabBoost,
+}
+
+/// Two spawns within this many uu of the ball are considered equidistant.
+const KICKOFF_TIE_TOLERANCE: f32 = 50.0;
+
+fn kickoff_role(ctx: &mut Context<'_>) -> KickoffRole {
+    let me_loc = ctx.me().Physics.loc_2d();
+    let team_locs: Vec<_> = ctx
+        .cars(ctx.game.team)
+        .map(|car| car.Physics.loc_2d())
+        .collect();
+    let attack_y_signum = ctx.game.enemy_goal().center_2d.y.signum();
+    let role = assign_kickoff_role(me_loc, &team_locs, attack_y_signum);
+    ctx.eeg
+        .log(name_of_type!(Kickoff), format!("kickoff role: {:?}", role));
+    role
+}
+
+/// Every car on the team runs this with the same inputs, so they all agree on
+/// who goes without having to communicate.
+///
+/// The closest car goes. If two cars are the same distance from the ball, the
+/// one on the left (from the perspective of a car facing the enemy goal) goes.
+/// `team_locs` must include `me_loc`.
+fn assign_kickoff_role(
+    me_loc: Point2<f32>,
+    team_locs: &[Point2<f32>],
+    attack_y_signum: f32,
+) -> KickoffRole {
+    let goes_before = |a: Point2<f32>, b: Point2<f32>| {
+        let a_dist = (a - Point2::origin()).norm();
+        let b_dist = (b - Point2::origin()).norm();
+        if (a_dist - b_dist).abs() < KICKOFF_TIE_TOLERANCE {
+            // RL's coordinates are left-handed, so for the blue team (attacking
+            // towards +y), left is +x.
+            let a_left = a.x * attack_y_signum;
+            let b_left = b.x * attack_y_signum;
+            a_left > b_left
+        } else {
+            a_dist < b_dist
+        }
+    };
+
+    let rank = team_locs
+        .iter()
+        .filter(|&&loc| goes_before(loc, me_loc))
+        .count();
+
+    if rank == 0 {
+        KickoffRole::Go
+    } else if me_loc.x.abs() >= 1000.0 {
+        // Diagonal spawns are right next to a corner boost.
+        KickoffRole::GrabBoost
+    } else if rank == 1 {
+        KickoffRole::CheatUp
+    } else {
+        KickoffRole::GrabBoost
+    }
+}
+
+fn cheat_up(ctx: &mut Context<'_>) -> impl Behavior {
+    // Roughly when the ball gets touched, so we show up just in time to pounce
+    // on it, facing up the field, without burning boost to get there early.
+    const ARRIVE_TIME: f32 = 2.5;
+
+    let own_goal_y = ctx.game.own_goal().center_2d.y;
+    let cheat_loc = Point2::new(0.0, own_goal_y * 0.45);
+    let upfield = Vector2::new(0.0, -own_goal_y).to_axis();
+    Chain::new(Priority::Idle, vec_box![
+        TryChoose::new(Priority::Idle, vec_box![
+            FollowRoute::new(
+                GroundArrive::new(cheat_loc, ARRIVE_TIME)
+                    .target_speed(0.0)
+                    .target_heading(upfield),
+            )
+            .never_recover(true),
+            FollowRoute::new(GroundDrive::new(cheat_loc)),
+        ]),
+        wait_for_kickoff_touch(),
+    ])
+}
+
+fn grab_corner_boost(ctx: &mut Context<'_>) -> impl Behavior {
+    let me_loc = ctx.me().Physics.loc_2d();
+    let own_goal_y = ctx.game.own_goal().center_2d.y;
+    let pickup = ctx
+        .game
+        .boost_dollars()
+        .iter()
+        .filter(|pickup| pickup.loc.y * own_goal_y > 0.0)
+        .min_by_key(|pickup| NotNan::new((pickup.loc - me_loc).norm()).unwrap())
+        .cloned();
+
+    let mut chain = Vec::<Box<dyn Behavior>>::new();
+    if let Some(pickup) = pickup {
+        let face = Point2::new(0.0, own_goal_y * 0.5);
+        chain.push(Box::new(FollowRoute::new(
+            GetDollar::new(face).pickup(&pickup).target_face(face),
+        )));
+    } else {
+        ctx.eeg.log(name_of_type!(Kickoff), "no corner boost found");
+    }
+    chain.push(Box::new(wait_for_kickoff_touch()));
+    Chain::new(Priority::Idle, chain)
+}
+
+/// Sit tight until someone touches the ball. Otherwise the kickoff would get
+/// forced again and we would re-plan our role from wherever we ended up.
+fn wait_for_kickoff_touch() -> impl Behavior {
+    While::new(
+        KickoffIsPending,
+        Yielder::new(9999.0, common::halfway_house::PlayerInput::default()),
+    )
+}
+
+struct KickoffIsPending;
+
+impl Predicate for KickoffIsPending {
+    fn name(&self) -> &str {
+        name_of_type!(KickoffIsPending)
+    }
+
+    fn evaluate(&mut self, ctx: &mut Context<'_>) -> bool {
+        PreKickoff::is_kickoff(&ctx.packet.GameBall)
+    }
+}
+
+fn is_off_center_kickoff(car_loc: Point2<f32>) -> bool {
+    (car_loc.x.abs() - 256.0).abs() < 50.0
 }
 
-fn is_diagonal_kickoff(ctx: &mut Context<'_>) -> bool {
-    let car_x = ctx.me().Physics.loc().x;
-    car_x.abs() >= 1000.0
+fn is_diagonal_kickoff(car_loc: Point2<f32>) -> bool {
+    car_loc.x.abs() >= 1000.0
 }
 
 struct KickoffStrike {
@@ -263,6 +436,154 @@ enum CommitAction {
     Chip,
 }
 
+#[cfg(test)]
+mod tests {
+    use crate::{
+        behavior::kickoff::{assign_kickoff_role, kickoff_approach, KickoffRole},
+        routing::{
+            models::{CarState, PlanningContext},
+            test::with_offline_planning_context,
+        },
+    };
+    use common::{prelude::*, rl};
+    use nalgebra::{Point2, Point3, UnitComplex, Vector3};
+
+    fn diagonal_left() -> Point2<f32> {
+        Point2::new(2048.0, -2560.0)
+    }
+
+    fn diagonal_right() -> Point2<f32> {
+        Point2::new(-2048.0, -2560.0)
+    }
+
+    fn off_center_left() -> Point2<f32> {
+        Point2::new(256.0, -3840.0)
+    }
+
+    fn off_center_right() -> Point2<f32> {
+        Point2::new(-256.0, -3840.0)
+    }
+
+    fn back_center() -> Point2<f32> {
+        Point2::new(0.0, -4608.0)
+    }
+
+    #[test]
+    fn solo_always_goes() {
+        let team = [back_center()];
+        assert_eq!(
+            assign_kickoff_role(back_center(), &team, 1.0),
+            KickoffRole::Go
+        );
+    }
+
+    #[test]
+    fn closest_goes() {
+        let team = [diagonal_right(), back_center()];
+        assert_eq!(
+            assign_kickoff_role(diagonal_right(), &team, 1.0),
+            KickoffRole::Go,
+        );
+        assert_eq!(
+            assign_kickoff_role(back_center(), &team, 1.0),
+            KickoffRole::CheatUp,
+        );
+    }
+
+    #[test]
+    fn left_goes_on_ties() {
+        let team = [diagonal_left(), diagonal_right()];
+        assert_eq!(
+            assign_kickoff_role(diagonal_left(), &team, 1.0),
+            KickoffRole::Go,
+        );
+        assert_eq!(
+            assign_kickoff_role(diagonal_right(), &team, 1.0),
+            KickoffRole::GrabBoost,
+        );
+
+        let team = [off_center_left(), off_center_right()];
+        assert_eq!(
+            assign_kickoff_role(off_center_left(), &team, 1.0),
+            KickoffRole::Go,
+        );
+        assert_eq!(
+            assign_kickoff_role(off_center_right(), &team, 1.0),
+            KickoffRole::CheatUp,
+        );
+    }
+
+    #[test]
+    fn left_is_relative_to_team() {
+        // Orange attacks towards -y, so their left is -x.
+        let right = Point2::new(2048.0, 2560.0);
+        let left = Point2::new(-2048.0, 2560.0);
+        let team = [left, right];
+        assert_eq!(assign_kickoff_role(left, &team, -1.0), KickoffRole::Go);
+        assert_eq!(
+            assign_kickoff_role(right, &team, -1.0),
+            KickoffRole::GrabBoost,
+        );
+    }
+
+    #[test]
+    fn three_v_three() {
+        let team = [diagonal_left(), off_center_right(), back_center()];
+        assert_eq!(
+            assign_kickoff_role(diagonal_left(), &team, 1.0),
+            KickoffRole::Go,
+        );
+        assert_eq!(
+            assign_kickoff_role(off_center_right(), &team, 1.0),
+            KickoffRole::CheatUp,
+        );
+        assert_eq!(
+            assign_kickoff_role(back_center(), &team, 1.0),
+            KickoffRole::GrabBoost,
+        );
+    }
+
+    /// Plan the whole approach from a kickoff spot, and return the name of the
+    /// first segment and how long it takes to reach the ball.
+    fn plan_approach(spot: Point2<f32>, speed_flip: bool) -> (String, f32) {
+        let yaw = (-spot.y).atan2(-spot.x);
+        let start = CarState {
+            loc: spot.to_3d(rl::OCTANE_NEUTRAL_Z),
+            rot: UnitComplex::new(yaw).around_z_axis(),
+            vel: Vector3::zeros(),
+            boost: 33.0,
+        };
+        let ball_loc = Point3::new(0.0, 0.0, rl::BALL_RADIUS);
+        with_offline_planning_context(&start, ball_loc, Vector3::zeros(), |ctx| {
+            let planner = kickoff_approach(spot, [0.0; 3], speed_flip);
+            let (plan, _log) = PlanningContext::plan_2(&*planner, ctx)
+                .unwrap_or_else(|err| panic!("{:?}", err.error));
+            let tail = plan
+                .provisional_expand_2(ctx.game, ctx.ball_prediction)
+                .unwrap_or_else(|err| panic!("{:?}", err.error));
+            let duration =
+                plan.segment.duration() + tail.iter().map(|(_, s)| s.duration()).sum::<f32>();
+            (plan.segment.name().to_string(), duration)
+        })
+    }
+
+    #[test]
+    fn speed_flip_beats_old_kickoff() {
+        for &spot in &[
+            diagonal_left(),
+            diagonal_right(),
+            off_center_left(),
+            off_center_right(),
+            back_center(),
+        ] {
+            let (first, flip_time) = plan_approach(spot, true);
+            let (_, drive_time) = plan_approach(spot, false);
+            assert_eq!(first, "SpeedFlip", "{:?}", spot);
+            assert!(flip_time < drive_time, "{:?}", spot);
+        }
+    }
+}
+
 #[cfg(test)]
 mod integration_tests {
     use crate::{
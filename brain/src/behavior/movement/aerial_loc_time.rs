@@ -1,11 +1,204 @@
+use crate::{
+    eeg::Drawable,
+    strategy::{Action, Behavior, Context},
+    utils::Stopwatch,
+};
+use common::{prelude::*, rl};
+use nalgebra::{Point3, Unit, Vector3};
+use nameof::name_of_type;
+use simulate::car_aerial::{self, AerialPlan, AerialPlanError};
+use std::f32::consts::PI;
+
+/// Take off from the ground and fly to a location in the air, arriving at a
+/// specific time.
+pub struct AerialLocTime {
+    target_loc: Point3<f32>,
+    duration: f32,
+    /// Whether we start on the ground and need to jump first.
+    takeoff: bool,
+    flight: Option<Flight>,
+    time: Stopwatch,
+}
+
+struct Flight {
+    /// Absolute game time.
+    arrival_time: f32,
+    plan: AerialPlan,
+}
+
+impl AerialLocTime {
+    /// Only boost when the nose is within this angle of where we want to go.
+    const BOOST_ANGLE: f32 = PI / 9.0;
+    /// Below this, the remaining error is small enough that boosting would do
+    /// more harm than good.
+    const MIN_BOOST_ACCEL: f32 = rl::CAR_AIR_BOOST_ACCEL * 0.25;
+
+    /// Arrive at `target_loc` `duration` seconds after the behavior starts.
+    pub fn new(target_loc: Point3<f32>, duration: f32) -> Self {
+        Self {
+            target_loc,
+            duration,
+            takeoff: true,
+            flight: None,
+            time: Stopwatch::new(),
+        }
+    }
+
+    /// Like `new`, but for a car that is already flying, e.g. after a first
+    /// touch.
+    pub fn airborne(target_loc: Point3<f32>, duration: f32) -> Self {
+        Self {
+            takeoff: false,
+            ..Self::new(target_loc, duration)
+        }
+    }
+
+    /// Can `car` get to `target_loc` in `duration` seconds from now?
+    pub fn feasible(
+        car: &common::halfway_house::PlayerInfo,
+        target_loc: Point3<f32>,
+        duration: f32,
+    ) -> Result<AerialPlan, AerialPlanError> {
+        if !car.OnGround {
+            return Err(AerialPlanError::NotOnFlatGround);
+        }
+        car_aerial::plan_aerial(
+            car.Physics.loc(),
+            car.Physics.vel(),
+            car.Physics.quat(),
+            car.Boost as f32,
+            target_loc,
+            duration,
+        )
+    }
+
+    /// Like `feasible`, but for a car that is already in the air.
+    pub fn feasible_airborne(
+        car: &common::halfway_house::PlayerInfo,
+        target_loc: Point3<f32>,
+        duration: f32,
+    ) -> Result<AerialPlan, AerialPlanError> {
+        car_aerial::plan_airborne(
+            car.Physics.loc(),
+            car.Physics.vel(),
+            car.Physics.quat(),
+            car.Boost as f32,
+            target_loc,
+            duration,
+        )
+    }
+
+    pub fn target_loc(&self) -> Point3<f32> {
+        self.target_loc
+    }
+
+    /// Nudge the target mid-flight, e.g. to follow a ball whose prediction
+    /// shifted. The arrival time stays the same.
+    pub fn retarget(&mut self, target_loc: Point3<f32>) {
+        self.target_loc = target_loc;
+    }
+}
+
+impl Behavior for AerialLocTime {
+    fn name(&self) -> &str {
+        name_of_type!(AerialLocTime)
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        let (ctx, eeg) = ctx.split();
+        let now = ctx.packet.GameInfo.TimeSeconds;
+        let elapsed = self.time.tick(now);
+
+        if self.flight.is_none() {
+            let plan = if self.takeoff {
+                Self::feasible(ctx.me(), self.target_loc, self.duration)
+            } else {
+                Self::feasible_airborne(ctx.me(), self.target_loc, self.duration)
+            };
+            let plan = match plan {
+                Ok(plan) => plan,
+                Err(err) => {
+                    eeg.log(self.name(), format!("infeasible: {:?}", err));
+                    return Action::Abort;
+                }
+            };
+            eeg.log(
+                self.name(),
+                format!("planned boost use: {:.0}", plan.boost_used),
+            );
+            self.flight = Some(Flight {
+                arrival_time: now + self.duration,
+                plan,
+            });
+        }
+        let flight = self.flight.as_ref().unwrap();
+
+        let time_remaining = flight.arrival_time - now;
+        if time_remaining <= 0.0 {
+            return Action::Return;
+        }
+
+        let me = ctx.me();
+        if elapsed >= 0.1 && me.OnGround {
+            eeg.log(self.name(), "I thought I jumped");
+            return Action::Abort;
+        }
+
+        // During the jump, go with the plan. After that, keep correcting for wherever
+        // we actually ended up.
+        let jump = elapsed < flight.plan.jump_duration;
+        let accel = if jump {
+            flight.plan.boost_direction.into_inner() * flight.plan.required_accel
+        } else {
+            car_aerial::steering_accel(
+                me.Physics.loc(),
+                me.Physics.vel(),
+                self.target_loc,
+                time_remaining,
+            )
+        };
+
+        // With nothing left to correct, point the nose at the target so we're ready
+        // for whatever happens there.
+        let forward = Unit::try_new(accel, Self::MIN_BOOST_ACCEL)
+            .or_else(|| Unit::try_new(self.target_loc - me.Physics.loc(), 1e-3))
+            .unwrap_or_else(|| me.Physics.forward_axis());
+        let roof = me.Physics.roof_axis().into_inner();
+        let up = Unit::try_new(
+            roof - forward.into_inner() * roof.dot(&forward.into_inner()),
+            1e-3,
+        )
+        .unwrap_or_else(Vector3::z_axis);
+        let (pitch, yaw, roll) = dom::get_pitch_yaw_roll(me, forward, up);
+
+        let aligned = me.Physics.forward_axis().angle_to(&forward) < Self::BOOST_ANGLE;
+        let boost = aligned && accel.norm() >= Self::MIN_BOOST_ACCEL;
+
+        eeg.draw(Drawable::GhostCar(self.target_loc, me.Physics.rot()));
+        eeg.print_time("time_remaining", time_remaining);
+        eeg.print_value("accel", accel);
+
+        Action::Yield(common::halfway_house::PlayerInput {
+            Pitch: pitch,
+            Yaw: yaw,
+            Roll: roll,
+            Jump: jump,
+            Boost: boost,
+            ..Default::default()
+        })
+    }
+}
+
 #[cfg(test)]
 mod integration_tests {
-    use crate::integration_tests::{TestRunner, TestScenario};
+    use crate::{
+        behavior::movement::AerialLocTime,
+        integration_tests::{TestRunner, TestScenario},
+    };
     use common::prelude::*;
     use nalgebra::Point3;
 
     #[test]
-    #[ignore = "TODO"]
     fn simple() {
         let expected_loc = Point3::new(400.0, 1100.0, 600.0);
         let test = TestRunner::new()
@@ -13,6 +206,7 @@ mod integration_tests {
                 ball_loc: Point3::new(1000.0, 0.0, 0.0),
                 ..Default::default()
             })
+            .behavior(AerialLocTime::new(expected_loc, 3.0))
             .run_for_millis(3000);
 
         let packet = test.sniff_packet();
@@ -141,8 +141,9 @@ fn jump_down_from_the_wall(ctx: &mut Context<'_>) -> Action {
             },
         )));
         // Press jump.
+        let (jump_hold, jump_release) = (0.2, 0.1);
         inputs.push(Box::new(YieldAndMaybePanicBoost::new(
-            0.2,
+            jump_hold,
             common::halfway_house::PlayerInput {
                 Pitch: 1.0,
                 Jump: true,
@@ -151,16 +152,24 @@ fn jump_down_from_the_wall(ctx: &mut Context<'_>) -> Action {
         )));
         // Release jump.
         inputs.push(Box::new(YieldAndMaybePanicBoost::new(
-            0.1,
+            jump_release,
             common::halfway_house::PlayerInput {
                 Pitch: 1.0,
                 Jump: false,
                 ..Default::default()
             },
         )));
-        // Maybe dodge.
-        if let Some(target_loc) = dodge_target(ctx) {
-            inputs.push(Box::new(Dodge::new().towards(target_loc)));
+        // Maybe dodge. Otherwise, save the dodge for a wavedash when we land.
+        match dodge_target(ctx) {
+            Some(target_loc) => inputs.push(Box::new(Dodge::new().towards(target_loc))),
+            None => {
+                let ball_loc = ctx.scenario.ball_prediction().at_time_or_last(2.0).loc;
+                let direction = (ball_loc.to_2d() - me.Physics.loc_2d())
+                    .to_3d(0.0)
+                    .to_axis();
+                let flip_time_left = rl::CAR_DODGE_TIME_LIMIT - jump_hold - jump_release;
+                inputs.push(Box::new(Land::new().wavedash(direction, flip_time_left)));
+            }
         }
 
         Action::tail_call(Chain::new(Priority::Idle, inputs))
@@ -0,0 +1,104 @@
+use crate::{
+    strategy::{Action, Behavior, Context, Priority},
+    utils::Stopwatch,
+};
+use common::prelude::*;
+use nameof::name_of_type;
+use simulate::{CarHalfFlip, CarHalfFlip1D};
+
+/// Turn around by backflipping, cancelling the flip halfway, and rolling back
+/// upright.
+pub struct HalfFlip {
+    roll_right: bool,
+    script: Option<CarHalfFlip1D>,
+    time: Stopwatch,
+}
+
+impl HalfFlip {
+    /// Don't wait forever for the wheels to touch down.
+    const MAX_LANDING_TIME: f32 = 1.0;
+
+    pub fn new() -> Self {
+        Self {
+            roll_right: true,
+            script: None,
+            time: Stopwatch::new(),
+        }
+    }
+
+    pub fn roll_right(mut self, roll_right: bool) -> Self {
+        self.roll_right = roll_right;
+        self
+    }
+}
+
+impl Behavior for HalfFlip {
+    fn name(&self) -> &str {
+        name_of_type!(HalfFlip)
+    }
+
+    fn priority(&self) -> Priority {
+        Priority::Force
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        if self.script.is_none() {
+            if !ctx.me().OnGround {
+                ctx.eeg.log(self.name(), "wheels must be on ground");
+                return Action::Abort;
+            }
+            let me = ctx.me();
+            let speed = me
+                .Physics
+                .vel()
+                .dot(&me.Physics.forward_axis().into_inner());
+            self.script = Some(CarHalfFlip::calc_1d(speed));
+        }
+        let script = self.script.as_ref().unwrap();
+
+        let elapsed = self.time.tick(ctx.packet.GameInfo.TimeSeconds);
+        let wait_start = script.jump_duration;
+        let flip_start = wait_start + script.wait_duration;
+        let cancel_start = flip_start + script.flip_duration;
+        let landing_start = cancel_start + script.cancel_duration;
+
+        if elapsed < wait_start {
+            Action::Yield(common::halfway_house::PlayerInput {
+                Jump: true,
+                ..Default::default()
+            })
+        } else if elapsed < flip_start {
+            Action::Yield(Default::default())
+        } else if elapsed < cancel_start {
+            if ctx.me().OnGround {
+                ctx.eeg.log(self.name(), "goomba stomped?");
+                return Action::Abort;
+            }
+            // Backflip.
+            Action::Yield(common::halfway_house::PlayerInput {
+                Pitch: 1.0,
+                Jump: true,
+                ..Default::default()
+            })
+        } else if elapsed < landing_start {
+            // Stop the flip with the nose pointing up, then roll over so the
+            // wheels face the ground.
+            Action::Yield(common::halfway_house::PlayerInput {
+                Pitch: -1.0,
+                Roll: if self.roll_right { 1.0 } else { -1.0 },
+                Throttle: 1.0,
+                ..Default::default()
+            })
+        } else if ctx.me().OnGround {
+            Action::Return
+        } else if elapsed >= landing_start + Self::MAX_LANDING_TIME {
+            ctx.eeg.log(self.name(), "never landed");
+            Action::Abort
+        } else {
+            Action::Yield(common::halfway_house::PlayerInput {
+                Throttle: 1.0,
+                ..Default::default()
+            })
+        }
+    }
+}
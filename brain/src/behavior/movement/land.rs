@@ -4,9 +4,13 @@ use crate::{
     strategy::{Action, Behavior, Context},
     utils::geometry::Plane,
 };
-use common::{kinematics::kinematic, prelude::*, rl};
+use common::{
+    kinematics::{kinematic, kinematic_time},
+    prelude::*,
+    rl,
+};
 use derive_new::new;
-use nalgebra::{Unit, Vector2, Vector3};
+use nalgebra::{Point3, Unit, Vector2, Vector3};
 use nameof::name_of_type;
 use std::f32::consts::PI;
 
@@ -14,6 +18,32 @@ use std::f32::consts::PI;
 pub struct Land {
     #[new(value = "false")]
     chatted: bool,
+    #[new(default)]
+    wavedash: Option<Unit<Vector3<f32>>>,
+    #[new(default)]
+    flip_time_left: f32,
+    #[new(default)]
+    flip_deadline: Option<f32>,
+}
+
+impl Land {
+    /// Land nose-up so the rear wheels touch first.
+    const WAVEDASH_TILT: f32 = PI / 12.0;
+    /// Press the dodge this long before the predicted contact, so it's still
+    /// going when the wheels touch.
+    const WAVEDASH_LEAD_TIME: f32 = 3.0 / 120.0;
+    /// If we're tilted this far off the setup orientation, a dodge would just
+    /// flip us onto our roof.
+    const WAVEDASH_MAX_ROOF_ERROR: f32 = PI / 6.0;
+
+    /// If we still have a dodge, use it just before touching down to gain
+    /// speed along `direction`. `flip_time_left` is how much longer the dodge
+    /// lasts, i.e. `rl::CAR_DODGE_TIME_LIMIT` minus the time since we jumped.
+    pub fn wavedash(mut self, direction: Unit<Vector3<f32>>, flip_time_left: f32) -> Self {
+        self.wavedash = Some(direction);
+        self.flip_time_left = flip_time_left;
+        self
+    }
 }
 
 impl Behavior for Land {
@@ -57,10 +87,27 @@ impl Behavior for Land {
             });
         }
 
-        ctx.eeg.draw(Drawable::print("air rolling", color::GREEN));
-
         let (plane, landing_time) = find_landing_plane(ctx);
-        ctx.eeg.print_value("plane", plane.normal);
+        let surface = ramp_aware_plane(ctx, plane, landing_time);
+        ctx.eeg.print_value("plane", surface.normal);
+
+        if let Some(direction) = self.wavedash {
+            // Count down from the first frame we see, since that's when
+            // `flip_time_left` was measured.
+            let now = ctx.packet.GameInfo.TimeSeconds;
+            let flip_deadline = *self.flip_deadline.get_or_insert(now + self.flip_time_left);
+            let flip_time_left = flip_deadline - now;
+            if !me.DoubleJumped && !panic_boost {
+                if let Some(action) =
+                    self.wavedash_landing(ctx, plane, &surface, direction, flip_time_left)
+                {
+                    return action;
+                }
+            }
+        }
+
+        ctx.eeg.draw(Drawable::print("air rolling", color::GREEN));
+        let plane = &surface;
         let want_to_boost_down = me.Boost > 0 && landing_time >= 0.6 && !panic_boost;
 
         // Point the nose of the car along the surface we're landing on.
@@ -109,6 +156,67 @@ impl Behavior for Land {
 }
 
 impl Land {
+    /// Line up the landing, then dodge right before the wheels touch.
+    ///
+    /// Returns `None` if a wavedash isn't possible, in which case we should
+    /// just land normally.
+    fn wavedash_landing(
+        &self,
+        ctx: &mut Context<'_>,
+        plane: &Plane,
+        surface: &Plane,
+        direction: Unit<Vector3<f32>>,
+        flip_time_left: f32,
+    ) -> Option<Action> {
+        let me = ctx.me();
+        let contact_time = contact_time(plane, &me.Physics.loc(), &me.Physics.vel())?;
+        if contact_time - Self::WAVEDASH_LEAD_TIME >= flip_time_left {
+            ctx.eeg
+                .log(self.name(), "the dodge runs out before touchdown");
+            return None;
+        }
+        let normal = surface.normal;
+        let forward = Unit::try_new(surface.project_vector(&direction), 1e-3)?;
+
+        let (sin, cos) = Self::WAVEDASH_TILT.sin_cos();
+        let target_forward =
+            Unit::new_normalize(forward.into_inner() * cos + normal.into_inner() * sin);
+        let target_up = Unit::new_normalize(normal.into_inner() * cos - forward.into_inner() * sin);
+
+        ctx.eeg.print_time("wavedash_contact_time", contact_time);
+
+        if contact_time <= Self::WAVEDASH_LEAD_TIME {
+            let roof_error = me.Physics.roof_axis().angle(&target_up.into_inner());
+            if roof_error >= Self::WAVEDASH_MAX_ROOF_ERROR {
+                ctx.eeg.log(self.name(), "not lined up for a wavedash");
+                return None;
+            }
+
+            // Dodge in `direction`, relative to wherever the nose ended up.
+            let angle = forward
+                .dot(&me.Physics.right_axis().into_inner())
+                .atan2(forward.dot(&me.Physics.forward_axis().into_inner()));
+            ctx.eeg.log(self.name(), "wavedash");
+            return Some(Action::Yield(common::halfway_house::PlayerInput {
+                Pitch: -angle.cos(),
+                Yaw: angle.sin(),
+                Jump: true,
+                ..Default::default()
+            }));
+        }
+
+        ctx.eeg
+            .draw(Drawable::print("setting up wavedash", color::GREEN));
+        let (pitch, yaw, roll) = dom::get_pitch_yaw_roll(me, target_forward, target_up);
+        Some(Action::Yield(common::halfway_house::PlayerInput {
+            Throttle: 1.0,
+            Pitch: pitch,
+            Yaw: yaw,
+            Roll: roll,
+            ..Default::default()
+        }))
+    }
+
     /// How far deep in enemy territory are we?
     pub(super) fn defensiveness(ctx: &mut Context<'_>) -> f32 {
         let safe = ctx.game.own_back_wall_center();
@@ -189,6 +297,34 @@ fn find_landing_plane<'ctx>(ctx: &mut Context<'ctx>) -> (&'ctx Plane, f32) {
     (ctx.game.pitch().ground(), 2.0)
 }
 
+/// When the car's wheels will touch `plane`, assuming freefall.
+fn contact_time(plane: &Plane, loc: &Point3<f32>, vel: &Vector3<f32>) -> Option<f32> {
+    let height = plane.distance_to_point(loc) - rl::OCTANE_NEUTRAL_Z;
+    let speed = plane.normal.dot(vel);
+    let accel = plane.normal.dot(&(Vector3::z() * rl::GRAVITY));
+    if accel.abs() < 1e-3 {
+        // Gravity runs parallel to walls, and the quadratic falls apart.
+        return if speed < 0.0 {
+            Some(height / -speed)
+        } else {
+            None
+        };
+    }
+    kinematic_time(-height, speed, accel)
+}
+
+/// If we're coming down near where two surfaces meet, orient to the ramp
+/// between them instead of either flat surface.
+fn ramp_aware_plane(ctx: &mut Context<'_>, plane: &Plane, landing_time: f32) -> Plane {
+    let (loc, _vel) = kinematic(
+        ctx.me().Physics.vel(),
+        Vector3::z() * rl::GRAVITY,
+        landing_time,
+    );
+    let loc = plane.project_point(&(ctx.me().Physics.loc() + loc));
+    Plane::point_normal(loc, ctx.game.pitch().surface_normal(&loc))
+}
+
 impl Land {
     pub fn panic_retreat_boost(ctx: &mut Context<'_>) -> bool {
         let own_goal_loc = ctx.game.own_goal().center_2d;
@@ -245,3 +381,71 @@ mod demo {
             .run_for_millis(3000);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{behavior::movement::land::contact_time, strategy::pitch::DFH_STADIUM};
+    use common::rl;
+    use nalgebra::{Point3, Vector3};
+
+    #[test]
+    fn contact_time_floor() {
+        let loc = Point3::new(0.0, 0.0, rl::OCTANE_NEUTRAL_Z + 100.0);
+        let time = contact_time(DFH_STADIUM.ground(), &loc, &Vector3::zeros()).unwrap();
+        assert!((time - (200.0 / -rl::GRAVITY).sqrt()).abs() < 0.001);
+
+        // Rising, so it goes up and comes back down first.
+        let vel = Vector3::new(0.0, 0.0, 300.0);
+        assert!(contact_time(DFH_STADIUM.ground(), &loc, &vel).unwrap() > time);
+    }
+
+    #[test]
+    fn contact_time_wall() {
+        let loc = Point3::new(rl::FIELD_MAX_X - rl::OCTANE_NEUTRAL_Z - 100.0, 0.0, 500.0);
+        let wall = DFH_STADIUM.closest_plane(&Point3::new(rl::FIELD_MAX_X, 0.0, 500.0));
+
+        // Gravity runs along the wall, so only the speed toward it counts.
+        let vel = Vector3::new(1000.0, 0.0, -200.0);
+        let time = contact_time(wall, &loc, &vel).unwrap();
+        assert!((time - 0.1).abs() < 0.001);
+
+        let away = Vector3::new(-1000.0, 0.0, -200.0);
+        assert!(contact_time(wall, &loc, &away).is_none());
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        behavior::movement::GetToFlatGround,
+        integration_tests::{TestRunner, TestScenario},
+    };
+    use common::{prelude::*, rl};
+    use nalgebra::{Point3, Rotation3, Vector3};
+    use std::f32::consts::PI;
+
+    #[test]
+    fn wavedash_off_the_wall() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                // Close enough that we save the dodge for a wavedash.
+                ball_loc: Point3::new(2500.0, 0.0, rl::BALL_RADIUS),
+                car_loc: Point3::new(rl::FIELD_MAX_X - rl::OCTANE_NEUTRAL_Z, 0.0, 400.0),
+                car_rot: Rotation3::from_unreal_angles(PI / 2.0, 0.0, 0.0),
+                car_vel: Vector3::zeros(),
+                ..Default::default()
+            })
+            .behavior(GetToFlatGround::new())
+            .run();
+
+        test.sleep_millis(600);
+        let packet = test.sniff_packet();
+        assert!(!packet.GameCars[0].OnGround);
+        let air_speed = packet.GameCars[0].Physics.vel_2d().norm();
+
+        test.sleep_millis(1200);
+        let packet = test.sniff_packet();
+        assert!(packet.GameCars[0].OnGround);
+        assert!(packet.GameCars[0].Physics.vel_2d().norm() >= air_speed + 300.0);
+    }
+}
@@ -1,27 +1,31 @@
 pub use self::{
+    aerial_loc_time::AerialLocTime,
     blitz_to_location::BlitzToLocation,
     dodge::Dodge,
     drive_towards::{drive_towards, DriveTowards},
     get_to_flat_ground::GetToFlatGround,
+    half_flip::HalfFlip,
     jump_and_turn::JumpAndTurn,
     land::Land,
     quick_jump_and_dodge::QuickJumpAndDodge,
-    simple_steer_towards::{simple_steer_towards, simple_yaw_diff},
+    simple_steer_towards::{simple_reverse_steer_towards, simple_steer_towards, simple_yaw_diff},
     skid_recover::SkidRecover,
+    speed_flip::SpeedFlip,
     yielder::Yielder,
 };
 
-#[cfg(test)]
 mod aerial_loc_time;
 mod blitz_to_location;
 mod dodge;
 mod drive_towards;
 mod get_to_flat_ground;
+mod half_flip;
 mod jump_and_turn;
 mod land;
 mod quick_jump_and_dodge;
 mod simple_steer_towards;
 mod skid_recover;
+mod speed_flip;
 #[cfg(test)]
 mod wall_drive;
 mod yielder;
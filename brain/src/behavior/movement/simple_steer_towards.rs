@@ -1,11 +1,24 @@
 use crate::utils::geometry::ExtendF32;
 use common::prelude::*;
 use nalgebra::Point2;
+use std::f32::consts::PI;
 
 pub fn simple_steer_towards(car: &common::halfway_house::Physics, target_loc: Point2<f32>) -> f32 {
     simple_yaw_diff(car, target_loc).max(-1.0).min(1.0) * 2.0
 }
 
+/// Like `simple_steer_towards`, but for backing up: swing the rear of the car
+/// towards the target.
+pub fn simple_reverse_steer_towards(
+    car: &common::halfway_house::Physics,
+    target_loc: Point2<f32>,
+) -> f32 {
+    // Measure from the rear of the car, and flip the steering since going
+    // backwards the car yaws the opposite way it's steered.
+    let yaw_diff = (simple_yaw_diff(car, target_loc) + PI).normalize_angle();
+    -yaw_diff.max(-1.0).min(1.0) * 2.0
+}
+
 pub fn simple_yaw_diff(car: &common::halfway_house::Physics, target_loc: Point2<f32>) -> f32 {
     let target_yaw = car.loc_2d().negated_difference_and_angle_to(target_loc);
     (target_yaw - car.rot().yaw()).normalize_angle()
@@ -0,0 +1,107 @@
+use crate::{
+    strategy::{Action, Behavior, Context, Priority},
+    utils::Stopwatch,
+};
+use common::prelude::*;
+use nameof::name_of_type;
+use simulate::{CarSpeedFlip, CarSpeedFlip1D};
+use std::f32::consts::PI;
+
+/// A diagonal dodge that's cancelled right away, so the car keeps boosting
+/// nose-forward through the whole flip.
+pub struct SpeedFlip {
+    dodge_right: bool,
+    script: Option<CarSpeedFlip1D>,
+    time: Stopwatch,
+}
+
+impl SpeedFlip {
+    const DODGE_ANGLE: f32 = PI / 5.0;
+    /// The dodge input only needs to register for a few ticks before the
+    /// cancel takes over.
+    const DODGE_TIME: f32 = 4.0 / 120.0;
+    const MAX_LANDING_TIME: f32 = 0.5;
+
+    pub fn new() -> Self {
+        Self {
+            dodge_right: true,
+            script: None,
+            time: Stopwatch::new(),
+        }
+    }
+
+    pub fn dodge_right(mut self, dodge_right: bool) -> Self {
+        self.dodge_right = dodge_right;
+        self
+    }
+}
+
+impl Behavior for SpeedFlip {
+    fn name(&self) -> &str {
+        name_of_type!(SpeedFlip)
+    }
+
+    fn priority(&self) -> Priority {
+        Priority::Force
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        if self.script.is_none() {
+            if !ctx.me().OnGround {
+                ctx.eeg.log(self.name(), "wheels must be on ground");
+                return Action::Abort;
+            }
+            let me = ctx.me();
+            let speed = me
+                .Physics
+                .vel()
+                .dot(&me.Physics.forward_axis().into_inner());
+            self.script = Some(CarSpeedFlip::calc_1d(speed));
+        }
+        let script = self.script.as_ref().unwrap();
+
+        let elapsed = self.time.tick(ctx.packet.GameInfo.TimeSeconds);
+        let wait_start = script.jump_duration;
+        let dodge_start = wait_start + script.wait_duration;
+        let cancel_start = dodge_start + Self::DODGE_TIME;
+        let landing_start = dodge_start + script.flight_duration;
+        let side = if self.dodge_right { 1.0 } else { -1.0 };
+
+        let input = if elapsed < wait_start {
+            common::halfway_house::PlayerInput {
+                Jump: true,
+                ..Default::default()
+            }
+        } else if elapsed < dodge_start {
+            Default::default()
+        } else if elapsed < cancel_start {
+            common::halfway_house::PlayerInput {
+                Pitch: -Self::DODGE_ANGLE.cos(),
+                Yaw: Self::DODGE_ANGLE.sin() * side,
+                Jump: true,
+                ..Default::default()
+            }
+        } else if elapsed < landing_start {
+            // Cancel the forward rotation and counter the roll, so we land on
+            // our wheels still pointed the same way.
+            common::halfway_house::PlayerInput {
+                Pitch: 1.0,
+                Roll: -side,
+                ..Default::default()
+            }
+        } else if ctx.me().OnGround {
+            return Action::Return;
+        } else if elapsed >= landing_start + Self::MAX_LANDING_TIME {
+            ctx.eeg.log(self.name(), "never landed");
+            return Action::Abort;
+        } else {
+            Default::default()
+        };
+
+        Action::Yield(common::halfway_house::PlayerInput {
+            Throttle: 1.0,
+            Boost: true,
+            ..input
+        })
+    }
+}
@@ -0,0 +1,326 @@
+use crate::{
+    behavior::{
+        movement::{drive_towards, AerialLocTime},
+        strike::{AerialShot, GroundedHitTarget},
+    },
+    eeg::Drawable,
+    helpers::ball::BallFrame,
+    strategy::{Action, Behavior, Context, Context2, Game, Priority, Scenario},
+    utils::Stopwatch,
+};
+use common::{prelude::*, rl};
+use nalgebra::Point2;
+use nameof::name_of_type;
+
+/// Read a ball that's about to come off the enemy backboard. Either wait under
+/// the rebound and aerial to it, or (with enough boost) touch it into the
+/// backboard first and follow it up in the air for a double tap.
+pub struct BackboardRead {
+    double_tap: bool,
+}
+
+impl BackboardRead {
+    /// Don't read further ahead than this; the prediction gets too shaky.
+    const MAX_TIME: f32 = 5.0;
+    /// Only count it as a backboard bounce if the ball clears the crossbar.
+    const MIN_BOUNCE_Z: f32 = rl::CROSSBAR_Z + rl::BALL_RADIUS;
+    /// How close to the back wall the ball needs to get to count as a bounce.
+    const BOUNCE_DISTANCE: f32 = rl::BALL_RADIUS + 20.0;
+    /// Let the ball come this far off the wall before trying to meet it.
+    const MIN_REBOUND_DISTANCE: f32 = 300.0;
+    /// A rough average speed for getting into position under the rebound.
+    const POSITION_SPEED: f32 = 1800.0;
+    /// Boost to keep in reserve after the first touch, for the second.
+    const DOUBLE_TAP_BOOST: f32 = 30.0;
+
+    fn new(double_tap: bool) -> Self {
+        Self { double_tap }
+    }
+
+    /// Returns `Ok` if the ball is going to come off the backboard somewhere
+    /// we can meet it.
+    pub fn choose(ctx: &Context<'_>) -> Result<Self, &'static str> {
+        let me = ctx.me();
+        if !me.OnGround {
+            return Err("not on the ground");
+        }
+
+        let bounce = bounce(ctx.game, &ctx.scenario).ok_or("not headed for the backboard")?;
+
+        if let Some(first_touch) = AerialShot::viable_shot(ctx.game, &ctx.scenario, me) {
+            if first_touch.time < bounce.t
+                && me.Boost as f32 >= first_touch.plan.boost_used + Self::DOUBLE_TAP_BOOST
+            {
+                return Ok(Self::new(true));
+            }
+        }
+
+        let rebound = rebound(ctx.game, &ctx.scenario, bounce).ok_or("no rebound to play")?;
+        let wait_loc = wait_loc(ctx.game, rebound);
+        let travel_time = (wait_loc - me.Physics.loc_2d()).norm() / Self::POSITION_SPEED;
+        if travel_time >= rebound.t {
+            return Err("can't get under the rebound in time");
+        }
+        Ok(Self::new(false))
+    }
+}
+
+impl Behavior for BackboardRead {
+    fn name(&self) -> &str {
+        name_of_type!(BackboardRead)
+    }
+
+    fn priority(&self) -> Priority {
+        Priority::Strike
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        if self.double_tap {
+            ctx.eeg.log(self.name(), "going for the double tap");
+            Action::tail_call(chain!(Priority::Strike, [
+                AerialShot::shoot(),
+                DoubleTap::new(),
+            ]))
+        } else {
+            ctx.eeg.log(self.name(), "waiting for the rebound");
+            Action::tail_call(chain!(Priority::Strike, [
+                WaitUnderRebound::new(),
+                AerialShot::shoot(),
+            ]))
+        }
+    }
+}
+
+/// The first frame where the ball hits the enemy back wall above the goal.
+fn bounce<'ball>(game: &Game<'_>, scenario: &'ball Scenario<'_>) -> Option<&'ball BallFrame> {
+    let enemy_goal = game.enemy_goal();
+    scenario
+        .ball_prediction()
+        .iter()
+        .take_while(|ball| ball.t < BackboardRead::MAX_TIME)
+        .find(|ball| {
+            ball.loc.z >= BackboardRead::MIN_BOUNCE_Z
+                && enemy_goal.is_y_within_range(ball.loc.y, ..BackboardRead::BOUNCE_DISTANCE)
+        })
+}
+
+/// The first frame after `bounce` where the ball is back out in the air and
+/// within aerial range.
+fn rebound<'ball>(
+    game: &Game<'_>,
+    scenario: &'ball Scenario<'_>,
+    bounce: &BallFrame,
+) -> Option<&'ball BallFrame> {
+    let enemy_goal = game.enemy_goal();
+    scenario
+        .ball_prediction()
+        .iter()
+        .skip_while(|ball| ball.t <= bounce.t)
+        .take_while(|ball| ball.t < BackboardRead::MAX_TIME)
+        .find(|ball| {
+            ball.loc.z >= GroundedHitTarget::MAX_BALL_Z
+                && !enemy_goal.is_y_within_range(ball.loc.y, ..BackboardRead::MIN_REBOUND_DISTANCE)
+        })
+}
+
+/// Wait a little way upfield of the rebound, so we're facing the goal when it
+/// comes down.
+fn wait_loc(game: &Game<'_>, rebound: &BallFrame) -> Point2<f32> {
+    const WAIT_DISTANCE: f32 = 500.0;
+    rebound.loc.to_2d() + game.enemy_goal().normal_2d.into_inner() * WAIT_DISTANCE
+}
+
+/// Drive to the spot under the rebound and hang around until the ball has come
+/// off the backboard.
+struct WaitUnderRebound {
+    time: Stopwatch,
+}
+
+impl WaitUnderRebound {
+    const TIME_LIMIT: f32 = BackboardRead::MAX_TIME;
+    /// Close enough to the wait spot to stop driving.
+    const ARRIVE_DISTANCE: f32 = 200.0;
+
+    fn new() -> Self {
+        Self {
+            time: Stopwatch::new(),
+        }
+    }
+}
+
+impl Behavior for WaitUnderRebound {
+    fn name(&self) -> &str {
+        name_of_type!(WaitUnderRebound)
+    }
+
+    fn priority(&self) -> Priority {
+        Priority::Strike
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        let elapsed = self.time.tick(ctx.packet.GameInfo.TimeSeconds);
+        if elapsed >= Self::TIME_LIMIT {
+            ctx.eeg.log(self.name(), "the ball never came off the wall");
+            return Action::Abort;
+        }
+
+        let target_loc = {
+            let (ctx, eeg) = ctx.split();
+            let bounce = some_or_else!(bounce(ctx.game, ctx.scenario), {
+                // No bounce left in the prediction. If that's because it already
+                // happened, it's time to go up.
+                let ball = ctx.scenario.ball_prediction().start();
+                let normal = ctx.game.enemy_goal().normal_2d;
+                if ball.loc.z >= GroundedHitTarget::MAX_BALL_Z
+                    && ball.vel.to_2d().dot(&normal.into_inner()) > 0.0
+                {
+                    eeg.log(self.name(), "ball is off the backboard");
+                    return Action::Return;
+                }
+                eeg.log(self.name(), "the ball isn't going to hit the backboard");
+                return Action::Abort;
+            });
+            let rebound = some_or_else!(rebound(ctx.game, ctx.scenario, bounce), {
+                eeg.log(self.name(), "no rebound to play");
+                return Action::Abort;
+            });
+
+            eeg.draw(Drawable::ghost_ball(bounce.loc));
+            eeg.draw(Drawable::ghost_ball(rebound.loc));
+            eeg.print_time("rebound_time", rebound.t);
+            wait_loc(ctx.game, rebound)
+        };
+
+        let mut input = drive_towards(ctx, target_loc);
+        if (target_loc - ctx.me().Physics.loc_2d()).norm() < Self::ARRIVE_DISTANCE {
+            input.Throttle = 0.0;
+        }
+        Action::Yield(input)
+    }
+}
+
+/// After the first touch into the backboard, stay in the air and fly to meet
+/// the rebound.
+struct DoubleTap;
+
+impl DoubleTap {
+    fn new() -> Self {
+        Self
+    }
+
+    fn plan(ctx: &Context2<'_, '_>) -> Option<AerialLocTime> {
+        let me = ctx.me();
+        let bounce = bounce(ctx.game, ctx.scenario)?;
+        ctx.scenario
+            .ball_prediction()
+            .iter()
+            .skip_while(|ball| ball.t <= bounce.t)
+            .take_while(|ball| ball.t < BackboardRead::MAX_TIME)
+            .filter(|ball| ball.loc.z >= GroundedHitTarget::MAX_BALL_Z)
+            .find_map(|ball| {
+                let target_loc = AerialShot::shot_contact_loc(ctx.game, me.Physics.loc(), ball);
+                AerialLocTime::feasible_airborne(me, target_loc, ball.t).ok()?;
+                Some(AerialLocTime::airborne(target_loc, ball.t))
+            })
+    }
+}
+
+impl Behavior for DoubleTap {
+    fn name(&self) -> &str {
+        name_of_type!(DoubleTap)
+    }
+
+    fn priority(&self) -> Priority {
+        Priority::Strike
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        let (ctx, eeg) = ctx.split();
+        if ctx.me().OnGround {
+            eeg.log(self.name(), "landed before the second touch");
+            return Action::Abort;
+        }
+
+        let flight = some_or_else!(Self::plan(&ctx), {
+            eeg.log(self.name(), "no second touch within reach");
+            return Action::Abort;
+        });
+        eeg.log_pretty(self.name(), "target_loc", flight.target_loc());
+        Action::tail_call(flight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        behavior::offense::backboard_read::{bounce, rebound, wait_loc, BackboardRead},
+        routing::test::{car_on_floor, with_offline_context},
+    };
+    use common::rl;
+    use nalgebra::{Point2, Point3, Vector3};
+    use std::f32::consts::PI;
+
+    /// A ball lofted at the enemy (orange) backboard, from blue's point of
+    /// view.
+    fn ball_at_backboard() -> (Point3<f32>, Vector3<f32>) {
+        (
+            Point3::new(0.0, 3500.0, 1200.0),
+            Vector3::new(0.0, 2000.0, 300.0),
+        )
+    }
+
+    #[test]
+    fn finds_the_bounce_and_rebound() {
+        let (ball_loc, ball_vel) = ball_at_backboard();
+        let car = car_on_floor(Point2::new(0.0, 0.0), PI / 2.0, 0.0, 0.0);
+        with_offline_context(&car, ball_loc, ball_vel, 100.0, |ctx| {
+            let bounce = bounce(ctx.game, ctx.scenario).unwrap();
+            assert!(bounce.loc.z >= rl::CROSSBAR_Z);
+            assert!(bounce.loc.y >= rl::FIELD_MAX_Y - 200.0);
+
+            let rebound = rebound(ctx.game, ctx.scenario, bounce).unwrap();
+            assert!(rebound.t > bounce.t);
+            assert!(rebound.loc.y < bounce.loc.y);
+            assert!(rebound.vel.y < 0.0);
+
+            // Wait on the near side of the rebound, facing the goal.
+            assert!(wait_loc(ctx.game, rebound).y < rebound.loc.y);
+        });
+    }
+
+    #[test]
+    fn reads_the_rebound() {
+        let (ball_loc, ball_vel) = ball_at_backboard();
+        let car = car_on_floor(Point2::new(0.0, 3000.0), PI / 2.0, 0.0, 0.0);
+        with_offline_context(&car, ball_loc, ball_vel, 100.0, |ctx| {
+            let read = BackboardRead::choose(ctx).unwrap();
+            // No boost to spare for a first touch, so wait for the rebound.
+            assert!(!read.double_tap);
+        });
+    }
+
+    #[test]
+    fn too_far_to_make_the_rebound() {
+        let (ball_loc, ball_vel) = ball_at_backboard();
+        let car = car_on_floor(Point2::new(0.0, -4000.0), PI / 2.0, 0.0, 0.0);
+        with_offline_context(&car, ball_loc, ball_vel, 100.0, |ctx| {
+            assert_eq!(
+                BackboardRead::choose(ctx).err(),
+                Some("can't get under the rebound in time"),
+            );
+        });
+    }
+
+    #[test]
+    fn ball_into_the_net_is_no_read() {
+        let ball_loc = Point3::new(0.0, 3500.0, rl::BALL_RADIUS);
+        let ball_vel = Vector3::new(0.0, 2000.0, 0.0);
+        let car = car_on_floor(Point2::new(0.0, 0.0), PI / 2.0, 0.0, 0.0);
+        with_offline_context(&car, ball_loc, ball_vel, 100.0, |ctx| {
+            assert_eq!(
+                BackboardRead::choose(ctx).err(),
+                Some("not headed for the backboard"),
+            );
+        });
+    }
+}
@@ -1,3 +1,309 @@
+use crate::{
+    behavior::{movement::simple_steer_towards, offense::Shoot, strike::Flick},
+    eeg::Drawable,
+    helpers::ball::{BallFrame, BallTrajectory},
+    strategy::{Action, Behavior, Context, Game, Scenario},
+};
+use common::{prelude::*, rl, Distance, Speed};
+use nameof::name_of_type;
+
+/// Keep the ball close and push it up the field, either balanced on the roof
+/// or bouncing just in front of the nose.
+pub struct Dribble {
+    style: DribbleStyle,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum DribbleStyle {
+    /// The ball rides on the roof.
+    Carry,
+    /// The ball bounces in front of the car, and we tap it on the way down.
+    Bounce,
+}
+
+impl Dribble {
+    /// How much possession we need before it's worth slowing the play down.
+    pub const MIN_POSSESSION: f32 = 3.0;
+    /// Only try to pick up balls slower than this.
+    pub const MAX_BALL_SPEED: f32 = 700.0;
+
+    /// An enemy that can reach the ball sooner than this is challenging us.
+    const CHALLENGE_TIME: f32 = 0.75;
+    /// Don't look further ahead than this for the next touch.
+    const MAX_LOOKAHEAD: f32 = 2.0;
+    /// The height of the center of a ball resting on the roof.
+    const CARRY_BALL_Z: f32 = 155.0;
+    /// How far behind the ball's center to keep the car's center while
+    /// carrying, so the ball tips gently forward.
+    const CARRY_OFFSET: f32 = 15.0;
+    /// A ball lower than this is still on the floor and needs picking up
+    /// before we can carry it.
+    const CARRY_PICKUP_BALL_Z: f32 = 120.0;
+    /// While picking up, close on the ball this much faster than it's rolling,
+    /// so it climbs up the hood instead of getting knocked away.
+    const PICKUP_CLOSING_SPEED: f32 = 200.0;
+    /// Tap the ball on the way down once it's this low.
+    const BOUNCE_BALL_Z: f32 = 130.0;
+    /// How far to lean into the ball's sideways drift to push it back on line.
+    const DRIFT_CORRECTION: f32 = 0.15;
+    const MAX_DRIFT_CORRECTION: f32 = 40.0;
+    /// If we're further than this from the ball, we've lost it.
+    const MAX_BALL_DISTANCE: f32 = 1500.0;
+    /// Flick once the ball is this close to the enemy goal.
+    const FLICK_DISTANCE: f32 = 2500.0;
+
+    pub fn new(style: DribbleStyle) -> Self {
+        Self { style }
+    }
+
+    /// Pick a style that suits what the ball is doing right now.
+    pub fn choose_style(ball: &BallFrame) -> DribbleStyle {
+        if ball.loc.z >= 200.0 || ball.vel.z.abs() >= 300.0 {
+            DribbleStyle::Bounce
+        } else {
+            DribbleStyle::Carry
+        }
+    }
+
+    /// Check the same things `execute_old` would abort on, so the caller
+    /// doesn't pick a dribble that gives up on the first frame.
+    pub fn viable(
+        style: DribbleStyle,
+        scenario: &Scenario<'_>,
+        car: &common::halfway_house::PlayerInfo,
+    ) -> Result<(), &'static str> {
+        if !car.OnGround {
+            return Err("not on ground");
+        }
+        let contact = some_or_else!(Self::new(style).next_contact(scenario.ball_prediction()), {
+            return Err("no touch coming up");
+        });
+        if (contact.loc.to_2d() - car.Physics.loc_2d()).norm() >= Self::MAX_BALL_DISTANCE {
+            return Err("ball is too far");
+        }
+        Ok(())
+    }
+
+    /// The next moment the ball is in position for a touch.
+    fn next_contact(&self, ball_prediction: &BallTrajectory) -> Option<BallFrame> {
+        let contact_z = self.contact_z();
+        ball_prediction
+            .iter()
+            .take_while(|ball| ball.t < Self::MAX_LOOKAHEAD)
+            .find(|ball| ball.loc.z <= contact_z && ball.vel.z <= 0.0)
+            .cloned()
+    }
+
+    fn contact_z(&self) -> f32 {
+        match self.style {
+            DribbleStyle::Carry => Self::CARRY_BALL_Z,
+            DribbleStyle::Bounce => Self::BOUNCE_BALL_Z,
+        }
+    }
+
+    /// Where the car's center should be at the moment of contact.
+    fn contact_offset(&self, game: &Game<'_>, picking_up: bool) -> f32 {
+        match self.style {
+            // Meet the ball with the nose and let it roll up onto the roof.
+            DribbleStyle::Carry if picking_up => game.me_vehicle().pivot_to_front_dist(),
+            DribbleStyle::Carry => Self::CARRY_OFFSET,
+            DribbleStyle::Bounce => {
+                game.me_vehicle().pivot_to_front_dist() + game.ball_radius() * 0.5
+            }
+        }
+    }
+}
+
+impl Behavior for Dribble {
+    fn name(&self) -> &str {
+        name_of_type!(Dribble)
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        let (ctx, eeg) = ctx.split();
+        let me = ctx.me();
+        if !me.OnGround {
+            eeg.log(self.name(), "not on ground");
+            return Action::Abort;
+        }
+
+        let contact = some_or_else!(self.next_contact(ctx.scenario.ball_prediction()), {
+            eeg.log(self.name(), "no touch coming up");
+            return Action::Abort;
+        });
+
+        let car_loc = me.Physics.loc_2d();
+        let ball_loc = contact.loc.to_2d();
+        let ball_distance = (ball_loc - car_loc).norm();
+        if ball_distance >= Self::MAX_BALL_DISTANCE {
+            eeg.log(self.name(), "lost the ball");
+            return Action::Abort;
+        }
+
+        let aim_loc = ctx.game.enemy_goal().center_2d;
+        let picking_up =
+            self.style == DribbleStyle::Carry && contact.loc.z < Self::CARRY_PICKUP_BALL_Z;
+        let controlled = !picking_up && ball_distance < 200.0 && contact.t < 0.25;
+
+        // Get rid of the ball before someone takes it from us.
+        let challenged = match ctx.scenario.enemy_intercept() {
+            Some((_, intercept)) => intercept.time < Self::CHALLENGE_TIME,
+            None => false,
+        };
+        let near_goal = (aim_loc - ball_loc).norm() < Self::FLICK_DISTANCE;
+        if challenged || near_goal {
+            match self.style {
+                DribbleStyle::Carry if controlled => {
+                    eeg.log(self.name(), "flick");
+                    return Action::tail_call(Flick::new(Flick::choose(&ctx)));
+                }
+                DribbleStyle::Bounce => {
+                    if Shoot::viable_shot(ctx.game, me.Physics.loc(), contact.loc).is_some() {
+                        eeg.log(self.name(), "shoot");
+                        return Action::tail_call(Shoot::new());
+                    }
+                }
+                DribbleStyle::Carry => {}
+            }
+            if challenged {
+                eeg.log(self.name(), "challenged before we had control");
+                return Action::Abort;
+            }
+        }
+
+        // Steer by parking the car under (or behind) the ball's next contact point.
+        // If the ball is drifting sideways, lean into the drift so the next touch
+        // pushes it back on line.
+        let push_dir = (aim_loc - ball_loc).to_axis();
+        let perp = push_dir.ortho();
+        let drift = contact.vel.to_2d().dot(&perp) * Self::DRIFT_CORRECTION;
+        let drift = drift
+            .max(-Self::MAX_DRIFT_CORRECTION)
+            .min(Self::MAX_DRIFT_CORRECTION);
+        let target_loc = ball_loc
+            - push_dir.into_inner() * self.contact_offset(ctx.game, picking_up)
+            + perp * drift;
+
+        // Match the ball's speed, plus a little extra to close any gap.
+        let forward = me.Physics.forward_axis_2d().into_inner();
+        let along_error = (target_loc - car_loc).dot(&forward);
+        let mut ball_speed = contact.vel.to_2d().dot(&forward);
+        if picking_up {
+            ball_speed += Self::PICKUP_CLOSING_SPEED;
+        }
+        let target_speed = (ball_speed + along_error * 2.0)
+            .max(0.0)
+            .min(rl::CAR_MAX_SPEED);
+        let car_speed = me.Physics.vel_2d().dot(&forward);
+        let speed_diff = target_speed - car_speed;
+
+        // Far away, drive straight at the target. Close in, look a little past it so
+        // the steering doesn't jitter.
+        let steer_loc = if (target_loc - car_loc).norm() >= 300.0 {
+            target_loc
+        } else {
+            target_loc + push_dir.into_inner() * 300.0
+        };
+
+        eeg.draw(Drawable::ghost_ball(contact.loc));
+        eeg.draw(Drawable::ghost_car_ground(target_loc, me.Physics.rot()));
+        eeg.print_value("style", format!("{:?}", self.style));
+        eeg.print_value("picking_up", picking_up);
+        eeg.print_value("target_speed", Speed(target_speed));
+        eeg.print_value("along_error", Distance(along_error));
+
+        Action::Yield(common::halfway_house::PlayerInput {
+            Throttle: (speed_diff / 250.0).max(-1.0).min(1.0),
+            Steer: simple_steer_towards(&me.Physics, steer_loc),
+            Boost: speed_diff >= 500.0,
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        behavior::offense::bounce_dribble::{Dribble, DribbleStyle},
+        routing::{models::CarState, test::with_offline_context},
+    };
+    use common::{prelude::*, rl};
+    use nalgebra::{Point3, UnitComplex, Vector3};
+    use std::f32::consts::PI;
+
+    fn car() -> CarState {
+        CarState {
+            loc: Point3::new(0.0, 0.0, rl::OCTANE_NEUTRAL_Z),
+            rot: UnitComplex::new(PI / 2.0).around_z_axis(),
+            vel: Vector3::new(0.0, 500.0, 0.0),
+            boost: 50.0,
+        }
+    }
+
+    fn viable(
+        style: DribbleStyle,
+        ball_loc: Point3<f32>,
+        ball_vel: Vector3<f32>,
+        on_ground: bool,
+    ) -> Result<(), &'static str> {
+        with_offline_context(&car(), ball_loc, ball_vel, 100.0, |ctx| {
+            let mut me = *ctx.me();
+            me.OnGround = on_ground;
+            Dribble::viable(style, ctx.scenario, &me)
+        })
+    }
+
+    #[test]
+    fn carry_a_ball_rolling_ahead() {
+        let ball_loc = Point3::new(0.0, 300.0, rl::BALL_RADIUS);
+        let ball_vel = Vector3::new(0.0, 400.0, 0.0);
+        assert_eq!(
+            viable(DribbleStyle::Carry, ball_loc, ball_vel, true),
+            Ok(()),
+        );
+    }
+
+    #[test]
+    fn bounce_a_dropping_ball() {
+        let ball_loc = Point3::new(0.0, 400.0, 500.0);
+        let ball_vel = Vector3::new(0.0, 300.0, 0.0);
+        assert_eq!(
+            viable(DribbleStyle::Bounce, ball_loc, ball_vel, true),
+            Ok(()),
+        );
+    }
+
+    #[test]
+    fn not_while_airborne() {
+        let ball_loc = Point3::new(0.0, 300.0, rl::BALL_RADIUS);
+        assert_eq!(
+            viable(DribbleStyle::Carry, ball_loc, Vector3::zeros(), false),
+            Err("not on ground"),
+        );
+    }
+
+    #[test]
+    fn not_when_the_ball_is_far_away() {
+        let ball_loc = Point3::new(0.0, 3000.0, rl::BALL_RADIUS);
+        assert_eq!(
+            viable(DribbleStyle::Carry, ball_loc, Vector3::zeros(), true),
+            Err("ball is too far"),
+        );
+    }
+
+    #[test]
+    fn not_when_the_ball_is_sky_high() {
+        // It won't come back down to bouncing height within the lookahead. Keep
+        // it clear of the ceiling, or it bounces straight back down.
+        let ball_loc = Point3::new(0.0, 300.0, 1500.0);
+        let ball_vel = Vector3::new(0.0, 0.0, 500.0);
+        assert_eq!(
+            viable(DribbleStyle::Bounce, ball_loc, ball_vel, true),
+            Err("no touch coming up"),
+        );
+    }
+}
+
 #[cfg(test)]
 mod integration_tests {
     use crate::integration_tests::{TestRunner, TestScenario};
@@ -1,7 +1,15 @@
 pub use self::{
-    offense::Offense, reset_behind_ball::ResetBehindBall, shoot::Shoot, tepid_hit::TepidHit,
+    backboard_read::BackboardRead,
+    bounce_dribble::{Dribble, DribbleStyle},
+    offense::Offense,
+    reset_behind_ball::ResetBehindBall,
+    shoot::{Shoot, Shot},
+    side_wall_self_pass::SideWallSelfPass,
+    support::{Support, TeammateHasBall},
+    tepid_hit::TepidHit,
 };
 
+mod backboard_read;
 mod bounce_dribble;
 #[allow(clippy::module_inception)]
 mod offense;
@@ -9,4 +17,5 @@ mod regroup;
 mod reset_behind_ball;
 mod shoot;
 mod side_wall_self_pass;
+mod support;
 mod tepid_hit;
@@ -1,9 +1,12 @@
 use crate::{
-    behavior::offense::{ResetBehindBall, Shoot, TepidHit},
+    behavior::{
+        offense::{BackboardRead, Dribble, ResetBehindBall, Shoot, SideWallSelfPass, TepidHit},
+        strike::{AerialShot, CeilingShot},
+    },
     eeg::Event,
-    helpers::{ball::BallFrame, intercept::naive_ground_intercept_2},
+    helpers::ball::BallFrame,
     routing::{behavior::FollowRoute, models::CarState, plan::GetDollar},
-    strategy::{Action, Behavior, Context, Game, Scenario},
+    strategy::{Action, Behavior, Context, Game, Intent, Scenario},
     utils::geometry::RayCoordinateSystem,
 };
 use common::{prelude::*, Angle, Distance};
@@ -30,35 +33,65 @@ impl Behavior for Offense {
 
         if can_we_shoot(ctx) {
             ctx.eeg.log(self.name(), "taking the shot!");
-            ctx.quick_chat(0.05, &[
-                rlbot::flat::QuickChatSelection::Information_Incoming,
-            ]);
+            ctx.declare_intent(Intent::GoingForBall);
             return Action::tail_call(Shoot::new());
         }
 
+        match BackboardRead::choose(ctx) {
+            Ok(read) => {
+                ctx.eeg.log(self.name(), "reading the backboard");
+                ctx.declare_intent(Intent::GoingForBall);
+                return Action::tail_call(read);
+            }
+            Err(reason) => {
+                ctx.eeg
+                    .log(self.name(), format!("no backboard read because {}", reason));
+            }
+        }
+
         // TODO: if angle is almost good, slightly adjust path such that good_angle
         // becomes true
 
         // TODO: otherwise drive to a point where me.y < ball.y, then slam the ball
         // sideways
 
+        if let Some(action) = ceiling_shot(ctx) {
+            ctx.declare_intent(Intent::GoingForBall);
+            return action;
+        }
+
+        if let Some(action) = dribble(ctx) {
+            ctx.declare_intent(Intent::GoingForBall);
+            return action;
+        }
+
         if let Some(action) = slow_play(ctx) {
-            ctx.quick_chat(0.01, &[rlbot::flat::QuickChatSelection::Information_IGotIt]);
+            ctx.declare_intent(Intent::GoingForBall);
             return action;
         }
 
         if let Some(b) = get_boost(ctx) {
-            ctx.quick_chat(0.05, &[
-                rlbot::flat::QuickChatSelection::Information_NeedBoost,
-            ]);
+            ctx.declare_intent(Intent::NeedBoost);
             return Action::TailCall(b);
         }
 
         if let Some(action) = poor_angle_swing_around(ctx) {
-            ctx.quick_chat(0.01, &[rlbot::flat::QuickChatSelection::Information_IGotIt]);
+            ctx.declare_intent(Intent::GoingForBall);
             return action;
         }
 
+        match SideWallSelfPass::applicable(ctx) {
+            Ok(()) => {
+                ctx.eeg.log(self.name(), "side wall self-pass");
+                ctx.declare_intent(Intent::GoingForBall);
+                return Action::tail_call(SideWallSelfPass::new());
+            }
+            Err(reason) => {
+                ctx.eeg
+                    .log(self.name(), format!("no self-pass because {}", reason));
+            }
+        }
+
         ctx.eeg
             .log(self.name(), "no good hit; going for a tepid hit");
         Action::tail_call(TepidHit::new())
@@ -86,13 +119,11 @@ fn can_we_shoot(ctx: &mut Context<'_>) -> bool {
         return false;
     });
 
-    let shoot_intercept = naive_ground_intercept_2(
-        &me.into(),
-        ctx.scenario.ball_prediction().iter_step_by(0.125),
-        |ball| Shoot::viable_shot(ctx.game, me.Physics.loc(), ball.loc),
-    );
-
-    let shoot_intercept = some_or_else!(shoot_intercept, {
+    let shoot_intercept = some_or_else!(ctx.scenario.me_shots().first().map(|r| &r.intercept), {
+        if AerialShot::viable_shot(ctx.game, &ctx.scenario, me).is_some() {
+            ctx.eeg.log(name_of_type!(Offense), "can_we_shoot: aerial");
+            return true;
+        }
         ctx.eeg.log(
             name_of_type!(Offense),
             "can_we_shoot: no shootable intercept",
@@ -116,6 +147,16 @@ fn can_we_shoot(ctx: &mut Context<'_>) -> bool {
     true
 }
 
+/// With the ball high up and nobody around, go over the top.
+fn ceiling_shot(ctx: &mut Context<'_>) -> Option<Action> {
+    if ctx.scenario.possession() < CeilingShot::MIN_POSSESSION {
+        return None;
+    }
+    CeilingShot::viable(ctx.game, &ctx.scenario, ctx.me())?;
+    ctx.eeg.log(name_of_type!(Offense), "ceiling shot");
+    Some(Action::tail_call(CeilingShot::new()))
+}
+
 fn playing_goalie(game: &Game<'_>, ball: &BallFrame) -> bool {
     let safe = game.own_goal().center_2d;
     let danger = game.enemy_goal().center_2d;
@@ -143,6 +184,41 @@ fn flat_and_defensive(ctx: &mut Context<'_>) -> bool {
         && (ball_loc - car_loc).norm() < min_dist
 }
 
+fn dribble(ctx: &mut Context<'_>) -> Option<Action> {
+    if ctx.scenario.possession() < Dribble::MIN_POSSESSION {
+        ctx.eeg
+            .log(name_of_type!(Offense), "dribble: need possession");
+        return None;
+    }
+
+    let ball = ctx.scenario.ball_prediction().start();
+    if ball.vel.to_2d().norm() >= Dribble::MAX_BALL_SPEED {
+        ctx.eeg
+            .log(name_of_type!(Offense), "dribble: ball is too fast");
+        return None;
+    }
+
+    // Losing the ball in our own half is how goals get conceded.
+    if ctx.game.own_goal().is_y_within_range(ball.loc.y, ..2500.0) {
+        ctx.eeg
+            .log(name_of_type!(Offense), "dribble: too close to own goal");
+        return None;
+    }
+
+    let style = Dribble::choose_style(ball);
+    if let Err(reason) = Dribble::viable(style, &ctx.scenario, ctx.me()) {
+        ctx.eeg
+            .log(name_of_type!(Offense), format!("dribble: {}", reason));
+        return None;
+    }
+
+    ctx.eeg.log(
+        name_of_type!(Offense),
+        format!("dribble: proceeding ({:?})", style),
+    );
+    Some(Action::tail_call(Dribble::new(style)))
+}
+
 fn slow_play(ctx: &mut Context<'_>) -> Option<Action> {
     // Only slow play if we have enough time.
     if ctx.scenario.possession() < 2.0 {
@@ -1,11 +1,12 @@
 use crate::{
+    behavior::higher_order::TryChoose,
     routing::{
         behavior::FollowRoute,
-        plan::{GetDollar, GroundDrive},
+        plan::{GetDollar, GroundDrive, SearchPlanner},
     },
-    strategy::{Action, Behavior, BoostPickup, Context},
+    strategy::{Action, Behavior, BoostPickup, Context, Priority},
 };
-use common::prelude::*;
+use common::{prelude::*, rl};
 use nalgebra::{Point2, Vector2};
 use nameof::name_of_type;
 use simulate::linear_interpolate;
@@ -42,14 +43,26 @@ impl Behavior for ResetBehindBall {
             return Action::tail_call(FollowRoute::new(GetDollar::new(self.loc).pickup(pickup)));
         }
 
+        // Search for a route that ends up facing the ball, so we're ready to
+        // go as soon as we get there. If it comes up empty, just drive there.
+        let search =
+            SearchPlanner::new(target_loc.to_3d(rl::OCTANE_NEUTRAL_Z)).target_face(self.loc);
         let straight = GroundDrive::new(target_loc)
             .end_chop(0.5)
-            .always_prefer_dodge(true);
-        Action::tail_call(
-            FollowRoute::new(straight)
-                .same_ball_trajectory(true)
-                .never_recover(self.never_recover),
-        )
+            .always_prefer_dodge(true)
+            .allow_reverse(true);
+        Action::tail_call(TryChoose::new(Priority::Idle, vec![
+            Box::new(
+                FollowRoute::new(search)
+                    .same_ball_trajectory(true)
+                    .never_recover(true),
+            ),
+            Box::new(
+                FollowRoute::new(straight)
+                    .same_ball_trajectory(true)
+                    .never_recover(self.never_recover),
+            ),
+        ]))
     }
 }
 
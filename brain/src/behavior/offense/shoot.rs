@@ -2,12 +2,18 @@ use crate::{
     behavior::{
         higher_order::Chain,
         strike::{
-            BounceShot, GroundedHit, GroundedHitAimContext, GroundedHitTarget,
+            AerialShot, BounceShot, GroundedHit, GroundedHitAimContext, GroundedHitTarget,
             GroundedHitTargetAdjust,
         },
     },
-    helpers::intercept::{naive_ground_intercept_2, NaiveIntercept},
-    routing::{behavior::FollowRoute, plan::GroundIntercept},
+    helpers::{
+        intercept::NaiveIntercept,
+        intercept_optimizer::{rank_ground_intercepts, InterceptWeights, RankedIntercept},
+    },
+    routing::{
+        behavior::FollowRoute,
+        plan::{AvoidCars, GroundIntercept},
+    },
     strategy::{Action, Behavior, Context, Game, Priority, Scenario},
 };
 use common::{prelude::*, Speed};
@@ -28,7 +34,7 @@ impl Shoot {
         car_loc: Point3<f32>,
         ball_loc: Point3<f32>,
     ) -> Option<Shot> {
-        // Aerials are not ready for prime-time yet
+        // Balls this high are left to `AerialShot`.
         if ball_loc.z >= GroundedHitTarget::MAX_BALL_Z {
             return None;
         }
@@ -57,7 +63,7 @@ impl Shoot {
     }
 
     fn aim(ctx: &mut GroundedHitAimContext<'_, '_>) -> Result<GroundedHitTarget, ()> {
-        match Self::aim_calc(ctx.game, ctx.scenario, ctx.car) {
+        match Self::aim_calc(ctx.scenario) {
             Some(i) => Ok(GroundedHitTarget::new(
                 i.time,
                 GroundedHitTargetAdjust::RoughAim,
@@ -72,9 +78,9 @@ impl Shoot {
         ctx: &mut GroundedHitAimContext<'_, '_>,
         intercept: &NaiveIntercept<Shot>,
     ) -> bool {
-        // `ctx.intercept_ball_loc` is the soonest possible intercept.
-        // `intercept` is the possibly-later "strategic" intercept that we'll wait for
-        // before shooting.
+        // `ctx.intercept_ball_loc` is the intercept `GroundedHit` is going for, and
+        // `intercept` is the one we're aiming from. Both come from
+        // `Scenario::me_shots`, so they're the same ball.
 
         let ball_loc = intercept.ball_loc.to_2d();
         let ball_vel = intercept.ball_vel.to_2d();
@@ -152,14 +158,35 @@ impl Shoot {
             && shot_angle < PI / 6.0
     }
 
-    fn aim_calc(
+    fn aim_calc<'s>(scenario: &'s Scenario<'_>) -> Option<&'s NaiveIntercept<Shot>> {
+        scenario.me_shots().first().map(|r| &r.intercept)
+    }
+
+    /// Every intercept we could shoot from, best first. This is expensive, so
+    /// use `Scenario::me_shots`, which caches it for the tick.
+    pub fn rank_shots(
         game: &Game<'_>,
         scenario: &Scenario<'_>,
         car: &common::halfway_house::PlayerInfo,
-    ) -> Option<NaiveIntercept<Shot>> {
-        naive_ground_intercept_2(&car.into(), scenario.ball_prediction(), |ball| {
-            Self::viable_shot(game, car.Physics.loc(), ball.loc)
-        })
+    ) -> Vec<RankedIntercept<Shot>> {
+        let enemy_time = scenario.enemy_intercept().map(|(_, i)| i.time);
+        rank_ground_intercepts(
+            game,
+            &car.into(),
+            enemy_time,
+            &InterceptWeights::default(),
+            scenario.ball_prediction().iter(),
+            |ball| Self::viable_shot(game, car.Physics.loc(), ball.loc),
+        )
+    }
+
+    /// The ranking `GroundedHit` goes by, so it hits the ball we're aiming at.
+    fn ranked_intercepts(scenario: &Scenario<'_>) -> Vec<RankedIntercept> {
+        scenario
+            .me_shots()
+            .iter()
+            .map(RankedIntercept::without_data)
+            .collect()
     }
 }
 
@@ -173,15 +200,23 @@ impl Behavior for Shoot {
     }
 
     fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
-        let intercept = Self::aim_calc(ctx.game, &ctx.scenario, ctx.me());
-        if intercept.is_none() {
+        let intercept_time = Self::aim_calc(&ctx.scenario).map(|i| i.time);
+        let intercept_time = some_or_else!(intercept_time, {
+            if AerialShot::viable_shot(ctx.game, &ctx.scenario, ctx.me()).is_some() {
+                ctx.eeg.log(self.name(), "no ground shot; going aerial");
+                return Action::tail_call(AerialShot::shoot());
+            }
             ctx.eeg.log(self.name(), "no viable shot");
             return Action::Abort;
-        }
+        });
 
+        // Drive to the best shot, which isn't necessarily the earliest ball we
+        // can reach.
+        let intercept = GroundIntercept::new().target_time(intercept_time);
         Action::tail_call(Chain::new(Priority::Strike, vec![
-            Box::new(FollowRoute::new(GroundIntercept::new()).same_ball_trajectory(true)),
-            Box::new(GroundedHit::hit_towards(Self::aim)),
+            // Don't plow through a goalie sitting between us and the ball.
+            Box::new(FollowRoute::new(AvoidCars::new(intercept)).same_ball_trajectory(true)),
+            Box::new(GroundedHit::hit_towards(Self::aim).rank_by(Self::ranked_intercepts)),
         ]))
     }
 }
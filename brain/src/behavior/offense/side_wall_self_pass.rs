@@ -1,6 +1,257 @@
+use crate::{
+    behavior::{
+        movement::drive_towards,
+        offense::Shoot,
+        strike::{
+            GroundedHit, GroundedHitAimContext, GroundedHitTarget, GroundedHitTargetAdjust, WallHit,
+        },
+    },
+    eeg::Drawable,
+    helpers::ball::BallFrame,
+    routing::{
+        behavior::FollowRoute,
+        plan::{GroundIntercept, WallIntercept},
+    },
+    strategy::{Action, Behavior, Context, Context2, Game, Priority},
+    utils::{Stopwatch, Wall, WallRayCalculator},
+};
+use common::{prelude::*, rl};
+use nalgebra::{Point2, Vector2};
+use nameof::name_of_type;
+use std::f32::consts::PI;
+
+/// Knock the ball up the nearby side wall, then meet it on the way down for a
+/// shot.
+pub struct SideWallSelfPass;
+
+impl SideWallSelfPass {
+    /// The pass and the chase afterwards take a while, so we need to be well
+    /// ahead of the enemy.
+    pub const MIN_POSSESSION: f32 = 2.0;
+    /// The ball needs to already be out towards a side wall.
+    const MIN_BALL_X: f32 = 1500.0;
+    /// Leave room in front of the enemy back wall for the ball to come down.
+    const MIN_DISTANCE_FROM_BACK_WALL: f32 = 2500.0;
+    /// How far upfield of the ball to aim the pass, per unit of distance
+    /// towards the side wall.
+    const PASS_SLOPE: f32 = 1.0;
+
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns `Ok` if there is a pass to be made here.
+    pub fn applicable(ctx: &Context<'_>) -> Result<(), &'static str> {
+        if ctx.scenario.possession() < Self::MIN_POSSESSION {
+            return Err("need possession");
+        }
+
+        let intercept = ctx.scenario.me_intercept().ok_or("no intercept")?;
+        let ball_loc = intercept.ball_loc;
+        if ball_loc.z >= GroundedHitTarget::MAX_BALL_Z {
+            return Err("ball is too high");
+        }
+        if ball_loc.x.abs() < Self::MIN_BALL_X {
+            return Err("not close enough to a side wall");
+        }
+        let enemy_goal = ctx.game.enemy_goal();
+        if enemy_goal.is_y_within_range(ball_loc.y, ..Self::MIN_DISTANCE_FROM_BACK_WALL) {
+            return Err("too close to the enemy back wall");
+        }
+
+        let aim_loc = pass_target(ctx.game, ball_loc.to_2d()).ok_or("no wall to pass to")?;
+        let car_to_ball = ball_loc.to_2d() - ctx.me().Physics.loc_2d();
+        if car_to_ball.angle_to(&(aim_loc - ball_loc.to_2d())).abs() >= PI / 3.0 {
+            return Err("awkward angle");
+        }
+        Ok(())
+    }
+
+    fn aim(ctx: &mut GroundedHitAimContext<'_, '_>) -> Result<GroundedHitTarget, ()> {
+        let aim_loc = pass_target(ctx.game, ctx.intercept_ball_loc.to_2d()).ok_or(())?;
+        // A soft touch, so the ball doesn't rocket off the wall past us.
+        Ok(GroundedHitTarget::new(
+            ctx.intercept_time,
+            GroundedHitTargetAdjust::RoughAim,
+            aim_loc,
+        )
+        .jump(false)
+        .dodge(false))
+    }
+}
+
+impl Behavior for SideWallSelfPass {
+    fn name(&self) -> &str {
+        name_of_type!(SideWallSelfPass)
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        if let Err(reason) = Self::applicable(ctx) {
+            ctx.eeg.log(self.name(), reason);
+            return Action::Abort;
+        }
+
+        Action::tail_call(chain!(Priority::Strike, [
+            FollowRoute::new(GroundIntercept::new()).same_ball_trajectory(true),
+            GroundedHit::hit_towards(Self::aim),
+            MeetOffWall::new(),
+        ]))
+    }
+}
+
+/// Where on the side wall should we aim a pass from `ball_loc`? Returns
+/// `None` if that spot isn't on a side wall.
+fn pass_target(game: &Game<'_>, ball_loc: Point2<f32>) -> Option<Point2<f32>> {
+    let upfield = game.own_goal().normal_2d.y.signum();
+    let dir = Vector2::new(ball_loc.x.signum(), upfield * SideWallSelfPass::PASS_SLOPE);
+    let aim_loc = WallRayCalculator::calculate(ball_loc, ball_loc + dir);
+    match WallRayCalculator::wall_for_point(game, aim_loc) {
+        Wall::Midfield => Some(aim_loc),
+        _ => None,
+    }
+}
+
+/// After the pass, wait under the ball until it comes back down within reach,
+/// then shoot.
+struct MeetOffWall {
+    time: Stopwatch,
+}
+
+impl MeetOffWall {
+    /// If the ball hasn't come back by now, the pass went wrong.
+    const TIME_LIMIT: f32 = 4.0;
+    /// How far from the wall to wait.
+    const WAIT_DISTANCE_FROM_WALL: f32 = 1000.0;
+
+    fn new() -> Self {
+        Self {
+            time: Stopwatch::new(),
+        }
+    }
+}
+
+impl Behavior for MeetOffWall {
+    fn name(&self) -> &str {
+        name_of_type!(MeetOffWall)
+    }
+
+    fn priority(&self) -> Priority {
+        Priority::Strike
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        let elapsed = self.time.tick(ctx.packet.GameInfo.TimeSeconds);
+        if elapsed >= Self::TIME_LIMIT {
+            ctx.eeg.log(self.name(), "the ball never came back");
+            return Action::Abort;
+        }
+
+        let landing_loc = {
+            let (ctx, eeg) = ctx.split();
+            let landing = some_or_else!(landing(&ctx), {
+                // If the ball is going to stay up on the wall, go and play it there.
+                if WallIntercept::new()
+                    .must_be_wall(true)
+                    .must_be_side_wall(true)
+                    .calc_intercept(&ctx)
+                    .is_ok()
+                {
+                    eeg.log(self.name(), "ball is staying on the wall");
+                    return Action::tail_call(chain!(Priority::Strike, [
+                        FollowRoute::new(
+                            WallIntercept::new()
+                                .must_be_wall(true)
+                                .must_be_side_wall(true)
+                        )
+                        .same_ball_trajectory(true),
+                        WallHit::new(),
+                    ]));
+                }
+                eeg.log(self.name(), "the ball isn't coming back");
+                return Action::Abort;
+            });
+
+            if Shoot::viable_shot(ctx.game, ctx.me().Physics.loc(), landing.loc).is_some() {
+                eeg.log(self.name(), "ball is coming back; shooting");
+                return Action::tail_call(Shoot::new());
+            }
+
+            eeg.draw(Drawable::ghost_ball(landing.loc));
+            landing.loc.to_2d()
+        };
+
+        // Wait a little way out from the wall so we're facing the ball as it comes
+        // down.
+        let wall_x = landing_loc.x.signum() * rl::FIELD_MAX_X;
+        let wait_loc = Point2::new(
+            wall_x - landing_loc.x.signum() * Self::WAIT_DISTANCE_FROM_WALL,
+            landing_loc.y,
+        );
+        Action::Yield(drive_towards(ctx, wait_loc))
+    }
+}
+
+/// Find the moment the ball comes back within reach after running along the
+/// wall.
+#[allow(clippy::float_cmp)]
+fn landing<'ball>(ctx: &Context2<'_, 'ball>) -> Option<&'ball BallFrame> {
+    let pitch = ctx.game.pitch();
+    let mut balls = ctx.scenario.ball_prediction().iter();
+    balls.find(|ball| {
+        let plane = pitch.closest_plane(&ball.loc);
+        plane.normal.x.abs() == 1.0
+            && plane.distance_to_point(&ball.loc) < ctx.game.ball_radius() + 20.0
+    })?;
+    balls.find(|ball| ball.loc.z < GroundedHitTarget::MAX_BALL_Z && ball.vel.z <= 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{behavior::offense::side_wall_self_pass::pass_target, strategy::Game};
+    use common::halfway_house::{LiveDataPacket, PlayerInfo};
+    use nalgebra::Point2;
+    use std::iter;
+
+    fn packet(team: u8) -> LiveDataPacket {
+        LiveDataPacket {
+            GameCars: iter::once(PlayerInfo {
+                Team: team,
+                ..Default::default()
+            })
+            .collect(),
+            NumCars: 1,
+            GameBall: Default::default(),
+            GameInfo: Default::default(),
+            Teams: Default::default(),
+            NumTeams: 0,
+        }
+    }
+
+    #[test]
+    fn blue_passes_upfield() {
+        let packet = packet(0);
+        let game = Game::offline_soccar(&packet, 0);
+        let ball_loc = Point2::new(3000.0, 0.0);
+        let aim_loc = pass_target(&game, ball_loc).unwrap();
+        assert!(aim_loc.y > ball_loc.y);
+    }
+
+    #[test]
+    fn orange_passes_upfield() {
+        let packet = packet(1);
+        let game = Game::offline_soccar(&packet, 0);
+        let ball_loc = Point2::new(-3000.0, 0.0);
+        let aim_loc = pass_target(&game, ball_loc).unwrap();
+        assert!(aim_loc.y < ball_loc.y);
+    }
+}
+
 #[cfg(test)]
 mod integration_tests {
-    use crate::integration_tests::{TestRunner, TestScenario};
+    use crate::{
+        behavior::offense::SideWallSelfPass,
+        integration_tests::{TestRunner, TestScenario},
+    };
     use common::prelude::*;
     use nalgebra::{Point3, Rotation3, Vector3};
 
@@ -16,7 +267,7 @@ mod integration_tests {
                 car_vel: Vector3::new(-1999.5629, 569.28217, 8.3),
                 ..Default::default()
             })
-            .soccar()
+            .behavior(SideWallSelfPass::new())
             .run_for_millis(5000);
 
         assert!(test.has_scored());
@@ -0,0 +1,51 @@
+use crate::{
+    behavior::{higher_order::Predicate, offense::ResetBehindBall},
+    routing::{behavior::FollowRoute, plan::GetDollar},
+    strategy::{Action, Behavior, Context},
+};
+use common::prelude::*;
+use derive_new::new;
+use nameof::name_of_type;
+
+/// Stay out of the way while a teammate plays the ball: top up on boost, then
+/// hang back far enough to pick up the pieces.
+#[derive(new)]
+pub struct Support;
+
+impl Support {
+    const DISTANCE_BEHIND_BALL: f32 = 2500.0;
+}
+
+impl Behavior for Support {
+    fn name(&self) -> &str {
+        name_of_type!(Support)
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        if ctx.me().Boost < 50 {
+            ctx.eeg.log(self.name(), "getting boost");
+            let (ctx, eeg) = ctx.split();
+            return Action::tail_call(FollowRoute::new(GetDollar::smart(&ctx, eeg)));
+        }
+
+        ctx.eeg.log(self.name(), "hanging back");
+        let ball_loc = ctx.scenario.ball_prediction().at_time_or_last(2.5).loc;
+        Action::tail_call(ResetBehindBall::behind_loc(
+            ball_loc.to_2d(),
+            Self::DISTANCE_BEHIND_BALL,
+        ))
+    }
+}
+
+/// True while one of our teammates has claimed the ball.
+pub struct TeammateHasBall;
+
+impl Predicate for TeammateHasBall {
+    fn name(&self) -> &str {
+        name_of_type!(TeammateHasBall)
+    }
+
+    fn evaluate(&mut self, ctx: &mut Context<'_>) -> bool {
+        ctx.intents.teammate_going_for_ball(ctx.game.player_index())
+    }
+}
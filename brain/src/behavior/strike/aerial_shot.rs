@@ -1,11 +1,211 @@
+use crate::{
+    behavior::{
+        movement::AerialLocTime,
+        strike::{BounceShot, GroundedHitTarget},
+    },
+    eeg::Drawable,
+    helpers::ball::{BallFrame, BallTrajectory},
+    strategy::{Action, Behavior, Context, Game, Priority, Scenario},
+};
+use common::{prelude::*, Time};
+use nalgebra::{Point2, Point3};
+use nameof::name_of_type;
+use simulate::car_aerial::AerialPlan;
+use std::f32::consts::PI;
+
+/// Jump, then boost through the air to meet the ball at a point in its
+/// predicted trajectory.
+pub struct AerialShot {
+    aim: Aim,
+    flight: Option<Flight>,
+}
+
+/// Which way to knock the ball once we reach it.
+#[derive(Copy, Clone)]
+enum Aim {
+    /// Towards the enemy goal.
+    Shoot,
+    /// Straight away from our own goal.
+    Clear,
+}
+
+pub struct AerialIntercept {
+    /// Seconds from now.
+    pub time: f32,
+    pub ball_loc: Point3<f32>,
+    /// Where the car should be at `time`.
+    pub car_loc: Point3<f32>,
+    pub plan: AerialPlan,
+}
+
+struct Flight {
+    /// Absolute game time.
+    intercept_time: f32,
+    child: AerialLocTime,
+}
+
+impl AerialShot {
+    /// Don't plan aerials further out than this; the prediction is too
+    /// uncertain and the enemy will have gotten there first.
+    const MAX_TIME: f32 = 4.0;
+    /// If the ball prediction drifts this far from where we aimed, give up.
+    const MAX_TARGET_DRIFT: f32 = 150.0;
+
+    pub fn shoot() -> Self {
+        Self::new(Aim::Shoot)
+    }
+
+    pub fn clear() -> Self {
+        Self::new(Aim::Clear)
+    }
+
+    fn new(aim: Aim) -> Self {
+        Self { aim, flight: None }
+    }
+
+    /// Find the soonest airborne ball we can reach with an aerial aimed at the
+    /// enemy goal.
+    pub fn viable_shot(
+        game: &Game<'_>,
+        scenario: &Scenario<'_>,
+        car: &common::halfway_house::PlayerInfo,
+    ) -> Option<AerialIntercept> {
+        find_intercept(game, scenario.ball_prediction(), car, Aim::Shoot)
+    }
+
+    /// Find the soonest airborne ball we can reach with an aerial that knocks
+    /// it away from our goal.
+    pub fn viable_clear(
+        game: &Game<'_>,
+        scenario: &Scenario<'_>,
+        car: &common::halfway_house::PlayerInfo,
+    ) -> Option<AerialIntercept> {
+        find_intercept(game, scenario.ball_prediction(), car, Aim::Clear)
+    }
+
+    /// Where the car needs to be to touch `ball` towards the enemy goal.
+    pub fn shot_contact_loc(
+        game: &Game<'_>,
+        car_loc: Point3<f32>,
+        ball: &BallFrame,
+    ) -> Point3<f32> {
+        contact_loc(game, car_loc, ball, Aim::Shoot)
+    }
+}
+
+impl Behavior for AerialShot {
+    fn name(&self) -> &str {
+        name_of_type!(AerialShot)
+    }
+
+    fn priority(&self) -> Priority {
+        Priority::Strike
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        let now = ctx.packet.GameInfo.TimeSeconds;
+
+        if self.flight.is_none() {
+            let intercept = some_or_else!(
+                find_intercept(ctx.game, ctx.scenario.ball_prediction(), ctx.me(), self.aim),
+                {
+                    ctx.eeg.log(self.name(), "no viable aerial");
+                    return Action::Abort;
+                }
+            );
+            ctx.eeg
+                .log_pretty(self.name(), "intercept_time", Time(intercept.time));
+            self.flight = Some(Flight {
+                intercept_time: now + intercept.time,
+                child: AerialLocTime::new(intercept.car_loc, intercept.time),
+            });
+        }
+        let flight = self.flight.as_mut().unwrap();
+
+        // Follow the ball if the prediction changes slightly, but if it moves a lot
+        // (e.g. someone else touched it) the plan is no longer any good.
+        let time_remaining = (flight.intercept_time - now).max(0.0);
+        let ball = ctx
+            .scenario
+            .ball_prediction()
+            .at_time_or_last(time_remaining);
+        let car_loc = contact_loc(ctx.game, ctx.me().Physics.loc(), ball, self.aim);
+        if (car_loc - flight.child.target_loc()).norm() >= Self::MAX_TARGET_DRIFT {
+            ctx.eeg
+                .log(name_of_type!(AerialShot), "ball went somewhere else");
+            return Action::Abort;
+        }
+        flight.child.retarget(car_loc);
+        ctx.eeg.draw(Drawable::ghost_ball(ball.loc));
+
+        flight.child.execute_old(ctx)
+    }
+}
+
+fn find_intercept(
+    game: &Game<'_>,
+    ball_prediction: &BallTrajectory,
+    car: &common::halfway_house::PlayerInfo,
+    aim: Aim,
+) -> Option<AerialIntercept> {
+    let car_loc = car.Physics.loc();
+    ball_prediction
+        .iter()
+        .take_while(|ball| ball.t < AerialShot::MAX_TIME)
+        .filter(|ball| ball.loc.z >= GroundedHitTarget::MAX_BALL_Z)
+        .find_map(|ball| {
+            let target_loc = contact_loc(game, car_loc, ball, aim);
+
+            // We can turn a little during the jump, but not all the way around.
+            let car_to_target = (target_loc - car_loc).to_2d();
+            if car.Physics.forward_axis_2d().angle_to(&car_to_target).abs() >= PI / 3.0 {
+                return None;
+            }
+
+            // Make sure we're coming at the ball from roughly behind, so we push it the
+            // way we want.
+            let ball_to_aim =
+                aim_loc(game, car_loc.to_2d(), ball.loc.to_2d(), aim) - ball.loc.to_2d();
+            if car_to_target.angle_to(&ball_to_aim).abs() >= PI / 3.0 {
+                return None;
+            }
+
+            let plan = AerialLocTime::feasible(car, target_loc, ball.t).ok()?;
+            Some(AerialIntercept {
+                time: ball.t,
+                ball_loc: ball.loc,
+                car_loc: target_loc,
+                plan,
+            })
+        })
+}
+
+fn aim_loc(game: &Game<'_>, car_loc: Point2<f32>, ball_loc: Point2<f32>, aim: Aim) -> Point2<f32> {
+    match aim {
+        Aim::Shoot => BounceShot::aim_loc(game.enemy_goal(), car_loc, ball_loc),
+        Aim::Clear => ball_loc + (ball_loc - game.own_goal().center_2d).normalize() * 1000.0,
+    }
+}
+
+/// Where the car needs to be to touch `ball` such that it heads towards the aim
+/// location.
+fn contact_loc(game: &Game<'_>, car_loc: Point3<f32>, ball: &BallFrame, aim: Aim) -> Point3<f32> {
+    let aim_loc = aim_loc(game, car_loc.to_2d(), ball.loc.to_2d(), aim);
+    let aim_dir = (aim_loc - ball.loc.to_2d()).to_axis();
+    let contact_dist = game.ball_radius() + game.me_vehicle().pivot_to_front_dist();
+    ball.loc - aim_dir.to_3d().into_inner() * contact_dist
+}
+
 #[cfg(test)]
 mod integration_tests {
-    use crate::integration_tests::{TestRunner, TestScenario};
+    use crate::{
+        behavior::strike::AerialShot,
+        integration_tests::{TestRunner, TestScenario},
+    };
     use common::prelude::*;
     use nalgebra::{Point3, Rotation3, Vector3};
 
     #[test]
-    #[ignore = "TODO"]
     fn easy_in_front_of_goal() {
         let test = TestRunner::new()
             .scenario(TestScenario {
@@ -16,8 +216,42 @@ mod integration_tests {
                 car_vel: Vector3::new(357.6586, 1213.9453, 8.309999),
                 ..Default::default()
             })
+            .behavior(AerialShot::shoot())
             .run_for_millis(4000);
 
         assert!(test.has_scored());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        behavior::strike::aerial_shot::{find_intercept, Aim},
+        routing::{models::CarState, test::with_offline_planning_context},
+    };
+    use common::prelude::*;
+    use nalgebra::{Point3, Rotation3, UnitQuaternion, Vector3};
+
+    /// The planning half of `easy_in_front_of_goal`, which runs without a game.
+    #[test]
+    fn finds_easy_in_front_of_goal() {
+        let start = CarState {
+            loc: Point3::new(-1604.453, 2690.225, 17.01),
+            rot: UnitQuaternion::from_rotation_matrix(&Rotation3::from_unreal_angles(
+                -0.009683254,
+                1.2704238,
+                -0.0000958738,
+            )),
+            vel: Vector3::new(357.6586, 1213.9453, 8.309999),
+            boost: 100.0,
+        };
+        let ball_loc = Point3::new(2947.987, 2573.8042, 954.9597);
+        let ball_vel = Vector3::new(-1540.0411, 924.74066, -1316.2262);
+        with_offline_planning_context(&start, ball_loc, ball_vel, |ctx| {
+            let intercept =
+                find_intercept(ctx.game, ctx.ball_prediction, ctx.game.me(), Aim::Shoot);
+            let intercept = intercept.expect("no aerial intercept");
+            assert!(intercept.time > 0.0);
+        });
+    }
+}
@@ -0,0 +1,273 @@
+use crate::{
+    behavior::movement::Dodge,
+    eeg::{color, Drawable, EEG},
+    routing::{behavior::FollowRoute, plan::WallToCeilingPlanner, CeilingChart},
+    strategy::{Action, Behavior, Context, Context2, Game, Priority, Scenario},
+    utils::Stopwatch,
+};
+use common::{
+    kinematics::{kinematic, kinematic_time},
+    prelude::*,
+    rl, Time,
+};
+use nalgebra::{Point2, Point3, Vector3};
+use nameof::name_of_type;
+
+/// Drive up a side wall onto the ceiling, drop off at the right moment with
+/// the dodge still in hand, and dodge into the ball as it falls.
+pub struct CeilingShot;
+
+pub struct CeilingIntercept {
+    /// Seconds from now.
+    pub time: f32,
+    pub ball_loc: Point3<f32>,
+    /// Where on the ceiling to drive to.
+    pub ceiling_loc: Point3<f32>,
+}
+
+impl CeilingShot {
+    /// The whole play takes several seconds to set up, so only try it when
+    /// nobody is around to interrupt.
+    pub const MIN_POSSESSION: f32 = Scenario::POSSESSION_SATURATED;
+    const MIN_BALL_Z: f32 = 800.0;
+    const MAX_BALL_Z: f32 = rl::FIELD_MAX_Z - 300.0;
+    /// Stay out of the corners, where the wall and ceiling don't meet cleanly.
+    const MAX_BALL_Y: f32 = 3500.0;
+    /// A rough average speed for the trip up the wall, for ruling out balls we
+    /// can't make it to.
+    const CLIMB_SPEED: f32 = 1500.0;
+    /// Time to spare on the ceiling, to line up the drop.
+    const SLACK_TIME: f32 = 0.5;
+    /// Come down on the far side of the ball from the enemy goal.
+    const CONTACT_OFFSET: f32 = rl::BALL_RADIUS + 50.0;
+
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn viable(
+        game: &Game<'_>,
+        scenario: &Scenario<'_>,
+        car: &common::halfway_house::PlayerInfo,
+    ) -> Option<CeilingIntercept> {
+        if !car.OnGround {
+            return None;
+        }
+
+        let car_loc = car.Physics.loc();
+        for ball in scenario.ball_prediction().iter() {
+            if ball.loc.z < Self::MIN_BALL_Z
+                || ball.loc.z >= Self::MAX_BALL_Z
+                || ball.vel.z >= 0.0
+                || ball.loc.y.abs() >= Self::MAX_BALL_Y
+            {
+                continue;
+            }
+
+            let drop_time = ball.t - fall_time(ball.loc.z);
+            if drop_time <= 0.0 {
+                continue;
+            }
+
+            let contact_loc = Self::contact_loc(game, ball.loc);
+            let ceiling_loc = contact_loc.to_3d(rl::FIELD_MAX_Z - rl::OCTANE_NEUTRAL_Z);
+            let chart = some_or_else!(CeilingChart::new(game.pitch(), ball.loc.x).ok(), {
+                continue;
+            });
+            let flattener = some_or_else!(chart.flattener(game.pitch(), &car_loc), {
+                continue;
+            });
+            let climb_distance = (chart.flatten_ceiling(ceiling_loc) - flattener * car_loc).norm();
+            if climb_distance / Self::CLIMB_SPEED + Self::SLACK_TIME >= drop_time {
+                continue;
+            }
+
+            return Some(CeilingIntercept {
+                time: ball.t,
+                ball_loc: ball.loc,
+                ceiling_loc,
+            });
+        }
+        None
+    }
+
+    /// Where the car should be (horizontally) when it meets the ball.
+    fn contact_loc(game: &Game<'_>, ball_loc: Point3<f32>) -> Point2<f32> {
+        let aim_dir = (game.enemy_goal().center_2d - ball_loc.to_2d()).to_axis();
+        ball_loc.to_2d() - aim_dir.into_inner() * Self::CONTACT_OFFSET
+    }
+}
+
+/// How long it takes to fall from the ceiling to the given height.
+fn fall_time(z: f32) -> f32 {
+    let ceiling_z = rl::FIELD_MAX_Z - rl::OCTANE_NEUTRAL_Z;
+    kinematic_time(z - ceiling_z, 0.0, rl::GRAVITY).unwrap_or(0.0)
+}
+
+impl Behavior for CeilingShot {
+    fn name(&self) -> &str {
+        name_of_type!(CeilingShot)
+    }
+
+    fn priority(&self) -> Priority {
+        Priority::Strike
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        let intercept = some_or_else!(Self::viable(ctx.game, &ctx.scenario, ctx.me()), {
+            ctx.eeg.log(self.name(), "no viable intercept");
+            return Action::Abort;
+        });
+
+        ctx.eeg
+            .log_pretty(self.name(), "intercept_time", Time(intercept.time));
+        ctx.eeg
+            .log_pretty(self.name(), "ball_loc", intercept.ball_loc);
+        ctx.eeg
+            .log_pretty(self.name(), "ceiling_loc", intercept.ceiling_loc);
+
+        Action::tail_call(chain!(Priority::Strike, [
+            FollowRoute::new(WallToCeilingPlanner::new(intercept.ceiling_loc)),
+            CeilingDrop::new(),
+        ]))
+    }
+}
+
+/// Drive along the ceiling until dropping off would meet the ball, then fall
+/// and dodge into it.
+struct CeilingDrop {
+    phase: Phase,
+    time: Stopwatch,
+}
+
+#[derive(Eq, PartialEq)]
+enum Phase {
+    Ceiling,
+    Detaching,
+    Falling,
+}
+
+impl CeilingDrop {
+    /// Give up if the drop hasn't lined up by now.
+    const TIME_LIMIT: f32 = 4.0;
+    /// Drop if we'd come down within this distance of the contact point.
+    const DROP_TOLERANCE: f32 = 120.0;
+    /// Start the dodge once the ball is this close.
+    const DODGE_DISTANCE: f32 = rl::BALL_RADIUS + 150.0;
+
+    fn new() -> Self {
+        Self {
+            phase: Phase::Ceiling,
+            time: Stopwatch::new(),
+        }
+    }
+
+    /// If we let go of the ceiling right now, how far would we miss the
+    /// contact point by? Returns the contact point and the miss distance.
+    fn drop_now_miss(ctx: &Context2<'_, '_>) -> Option<(Point2<f32>, f32)> {
+        let me = ctx.me();
+        let gravity = Vector3::z() * rl::GRAVITY;
+        for ball in ctx.scenario.ball_prediction().iter() {
+            let (offset, _vel) = kinematic(me.Physics.vel(), gravity, ball.t);
+            let car_loc = me.Physics.loc() + offset;
+            if car_loc.z <= ball.loc.z {
+                let contact_loc = CeilingShot::contact_loc(ctx.game, ball.loc);
+                return Some((contact_loc, (car_loc.to_2d() - contact_loc).norm()));
+            }
+        }
+        None
+    }
+}
+
+impl Behavior for CeilingDrop {
+    fn name(&self) -> &str {
+        name_of_type!(CeilingDrop)
+    }
+
+    fn priority(&self) -> Priority {
+        Priority::Strike
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        let elapsed = self.time.tick(ctx.packet.GameInfo.TimeSeconds);
+        if elapsed >= Self::TIME_LIMIT {
+            ctx.eeg.log(self.name(), "time limit exceeded");
+            return Action::Abort;
+        }
+
+        let (ctx, eeg) = ctx.split();
+        let me = ctx.me();
+
+        match self.phase {
+            Phase::Ceiling => {
+                if !me.OnGround || me.Physics.roof_axis().z > -0.9 {
+                    eeg.log(self.name(), "not on the ceiling");
+                    return Action::Abort;
+                }
+
+                let (contact_loc, miss) = some_or_else!(Self::drop_now_miss(&ctx), {
+                    eeg.log(self.name(), "the ball isn't coming down in time");
+                    return Action::Abort;
+                });
+                eeg.print_distance("drop_miss", miss);
+                eeg.draw(Drawable::ghost_ball(contact_loc.to_3d(rl::BALL_RADIUS)));
+
+                if miss < Self::DROP_TOLERANCE {
+                    eeg.log(self.name(), "dropping");
+                    self.phase = Phase::Detaching;
+                    return Action::Yield(Default::default());
+                }
+
+                // Keep moving so we stay stuck to the ceiling, and steer over
+                // the spot where we'll need to drop.
+                let me_forward = me.Physics.forward_axis();
+                let target_loc = contact_loc.to_3d(me.Physics.loc().z);
+                let rotation = me_forward.rotation_to(&(target_loc - me.Physics.loc()).to_axis());
+                let steer = rotation.project_2d(&me.Physics.roof_axis());
+                Action::Yield(common::halfway_house::PlayerInput {
+                    Throttle: 1.0,
+                    Steer: steer.angle().max(-1.0).min(1.0),
+                    ..Default::default()
+                })
+            }
+            Phase::Detaching => {
+                // Let go of the throttle and wait for gravity. Don't jump, so
+                // we still have a dodge for the shot.
+                if me.OnGround {
+                    return Action::Yield(Default::default());
+                }
+                self.phase = Phase::Falling;
+                self.fall(&ctx, eeg)
+            }
+            Phase::Falling => {
+                if me.OnGround {
+                    eeg.log(self.name(), "landed without touching the ball");
+                    return Action::Return;
+                }
+                self.fall(&ctx, eeg)
+            }
+        }
+    }
+}
+
+impl CeilingDrop {
+    fn fall(&self, ctx: &Context2<'_, '_>, eeg: &mut EEG) -> Action {
+        let me = ctx.me();
+        let ball_loc = ctx.packet.GameBall.Physics.loc();
+        let to_ball = ball_loc - me.Physics.loc();
+
+        if to_ball.norm() < Self::DODGE_DISTANCE {
+            eeg.log(self.name(), "dodging into the ball");
+            return Action::tail_call(Dodge::new().towards_ball());
+        }
+
+        eeg.draw(Drawable::print("falling towards ball", color::GREEN));
+        let (pitch, yaw, roll) = dom::get_pitch_yaw_roll(me, to_ball.to_axis(), Vector3::z_axis());
+        Action::Yield(common::halfway_house::PlayerInput {
+            Pitch: pitch,
+            Yaw: yaw,
+            Roll: roll,
+            ..Default::default()
+        })
+    }
+}
@@ -26,7 +26,13 @@ impl Behavior for FiftyFifty {
         Action::tail_call(While::new(
             WeDontWinTheRace,
             Chain::new(Priority::Idle, vec![
-                Box::new(FollowRoute::new(GroundIntercept::new()).same_ball_trajectory(true)),
+                // We're racing them to the ball, so expect to trade bumps on the way.
+                // A fresh plan each time would only slow us down.
+                Box::new(
+                    FollowRoute::new(GroundIntercept::new())
+                        .same_ball_trajectory(true)
+                        .replan_on_drift(false),
+                ),
                 Box::new(GroundedHit::hit_towards(defensive_hit)),
             ]),
         ))
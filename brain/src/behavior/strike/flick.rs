@@ -0,0 +1,275 @@
+use crate::{
+    behavior::movement::{Dodge, QuickJumpAndDodge, Yielder},
+    helpers::telepathy,
+    strategy::{Action, Behavior, Context, Context2, Priority},
+};
+use common::prelude::*;
+use nalgebra::UnitComplex;
+use nameof::name_of_type;
+use std::f32::consts::PI;
+
+/// Release a ball we're carrying on the roof.
+pub struct Flick {
+    kind: FlickKind,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum FlickKind {
+    /// A diagonal dodge that sends the ball off to one side.
+    FortyFive { right: bool },
+    /// Tilt back and backflip, lobbing the ball high over whoever is in front.
+    Musty,
+    /// Jump and hang there with the ball on the roof until the enemy commits,
+    /// then dodge forward.
+    Delayed,
+}
+
+impl Flick {
+    /// If the ball has slid this far towards the back of the roof, a forward
+    /// dodge would just knock it off behind us.
+    const BALL_BACK_OF_ROOF: f32 = 25.0;
+    /// An enemy further away than this (in seconds) hasn't committed yet, so
+    /// it's worth waiting them out.
+    const ENEMY_COMMIT_TIME: f32 = 1.0;
+    /// How long to hold jump before the dodge, so the ball has a moment to
+    /// lift off the roof.
+    const POP_TIME: f32 = 0.1;
+    const MUSTY_TILT_TIME: f32 = 0.1;
+
+    pub fn new(kind: FlickKind) -> Self {
+        Self { kind }
+    }
+
+    /// Pick the flick that best suits where the ball sits on the roof and
+    /// where the enemy is coming from.
+    pub fn choose(ctx: &Context2<'_, '_>) -> FlickKind {
+        let me = ctx.me();
+        let ball_loc = ctx.packet.GameBall.Physics.loc();
+        let local_ball = me.Physics.quat().inverse() * (ball_loc - me.Physics.loc());
+
+        if local_ball.x < -Self::BALL_BACK_OF_ROOF {
+            return FlickKind::Musty;
+        }
+
+        let (enemy, intercept) = match ctx.scenario.enemy_intercept() {
+            Some(x) => x,
+            None => return FlickKind::Delayed,
+        };
+        if intercept.time >= Self::ENEMY_COMMIT_TIME {
+            return FlickKind::Delayed;
+        }
+
+        // If they're coming straight at us, go over the top.
+        let forward = me.Physics.forward_axis_2d();
+        let head_on = telepathy::predict_enemy_hit_direction_2(ctx)
+            .map(|dir| dir.angle_to(&forward.into_inner()).abs() >= PI * 0.75)
+            .unwrap_or(false);
+        if head_on {
+            return FlickKind::Musty;
+        }
+
+        // Otherwise send it away from the side they're approaching on.
+        let right_axis = me.Physics.right_axis().to_2d();
+        let enemy_side =
+            (enemy.Physics.loc_2d() - me.Physics.loc_2d()).dot(&right_axis.into_inner());
+        FlickKind::FortyFive {
+            right: enemy_side < 0.0,
+        }
+    }
+}
+
+impl Behavior for Flick {
+    fn name(&self) -> &str {
+        name_of_type!(Flick)
+    }
+
+    fn priority(&self) -> Priority {
+        Priority::Strike
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        if !ctx.me().OnGround {
+            ctx.eeg.log(self.name(), "wheels must be on ground");
+            return Action::Abort;
+        }
+
+        ctx.eeg.log(self.name(), format!("{:?}", self.kind));
+        match self.kind {
+            FlickKind::FortyFive { right } => {
+                let angle = if right { PI / 4.0 } else { -PI / 4.0 };
+                Action::tail_call(
+                    QuickJumpAndDodge::new()
+                        .jump_time(Self::POP_TIME)
+                        .angle(angle),
+                )
+            }
+            FlickKind::Musty => Action::tail_call(chain!(Priority::Strike, [
+                Yielder::new(Self::POP_TIME, common::halfway_house::PlayerInput {
+                    Jump: true,
+                    ..Default::default()
+                }),
+                // Nose up, so the backflip scoops under the ball.
+                Yielder::new(Self::MUSTY_TILT_TIME, common::halfway_house::PlayerInput {
+                    Pitch: 1.0,
+                    ..Default::default()
+                }),
+                Dodge::new().angle(UnitComplex::new(PI)),
+            ])),
+            FlickKind::Delayed => Action::tail_call(chain!(Priority::Strike, [
+                Yielder::new(Self::POP_TIME, common::halfway_house::PlayerInput {
+                    Jump: true,
+                    ..Default::default()
+                }),
+                HoldBall::new(),
+                Dodge::new(),
+            ])),
+        }
+    }
+}
+
+/// While airborne after a delayed flick's jump, wait for the ball to settle
+/// back onto the roof (or start to slide off it), then return so the dodge
+/// can follow.
+struct HoldBall {
+    start: Option<f32>,
+    lifted: bool,
+}
+
+impl HoldBall {
+    /// The jump pops the ball up off the roof. Once it's falling back and this
+    /// close above the car's center (in the car's frame), it's ready to be
+    /// flicked.
+    const RESETTLED_HEIGHT: f32 = 150.0;
+    /// If the ball drifts this far forward or back along the roof, flick now
+    /// before it falls off.
+    const MAX_SLIDE: f32 = 60.0;
+    /// An enemy this close (in seconds) is about to take the ball off us.
+    const ENEMY_ARRIVAL_TIME: f32 = 0.3;
+    /// Never wait longer than this, or the dodge will expire.
+    const MAX_HOLD_TIME: f32 = 1.0;
+
+    fn new() -> Self {
+        Self {
+            start: None,
+            lifted: false,
+        }
+    }
+}
+
+impl Behavior for HoldBall {
+    fn name(&self) -> &str {
+        name_of_type!(HoldBall)
+    }
+
+    fn priority(&self) -> Priority {
+        Priority::Strike
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        let now = ctx.packet.GameInfo.TimeSeconds;
+        let start = *self.start.get_or_insert(now);
+
+        let me = ctx.me();
+        let to_local = me.Physics.quat().inverse();
+        let ball = &ctx.packet.GameBall.Physics;
+        let local_ball = to_local * (ball.loc() - me.Physics.loc());
+        let local_ball_vel = to_local * (ball.vel() - me.Physics.vel());
+
+        self.lifted |= local_ball.z >= Self::RESETTLED_HEIGHT;
+        let settled =
+            self.lifted && local_ball.z < Self::RESETTLED_HEIGHT && local_ball_vel.z <= 0.0;
+        let sliding = local_ball.x.abs() >= Self::MAX_SLIDE;
+        let challenged = ctx
+            .scenario
+            .enemy_intercept()
+            .map(|(_, intercept)| intercept.time < Self::ENEMY_ARRIVAL_TIME)
+            .unwrap_or(false);
+        let expired = now - start >= Self::MAX_HOLD_TIME;
+
+        ctx.eeg.print_value("local_ball", local_ball);
+        if settled || sliding || challenged || expired {
+            ctx.eeg.log(
+                self.name(),
+                format!(
+                    "release: settled={} sliding={} challenged={} expired={}",
+                    settled, sliding, challenged, expired,
+                ),
+            );
+            return Action::Return;
+        }
+
+        // Let go of jump, so the dodge registers as a fresh press.
+        Action::Yield(Default::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        behavior::strike::flick::{Flick, FlickKind},
+        routing::{models::CarState, test::with_offline_context_and_enemies},
+    };
+    use common::{prelude::*, rl};
+    use nalgebra::{Point2, Point3, UnitComplex, Vector3};
+    use std::f32::consts::PI;
+
+    /// Our car sits at the origin facing +y with the ball on the roof, offset
+    /// forward by `ball_forward`.
+    fn choose(ball_forward: f32, enemies: &[CarState]) -> FlickKind {
+        let car = CarState {
+            loc: Point3::new(0.0, 0.0, rl::OCTANE_NEUTRAL_Z),
+            rot: UnitComplex::new(PI / 2.0).around_z_axis(),
+            vel: Vector3::zeros(),
+            boost: 50.0,
+        };
+        let ball_loc = Point3::new(0.0, ball_forward, 160.0);
+        with_offline_context_and_enemies(&car, enemies, ball_loc, Vector3::zeros(), 100.0, |ctx| {
+            let (ctx, _eeg) = ctx.split();
+            Flick::choose(&ctx)
+        })
+    }
+
+    /// An enemy at `loc`, at rest and facing the ball.
+    fn enemy_at(loc: Point2<f32>) -> CarState {
+        let to_ball = Point2::origin() - loc;
+        CarState {
+            loc: loc.to_3d(rl::OCTANE_NEUTRAL_Z),
+            rot: UnitComplex::new(to_ball.y.atan2(to_ball.x)).around_z_axis(),
+            vel: Vector3::zeros(),
+            boost: 50.0,
+        }
+    }
+
+    #[test]
+    fn musty_when_the_ball_slides_back() {
+        assert_eq!(choose(-40.0, &[]), FlickKind::Musty);
+    }
+
+    #[test]
+    fn delayed_when_unchallenged() {
+        assert_eq!(choose(0.0, &[]), FlickKind::Delayed);
+        let far = enemy_at(Point2::new(0.0, 4000.0));
+        assert_eq!(choose(0.0, &[far]), FlickKind::Delayed);
+    }
+
+    #[test]
+    fn musty_over_a_head_on_challenge() {
+        let enemy = enemy_at(Point2::new(0.0, 600.0));
+        assert_eq!(choose(0.0, &[enemy]), FlickKind::Musty);
+    }
+
+    #[test]
+    fn forty_five_away_from_the_challenger() {
+        // For a car facing +y, right is -x.
+        let from_right = enemy_at(Point2::new(-600.0, -300.0));
+        assert_eq!(
+            choose(0.0, &[from_right]),
+            FlickKind::FortyFive { right: false },
+        );
+        let from_left = enemy_at(Point2::new(600.0, -300.0));
+        assert_eq!(
+            choose(0.0, &[from_left]),
+            FlickKind::FortyFive { right: true },
+        );
+    }
+}
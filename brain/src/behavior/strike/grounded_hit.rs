@@ -5,7 +5,12 @@ use crate::{
         strike::BounceShot,
     },
     eeg::{color, Drawable, EEG},
-    helpers::intercept::{naive_ground_intercept, NaiveIntercept},
+    helpers::{
+        intercept::NaiveIntercept,
+        intercept_optimizer::{
+            draw_ranking, rank_ground_intercepts, InterceptWeights, RankedIntercept,
+        },
+    },
     routing::recover::{IsSkidding, NotOnFlatGround},
     strategy::{Action, Behavior, Context, Game, Priority, Scenario},
     utils::intercept_memory::{InterceptMemory, InterceptMemoryResult},
@@ -26,8 +31,13 @@ where
 {
     aim: Aim,
     intercept: InterceptMemory,
+    weights: InterceptWeights,
+    ranked_by: Option<RankIntercepts>,
 }
 
+/// Intercepts ranked by the caller, best first.
+pub type RankIntercepts = fn(&Scenario<'_>) -> Vec<RankedIntercept>;
+
 impl<Aim> GroundedHit<Aim>
 where
     Aim: Fn(&mut GroundedHitAimContext<'_, '_>) -> Result<GroundedHitTarget, ()> + Send,
@@ -36,8 +46,22 @@ where
         Self {
             aim,
             intercept: InterceptMemory::new(),
+            // The aim isn't known until there's an intercept, so by default
+            // leave shot quality out of it.
+            weights: InterceptWeights {
+                shot: 0.0,
+                ..Default::default()
+            },
+            ranked_by: None,
         }
     }
+
+    /// Go by the caller's ranking instead of our own, so we go for the
+    /// intercept it had in mind.
+    pub fn rank_by(mut self, ranked_by: RankIntercepts) -> Self {
+        self.ranked_by = Some(ranked_by);
+        self
+    }
 }
 
 impl GroundedHit<fn(&mut GroundedHitAimContext<'_, '_>) -> Result<GroundedHitTarget, ()>> {
@@ -127,30 +151,58 @@ where
     Aim: Fn(&mut GroundedHitAimContext<'_, '_>) -> Result<GroundedHitTarget, ()> + Send,
 {
     fn intercept_loc(&mut self, ctx: &mut Context<'_>) -> Result<NaiveIntercept, ()> {
-        let me = ctx.me();
-
-        // First pass: get approximate jump height
-        let intercept = naive_ground_intercept(
-            ctx.scenario.ball_prediction().iter(),
-            me.Physics.loc(),
-            me.Physics.vel(),
-            me.Boost as f32,
-            |ball| ball.loc.z < GroundedHit::MAX_BALL_Z,
-        );
-        let intercept = some_or_else!(intercept, {
+        // First pass: get approximate jump height.
+        let ranked = self.rank_intercepts(ctx, GroundedHit::MAX_BALL_Z);
+        let best = some_or_else!(ranked.first(), {
             ctx.eeg.log(self.name(), "can't find intercept");
             return Err(());
         });
 
-        self.intercept_phase_2(ctx, intercept)
+        let ranked = match self.intercept_phase_2(ctx, &best.intercept)? {
+            None => ranked,
+            Some(ball_max_z) => {
+                let ranked = self.rank_intercepts(ctx, ball_max_z);
+                if ranked.is_empty() {
+                    ctx.eeg.log(self.name(), "can't find phase-two intercept");
+                    return Err(());
+                }
+                ctx.eeg.draw(Drawable::print("two-phase", color::GREEN));
+                ranked
+            }
+        };
+
+        draw_ranking(ctx.eeg, &ranked);
+        Ok(ranked.into_iter().next().unwrap().intercept)
+    }
+
+    fn rank_intercepts(&self, ctx: &Context<'_>, ball_max_z: f32) -> Vec<RankedIntercept> {
+        if let Some(ranked_by) = self.ranked_by {
+            // Scores don't depend on which frames are left out, so filtering
+            // the ranking is the same as ranking the frames that pass.
+            return ranked_by(&ctx.scenario)
+                .into_iter()
+                .filter(|r| r.intercept.ball_loc.z < ball_max_z)
+                .collect();
+        }
+
+        let enemy_time = ctx.scenario.enemy_intercept().map(|(_, i)| i.time);
+        rank_ground_intercepts(
+            ctx.game,
+            &ctx.me().into(),
+            enemy_time,
+            &self.weights,
+            ctx.scenario.ball_prediction().iter(),
+            |ball| ball.loc.z < ball_max_z,
+        )
     }
 
     // Phase 2: Get a more accurate intercept based on how high we need to jump.
+    // Returns the height limit to rank again with, if we should.
     fn intercept_phase_2(
         &self,
         ctx: &mut Context<'_>,
-        intercept: NaiveIntercept,
-    ) -> Result<NaiveIntercept, ()> {
+        intercept: &NaiveIntercept,
+    ) -> Result<Option<f32>, ()> {
         let me = ctx.me();
 
         // This second phase is good and I want to do it, but sometimes it's not good
@@ -163,7 +215,7 @@ where
         if in_defense && ctx.scenario.possession().abs() < Scenario::POSSESSION_CONTESTABLE {
             // Proceed below.
         } else {
-            return Ok(intercept);
+            return Ok(None);
         }
 
         let mut aim_context = GroundedHitAimContext {
@@ -177,22 +229,8 @@ where
         };
         let target = (self.aim)(&mut aim_context)
             .map_err(|_| ctx.eeg.log(self.name(), "error getting aim location"))?;
-        let (target_loc, _target_rot) = Self::preliminary_target(ctx, &intercept, &target);
-        let ball_max_z = JUMP_MAX_Z + (intercept.ball_loc.z - target_loc.z);
-
-        let intercept = naive_ground_intercept(
-            ctx.scenario.ball_prediction().iter(),
-            me.Physics.loc(),
-            me.Physics.vel(),
-            me.Boost as f32,
-            |ball| ball.loc.z < ball_max_z,
-        );
-        let intercept = some_or_else!(intercept, {
-            ctx.eeg.log(self.name(), "can't find phase-two intercept");
-            return Err(());
-        });
-        ctx.eeg.draw(Drawable::print("two-phase", color::GREEN));
-        Ok(intercept)
+        let (target_loc, _target_rot) = Self::preliminary_target(ctx, intercept, &target);
+        Ok(Some(JUMP_MAX_Z + (intercept.ball_loc.z - target_loc.z)))
     }
 
     fn plan(&mut self, ctx: &mut Context<'_>, intercept: &NaiveIntercept) -> Result<Plan, ()> {
@@ -1,6 +1,9 @@
 pub use self::{
+    aerial_shot::{AerialIntercept, AerialShot},
     bounce_shot::BounceShot,
+    ceiling_shot::{CeilingIntercept, CeilingShot},
     fifty_fifty::FiftyFifty,
+    flick::{Flick, FlickKind},
     grounded_hit::{
         GroundedHit, GroundedHitAimContext, GroundedHitTarget, GroundedHitTargetAdjust,
     },
@@ -9,7 +12,9 @@ pub use self::{
 
 mod aerial_shot;
 mod bounce_shot;
+mod ceiling_shot;
 mod fifty_fifty;
+mod flick;
 mod ground_shot;
 mod grounded_hit;
 mod jump_shot;
@@ -1,4 +1,4 @@
-use crate::strategy::{Action, Behavior, Context, Priority};
+use crate::strategy::{Action, Behavior, Channel, Context, Priority};
 use nameof::name_of_type;
 
 pub struct SaltWhileDemolished;
@@ -23,11 +23,12 @@ impl Behavior for SaltWhileDemolished {
             return Action::Return;
         }
 
-        if ctx.packet.GameInfo.TimeSeconds - *ctx.last_quick_chat >= 0.75 {
-            ctx.eeg
-                .quick_chat(rlbot::flat::QuickChatSelection::Reactions_Okay);
-            *ctx.last_quick_chat = ctx.packet.GameInfo.TimeSeconds;
-        }
+        // The cooldown on reactions sets the pace of the salt.
+        ctx.chat.send(
+            ctx.packet.GameInfo.TimeSeconds,
+            rlbot::flat::QuickChatSelection::Reactions_Okay,
+            Channel::Everyone,
+        );
 
         Action::Yield(Default::default())
     }
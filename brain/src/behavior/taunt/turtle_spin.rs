@@ -116,11 +116,6 @@ impl TurtleSpin {
         }
         self.has_chatted = true;
 
-        if ctx.packet.GameInfo.TimeSeconds - *ctx.last_quick_chat < 1.0 {
-            return;
-        }
-        *ctx.last_quick_chat = ctx.packet.GameInfo.TimeSeconds;
-
         ctx.quick_chat(self.quick_chat_probability, &[
             rlbot::flat::QuickChatSelection::Compliments_WhatAPlay,
             rlbot::flat::QuickChatSelection::Reactions_OMG,
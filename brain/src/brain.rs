@@ -2,9 +2,15 @@
 use crate::strategy::Behavior;
 use crate::{
     eeg::{color, Drawable, EEG},
-    helpers::ball::{BallPredictor, ChipBallPrediction, FrameworkBallPrediction},
-    strategy::{infer_game_mode, Context, Dropshot, Game, Runner, Scenario, Soccar},
-    utils::FPSCounter,
+    helpers::ball::{
+        BallPredictor, BallTrajectory, ChipBallPrediction, FrameworkBallPrediction,
+        SharedBallPrediction,
+    },
+    strategy::{
+        infer_game_mode, Context, Dropshot, Game, OutgoingQuickChat, QuickChat, Runner, Scenario,
+        Soccar, Team, TeamIntents,
+    },
+    utils::{FPSCounter, Rng},
 };
 use common::{prelude::*, ControllerInput, ExtendDuration};
 use nalgebra::{clamp, Point3};
@@ -16,9 +22,9 @@ pub struct Brain {
     ball_predictor: Box<dyn BallPredictor>,
     player_index: Option<i32>,
     fps_counter: FPSCounter,
-    /// This is not automated or enforced in any way, it's just a convenient
-    /// memory slot for optional use in behaviors.
-    last_quick_chat: f32,
+    quick_chat: QuickChat,
+    rng: Rng,
+    seed_logged: bool,
 }
 
 impl Brain {
@@ -28,7 +34,9 @@ impl Brain {
             ball_predictor: Box::new(ball_predictor),
             player_index: None,
             fps_counter: FPSCounter::new(),
-            last_quick_chat: 0.0,
+            quick_chat: QuickChat::new(),
+            rng: Rng::from_time(),
+            seed_logged: false,
         }
     }
 
@@ -70,14 +78,59 @@ impl Brain {
         self.player_index = Some(player_index);
     }
 
+    /// Replace the random number stream with one started from `seed`, so that a
+    /// run can be reproduced.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = Rng::new(seed);
+        self.seed_logged = false;
+    }
+
+    /// Pass along a quick chat the game says someone sent. Teammates' chats
+    /// are turned into intents on the next tick.
+    pub fn hear_quick_chat(
+        &mut self,
+        player_index: i32,
+        selection: rlbot::flat::QuickChatSelection,
+    ) {
+        self.quick_chat.hear(player_index as usize, selection);
+    }
+
+    /// Returns the quick chat chosen during the last tick, if any.
+    pub fn take_quick_chat(&mut self) -> Option<OutgoingQuickChat> {
+        self.quick_chat.take_outgoing()
+    }
+
     pub fn tick(
         &mut self,
         field_info: rlbot::flat::FieldInfo<'_>,
         packet: &common::halfway_house::LiveDataPacket,
         eeg: &mut EEG,
+    ) -> common::halfway_house::PlayerInput {
+        self.tick_shared(field_info, packet, None, None, eeg)
+    }
+
+    /// Like `tick`, but with a ball prediction and team intents that were
+    /// computed once for the whole hivemind. Without shared intents, we go by
+    /// what our teammates have said in quick chat.
+    pub(crate) fn tick_shared(
+        &mut self,
+        field_info: rlbot::flat::FieldInfo<'_>,
+        packet: &common::halfway_house::LiveDataPacket,
+        ball_prediction: Option<&BallTrajectory>,
+        intents: Option<&TeamIntents>,
+        eeg: &mut EEG,
     ) -> common::halfway_house::PlayerInput {
         self.fps_counter.tick(packet.GameInfo.TimeSeconds);
 
+        if !self.seed_logged {
+            log::info!("rng seed {}", self.rng.seed());
+            eeg.log(
+                name_of_type!(Brain),
+                format!("rng seed {}", self.rng.seed()),
+            );
+            self.seed_logged = true;
+        }
+
         eeg.print_time("game_time", packet.GameInfo.TimeSeconds);
         eeg.print_value("fps", format_fps(self.fps_counter.fps()));
         eeg.print_value("ball loc", packet.GameBall.Physics.loc());
@@ -86,7 +139,7 @@ impl Brain {
         eeg.print_value("p1 vel", Point3::from(packet.GameCars[0].Physics.vel()));
         eeg.draw(Drawable::print("-----------------------", color::GREEN));
 
-        let mut result = self.determine_controls(field_info, packet, eeg);
+        let mut result = self.determine_controls(field_info, packet, ball_prediction, intents, eeg);
 
         result.Throttle = clamp(result.Throttle, -1.0, 1.0);
         result.Steer = clamp(result.Steer, -1.0, 1.0);
@@ -111,13 +164,43 @@ impl Brain {
         &mut self,
         field_info: rlbot::flat::FieldInfo<'_>,
         packet: &common::halfway_house::LiveDataPacket,
+        ball_prediction: Option<&BallTrajectory>,
+        intents: Option<&TeamIntents>,
         eeg: &mut EEG,
     ) -> common::halfway_house::PlayerInput {
         let start = Instant::now();
 
-        let game = Game::new(field_info, packet, self.player_index.unwrap() as usize);
-        let scenario = Scenario::new(&game, &*self.ball_predictor, packet);
-        let mut ctx = Context::new(&game, packet, &scenario, eeg, &mut self.last_quick_chat);
+        let shared;
+        let ball_predictor: &dyn BallPredictor = match ball_prediction {
+            Some(trajectory) => {
+                shared = SharedBallPrediction::new(trajectory);
+                &shared
+            }
+            None => &*self.ball_predictor,
+        };
+
+        let player_index = self.player_index.unwrap() as usize;
+        let game = Game::new(field_info, packet, player_index);
+
+        let heard = self
+            .quick_chat
+            .update(packet.GameInfo.TimeSeconds, player_index, |index| {
+                index != player_index
+                    && index < packet.NumCars as usize
+                    && Team::from_ffi(packet.GameCars[index].Team) == game.team
+            });
+        let intents = intents.unwrap_or(&heard);
+
+        let scenario = Scenario::new(&game, ball_predictor, packet);
+        let mut ctx = Context::new(
+            &game,
+            packet,
+            &scenario,
+            intents,
+            eeg,
+            &mut self.quick_chat,
+            &mut self.rng,
+        );
 
         ctx.eeg.print_time("possession", ctx.scenario.possession());
 
@@ -1,4 +1,5 @@
 use crate::eeg::{color, window::Window};
+use collect::{PlanCollector, RecordingPlan};
 use common::{prelude::*, rl, Angle, Distance, PrettyPrint, Time};
 use graphics::types::Color;
 use nalgebra::{Point2, Point3, Rotation3};
@@ -10,9 +11,7 @@ pub struct EEG {
     current_packet_time: f32,
     draw_list: DrawList,
     pub events: Option<HashSet<Event>>,
-    // I added quick-chat here only for convenience before a tournament, but it should really be
-    // somewhere else…
-    pub quick_chat: Option<rlbot::flat::QuickChatSelection>,
+    plan_collector: Option<PlanCollector>,
 }
 
 #[derive(Eq, PartialEq, Hash)]
@@ -42,7 +41,7 @@ impl EEG {
             current_packet_time: 0.0,
             draw_list: DrawList::new(),
             events: None,
-            quick_chat: None,
+            plan_collector: None,
         }
     }
 
@@ -57,6 +56,12 @@ impl EEG {
     pub fn track_events(&mut self) {
         self.events = Some(HashSet::new());
     }
+
+    /// Save every route plan, so a route that went wrong can be inspected
+    /// after the fact.
+    pub fn record_plans(&mut self, plan_collector: PlanCollector) {
+        self.plan_collector = Some(plan_collector);
+    }
 }
 
 impl EEG {
@@ -64,7 +69,6 @@ impl EEG {
     pub fn begin(&mut self, packet: &common::halfway_house::LiveDataPacket) {
         self.current_packet_time = packet.GameInfo.TimeSeconds;
         assert!(self.draw_list.drawables.is_empty());
-        self.quick_chat = None;
     }
 
     /// Call this at the end of each frame.
@@ -75,10 +79,6 @@ impl EEG {
         }
     }
 
-    pub fn quick_chat(&mut self, selection: rlbot::flat::QuickChatSelection) {
-        self.quick_chat = Some(selection);
-    }
-
     pub fn draw(&mut self, drawable: Drawable) {
         self.draw_list.draw(drawable);
     }
@@ -115,6 +115,17 @@ impl EEG {
         self.log(tag, format!("{} = {}", name, value.pretty()))
     }
 
+    /// Recording is only a debugging aid, so if a write fails, say so once and
+    /// stop recording instead of taking the bot down with it.
+    pub fn record_plan(&mut self, plan: &RecordingPlan) {
+        if let Some(ref mut plan_collector) = self.plan_collector {
+            if let Err(err) = plan_collector.write(plan) {
+                log::warn!("error recording plan, no longer recording: {}", err);
+                self.plan_collector = None;
+            }
+        }
+    }
+
     pub fn track(&mut self, event: Event) {
         if let Some(ref mut events) = self.events {
             events.insert(event);
@@ -7,6 +7,7 @@ use std::{iter::Cloned, slice::Iter};
 
 const PREDICT_DURATION: f32 = 7.0;
 
+#[derive(Clone)]
 pub struct BallTrajectory {
     frames: Vec<BallFrame>,
 }
@@ -172,6 +173,19 @@ impl BallPredictor for FrameworkBallPrediction {
     }
 }
 
+/// Hands out a prediction that was already computed elsewhere, so several
+/// cars can share one prediction per tick.
+#[derive(new)]
+pub struct SharedBallPrediction<'a> {
+    trajectory: &'a BallTrajectory,
+}
+
+impl<'a> BallPredictor for SharedBallPrediction<'a> {
+    fn predict(&self, _packet: &common::halfway_house::LiveDataPacket) -> BallTrajectory {
+        self.trajectory.clone()
+    }
+}
+
 fn point3(v: &rlbot::flat::Vector3) -> Point3<f32> {
     Point3::new(v.x(), v.y(), v.z())
 }
@@ -1,34 +1,28 @@
 use crate::{helpers::ball::BallFrame, routing::models::CarState};
 use common::prelude::*;
-use nalgebra::{Point3, UnitQuaternion, Vector3};
+use nalgebra::{Point3, Vector3};
 use simulate::Car1D;
 use std::borrow::Borrow;
 
-pub fn naive_ground_intercept<'a>(
-    ball: impl Iterator<Item = &'a BallFrame>,
-    start_loc: Point3<f32>,
-    start_vel: Vector3<f32>,
-    start_boost: f32,
-    predicate: impl Fn(&BallFrame) -> bool,
-) -> Option<NaiveIntercept> {
-    let start = CarState {
-        loc: start_loc,
-        rot: UnitQuaternion::identity(), // This isn't used.
-        vel: start_vel,
-        boost: start_boost,
-    };
-    naive_ground_intercept_2(
-        &start,
-        ball,
-        |ball| if predicate(ball) { Some(()) } else { None },
-    )
-}
-
 pub fn naive_ground_intercept_2<BF, IID>(
     start: &CarState,
     ball: impl IntoIterator<Item = BF>,
     predicate: impl Fn(&BallFrame) -> IID,
 ) -> Option<NaiveIntercept<IID::Data>>
+where
+    BF: Borrow<BallFrame>,
+    IID: IntoInterceptData,
+{
+    reachable_ground_intercepts(start, ball, predicate).next()
+}
+
+/// Every frame of `ball` that we could reach by blitzing in a straight line,
+/// and that passes `predicate`, in order.
+pub fn reachable_ground_intercepts<BF, IID>(
+    start: &CarState,
+    ball: impl IntoIterator<Item = BF>,
+    predicate: impl Fn(&BallFrame) -> IID,
+) -> impl Iterator<Item = NaiveIntercept<IID::Data>>
 where
     BF: Borrow<BallFrame>,
     IID: IntoInterceptData,
@@ -37,35 +31,31 @@ where
     // we want their meshes to barely be touching.
     const RADII: f32 = 240.0;
 
+    let start_loc = start.loc;
     let mut sim_car = Car1D::new()
         .with_speed(start.vel.norm())
         .with_boost(start.boost);
 
-    let (sim_ball, data) = ball.into_iter().find_map(|ball| {
+    ball.into_iter().filter_map(move |ball| {
         let ball = ball.borrow();
 
         sim_car.advance(ball.dt(), 1.0, true);
 
-        let target_dist = (ball.loc - start.loc).to_2d().norm() - RADII;
-        if sim_car.distance() >= target_dist {
-            if let Some(data) = predicate(&ball).into_intercept_data() {
-                return Some((ball.clone(), data));
-            }
+        let target_dist = (ball.loc - start_loc).to_2d().norm() - RADII;
+        if sim_car.distance() < target_dist {
+            return None;
         }
-
-        None
-    })?;
-
-    let intercept_loc = sim_ball.loc - (sim_ball.loc - start.loc).normalize() * RADII;
-    let intercept = NaiveIntercept {
-        time: sim_ball.t,
-        ball_loc: sim_ball.loc,
-        ball_vel: sim_ball.vel,
-        car_loc: intercept_loc,
-        car_speed: sim_car.speed(),
-        data,
-    };
-    Some(intercept)
+        let data = predicate(ball).into_intercept_data()?;
+
+        Some(NaiveIntercept {
+            time: ball.t,
+            ball_loc: ball.loc,
+            ball_vel: ball.vel,
+            car_loc: ball.loc - (ball.loc - start_loc).normalize() * RADII,
+            car_speed: sim_car.speed(),
+            data,
+        })
+    })
 }
 
 pub struct NaiveIntercept<D = ()> {
@@ -102,7 +92,7 @@ impl<T> IntoInterceptData for Option<T> {
     }
 }
 
-/// Very rough estimate of a penalty to `naive_ground_intercept` that accounts
+/// Very rough estimate of a penalty to `naive_ground_intercept_2` that accounts
 /// for turning. That function does not account for turning, and this tries to
 /// patch it up after the fact.
 pub fn naive_intercept_penalty(car: &CarState, ball: &BallFrame) -> f32 {
@@ -0,0 +1,300 @@
+use crate::{
+    eeg::{color, Drawable, EEG},
+    helpers::{
+        ball::BallFrame,
+        intercept::{reachable_ground_intercepts, IntoInterceptData, NaiveIntercept},
+    },
+    routing::models::CarState,
+    strategy::Game,
+};
+use common::{prelude::*, rl};
+use nalgebra::Vector2;
+use ordered_float::NotNan;
+use simulate::Car1D;
+use std::{borrow::Borrow, cmp::Reverse};
+
+/// How much each term counts toward an intercept's total score. Each term is
+/// scaled so that a weight of 1 makes a full swing of that term worth about a
+/// second of waiting.
+#[derive(Copy, Clone)]
+pub struct InterceptWeights {
+    pub time: f32,
+    pub shot: f32,
+    pub own_goal_risk: f32,
+    pub race: f32,
+    pub boost: f32,
+}
+
+impl Default for InterceptWeights {
+    fn default() -> Self {
+        Self {
+            time: 1.0,
+            shot: 1.0,
+            own_goal_risk: 2.0,
+            race: 1.0,
+            boost: 0.5,
+        }
+    }
+}
+
+/// The raw terms that go into an intercept's score, before weighting.
+#[derive(Copy, Clone)]
+pub struct InterceptScore {
+    /// Seconds until we reach the ball.
+    pub time: f32,
+    /// From -1 to 1, how well driving straight through the ball sends it
+    /// toward the enemy goal.
+    pub shot: f32,
+    /// From 0 to 1, how much driving straight through the ball sends it
+    /// toward our own goal, scaled up the closer it is to that goal.
+    pub own_goal_risk: f32,
+    /// Seconds we reach the ball before the fastest enemy does. Negative if
+    /// they beat us there.
+    pub race_margin: f32,
+    /// From 0 to 1, the fraction of a full tank spent getting there.
+    pub boost_cost: f32,
+}
+
+impl InterceptScore {
+    /// A lead of more than this doesn't make an intercept any safer.
+    const MAX_RACE_MARGIN: f32 = 1.0;
+
+    /// Higher is better.
+    pub fn total(&self, weights: &InterceptWeights) -> f32 {
+        let race_margin = self
+            .race_margin
+            .max(-Self::MAX_RACE_MARGIN)
+            .min(Self::MAX_RACE_MARGIN);
+        -self.time * weights.time + self.shot * weights.shot
+            - self.own_goal_risk * weights.own_goal_risk
+            + race_margin * weights.race
+            - self.boost_cost * weights.boost
+    }
+}
+
+pub struct RankedIntercept<D = ()> {
+    pub intercept: NaiveIntercept<D>,
+    pub score: InterceptScore,
+    pub total: f32,
+}
+
+impl<D> RankedIntercept<D> {
+    /// The same intercept, without the caller's data.
+    pub fn without_data(&self) -> RankedIntercept {
+        let i = &self.intercept;
+        RankedIntercept {
+            intercept: NaiveIntercept {
+                time: i.time,
+                ball_loc: i.ball_loc,
+                ball_vel: i.ball_vel,
+                car_loc: i.car_loc,
+                car_speed: i.car_speed,
+                data: (),
+            },
+            score: self.score,
+            total: self.total,
+        }
+    }
+}
+
+/// Score every frame of `ball` that we can reach and that passes `predicate`,
+/// and return them best first.
+///
+/// `enemy_time` is when the fastest enemy reaches the ball, if anyone can.
+pub fn rank_ground_intercepts<BF, IID>(
+    game: &Game<'_>,
+    start: &CarState,
+    enemy_time: Option<f32>,
+    weights: &InterceptWeights,
+    ball: impl IntoIterator<Item = BF>,
+    predicate: impl Fn(&BallFrame) -> IID,
+) -> Vec<RankedIntercept<IID::Data>>
+where
+    BF: Borrow<BallFrame>,
+    IID: IntoInterceptData,
+{
+    let mut ranked: Vec<_> = reachable_ground_intercepts(start, ball, predicate)
+        .map(|intercept| {
+            let score = score(game, start, enemy_time, &intercept);
+            let total = score.total(weights);
+            RankedIntercept {
+                intercept,
+                score,
+                total,
+            }
+        })
+        .collect();
+    ranked.sort_by_key(|r| Reverse(NotNan::new(r.total).unwrap()));
+    ranked
+}
+
+fn score<D>(
+    game: &Game<'_>,
+    start: &CarState,
+    enemy_time: Option<f32>,
+    intercept: &NaiveIntercept<D>,
+) -> InterceptScore {
+    let ball_loc = intercept.ball_loc.to_2d();
+    let hit_dir = ball_loc - start.loc_2d();
+    let own_goal = game.own_goal().center_2d;
+
+    let shot = cos_between(hit_dir, game.enemy_goal().center_2d - ball_loc);
+    let own_goal_closeness = 1.0 - (own_goal - ball_loc).norm() / (rl::FIELD_MAX_Y * 2.0);
+    let own_goal_risk =
+        cos_between(hit_dir, own_goal - ball_loc).max(0.0) * own_goal_closeness.max(0.0);
+
+    InterceptScore {
+        time: intercept.time,
+        shot,
+        own_goal_risk,
+        race_margin: enemy_time.map_or(InterceptScore::MAX_RACE_MARGIN, |t| t - intercept.time),
+        boost_cost: boost_to_arrive(start, intercept) / 100.0,
+    }
+}
+
+/// The boost it takes to reach the intercept in time. The intercept search
+/// boosts the whole way, but for a later intercept we only need to boost
+/// until the earliest time we could get there, or not at all if throttle
+/// alone makes it.
+fn boost_to_arrive<D>(start: &CarState, intercept: &NaiveIntercept<D>) -> f32 {
+    let distance = (intercept.car_loc - start.loc).to_2d().norm();
+    if distance < 1.0 {
+        return 0.0;
+    }
+
+    let mut throttle = Car1D::new()
+        .with_speed(start.vel.norm())
+        .with_boost(start.boost);
+    throttle.advance(intercept.time, 1.0, false);
+    if throttle.distance() >= distance {
+        return 0.0;
+    }
+
+    let mut blitz = Car1D::new()
+        .with_speed(start.vel.norm())
+        .with_boost(start.boost);
+    blitz.advance_by_distance(distance, 1.0, true);
+    (start.boost - blitz.boost()).max(0.0)
+}
+
+/// The cosine of the angle between `a` and `b`, or 0 if either has no
+/// direction to speak of.
+fn cos_between(a: Vector2<f32>, b: Vector2<f32>) -> f32 {
+    if a.norm() < 1.0 || b.norm() < 1.0 {
+        return 0.0;
+    }
+    a.to_axis().dot(&b.to_axis())
+}
+
+/// Print the top few intercepts with their score breakdown, and mark the best
+/// one on the field.
+pub fn draw_ranking<D>(eeg: &mut EEG, ranked: &[RankedIntercept<D>]) {
+    const SHOWN: usize = 3;
+
+    if let Some(best) = ranked.first() {
+        eeg.draw(Drawable::GhostBall(best.intercept.ball_loc, color::GREEN));
+    }
+    for (i, r) in ranked.iter().take(SHOWN).enumerate() {
+        let s = &r.score;
+        eeg.draw(Drawable::print(
+            format!(
+                "#{} {:.2}s shot {:.2} risk {:.2} race {:+.2} boost {:.2} = {:.2}",
+                i + 1,
+                s.time,
+                s.shot,
+                s.own_goal_risk,
+                s.race_margin,
+                s.boost_cost,
+                r.total,
+            ),
+            if i == 0 { color::GREEN } else { color::WHITE },
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        helpers::intercept_optimizer::{rank_ground_intercepts, InterceptWeights},
+        routing::{
+            models::CarState,
+            test::{car_on_floor, with_offline_planning_context},
+        },
+    };
+    use common::rl;
+    use nalgebra::{Point2, Point3, Vector3};
+    use std::f32::consts::PI;
+
+    #[test]
+    fn ranked_best_first() {
+        let start = car_on_floor(Point2::new(0.0, -2000.0), PI / 2.0, 1000.0, 50.0);
+        let ball_loc = Point3::new(1000.0, 0.0, rl::BALL_RADIUS);
+        let ball_vel = Vector3::new(-500.0, 300.0, 0.0);
+        with_offline_planning_context(&start, ball_loc, ball_vel, |ctx| {
+            let ranked = rank_ground_intercepts(
+                ctx.game,
+                &ctx.start,
+                None,
+                &InterceptWeights::default(),
+                ctx.ball_prediction.iter(),
+                |_| true,
+            );
+            assert!(!ranked.is_empty());
+            for pair in ranked.windows(2) {
+                assert!(pair[0].total >= pair[1].total);
+            }
+        });
+    }
+
+    #[test]
+    fn scores_shot_and_own_goal_risk() {
+        let ball_loc = Point3::new(0.0, 0.0, rl::BALL_RADIUS);
+        let score_from = |start: CarState| {
+            with_offline_planning_context(&start, ball_loc, Vector3::zeros(), |ctx| {
+                let ranked = rank_ground_intercepts(
+                    ctx.game,
+                    &ctx.start,
+                    None,
+                    &InterceptWeights::default(),
+                    ctx.ball_prediction.iter(),
+                    |_| true,
+                );
+                ranked[0].score
+            })
+        };
+
+        let own_goal_y = -rl::FIELD_MAX_Y;
+        let own_side = Point2::new(0.0, own_goal_y / 2.0);
+        let enemy_side = Point2::new(0.0, -own_goal_y / 2.0);
+        let from_own_side = score_from(car_on_floor(own_side, PI / 2.0, 1000.0, 50.0));
+        let from_enemy_side = score_from(car_on_floor(enemy_side, -PI / 2.0, 1000.0, 50.0));
+
+        assert!(from_own_side.shot > 0.9);
+        assert!(from_own_side.own_goal_risk < 1e-3);
+        assert!(from_enemy_side.shot < -0.9);
+        assert!(from_enemy_side.own_goal_risk > 0.0);
+    }
+
+    #[test]
+    fn boost_cost_only_until_earliest_arrival() {
+        let start = car_on_floor(Point2::new(0.0, -2000.0), PI / 2.0, 1000.0, 50.0);
+        let ball_loc = Point3::new(0.0, 0.0, rl::BALL_RADIUS);
+        with_offline_planning_context(&start, ball_loc, Vector3::zeros(), |ctx| {
+            let mut ranked = rank_ground_intercepts(
+                ctx.game,
+                &ctx.start,
+                None,
+                &InterceptWeights::default(),
+                ctx.ball_prediction.iter(),
+                |_| true,
+            );
+            ranked.sort_by(|a, b| a.intercept.time.partial_cmp(&b.intercept.time).unwrap());
+            let earliest = &ranked.first().unwrap().score;
+            let latest = &ranked.last().unwrap().score;
+            assert!(earliest.boost_cost > 0.0);
+            // The ball sits still, so by the end there's time to drive there
+            // without boosting at all.
+            assert!(latest.boost_cost < 1e-3);
+        });
+    }
+}
@@ -2,4 +2,5 @@ pub mod ball;
 pub mod drive;
 pub mod hit_angle;
 pub mod intercept;
+pub mod intercept_optimizer;
 pub mod telepathy;
@@ -0,0 +1,222 @@
+use crate::{
+    brain::Brain,
+    eeg::{color, Drawable, EEG},
+    helpers::ball::{
+        BallPredictor, BallTrajectory, ChipBallPrediction, FrameworkBallPrediction,
+        SharedBallPrediction,
+    },
+    strategy::{Game, Intent, OutgoingQuickChat, Scenario, TeamIntents},
+};
+use nameof::name_of_type;
+use ordered_float::NotNan;
+
+/// Drives every car on our team from a single process.
+///
+/// The ball is predicted once per tick and shared by all the drones, and the
+/// decision of who goes for the ball is made here instead of over quick chat.
+/// Each drone still keeps its own `Brain`, so behavior state is per-car.
+pub struct Hivemind {
+    drones: Vec<Drone>,
+    ball_predictor: Box<dyn BallPredictor>,
+    ball_chaser: Option<i32>,
+}
+
+struct Drone {
+    player_index: i32,
+    brain: Brain,
+}
+
+pub struct DroneOutput {
+    pub player_index: i32,
+    pub input: common::halfway_house::PlayerInput,
+    pub quick_chat: Option<OutgoingQuickChat>,
+}
+
+impl Hivemind {
+    /// How much sooner (in seconds) another drone must be able to reach the
+    /// ball before it takes over from the current chaser. This keeps the cars
+    /// from trading the ball back and forth on every tick.
+    const CHASER_HYSTERESIS: f32 = 0.5;
+
+    fn new(
+        player_indices: &[i32],
+        brain: impl Fn() -> Brain,
+        ball_predictor: impl BallPredictor + 'static,
+    ) -> Self {
+        let drones = player_indices
+            .iter()
+            .map(|&player_index| {
+                let mut brain = brain();
+                brain.set_player_index(player_index);
+                Drone {
+                    player_index,
+                    brain,
+                }
+            })
+            .collect();
+        Self {
+            drones,
+            ball_predictor: Box::new(ball_predictor),
+            ball_chaser: None,
+        }
+    }
+
+    pub fn soccar(player_indices: &[i32]) -> Self {
+        Self::new(player_indices, Brain::soccar, ChipBallPrediction::new())
+    }
+
+    pub fn dropshot(rlbot: &'static rlbot::RLBot, player_indices: &[i32]) -> Self {
+        Self::new(
+            player_indices,
+            || Brain::dropshot(rlbot),
+            FrameworkBallPrediction::new(rlbot),
+        )
+    }
+
+    pub fn hoops(rlbot: &'static rlbot::RLBot, player_indices: &[i32]) -> Self {
+        Self::new(
+            player_indices,
+            || Brain::hoops(rlbot),
+            FrameworkBallPrediction::new(rlbot),
+        )
+    }
+
+    /// Seed every drone's random number stream. Each drone gets its own seed
+    /// derived from this one, so they don't all make the same choices.
+    pub fn set_seed(&mut self, seed: u64) {
+        for (i, drone) in self.drones.iter_mut().enumerate() {
+            drone.brain.set_seed(seed.wrapping_add(i as u64));
+        }
+    }
+
+    pub fn tick(
+        &mut self,
+        field_info: rlbot::flat::FieldInfo<'_>,
+        packet: &common::halfway_house::LiveDataPacket,
+        eeg: &mut EEG,
+    ) -> Vec<DroneOutput> {
+        let ball_prediction = self.ball_predictor.predict(packet);
+        let intents = self.coordinate(field_info, packet, &ball_prediction, eeg);
+
+        let mut outputs = Vec::with_capacity(self.drones.len());
+        for drone in &mut self.drones {
+            eeg.draw(Drawable::print(
+                format!("drone {}", drone.player_index),
+                color::WHITE,
+            ));
+            let input = drone.brain.tick_shared(
+                field_info,
+                packet,
+                Some(&ball_prediction),
+                Some(&intents),
+                eeg,
+            );
+            outputs.push(DroneOutput {
+                player_index: drone.player_index,
+                input,
+                quick_chat: drone.brain.take_quick_chat(),
+            });
+        }
+        outputs
+    }
+
+    /// Decide which drone goes for the ball, and tell everyone else to support.
+    fn coordinate(
+        &mut self,
+        field_info: rlbot::flat::FieldInfo<'_>,
+        packet: &common::halfway_house::LiveDataPacket,
+        ball_prediction: &BallTrajectory,
+        eeg: &mut EEG,
+    ) -> TeamIntents {
+        let ball_predictor = SharedBallPrediction::new(ball_prediction);
+        let intercept_times: Vec<(i32, Option<f32>)> = self
+            .drones
+            .iter()
+            .map(|drone| {
+                let player_index = drone.player_index;
+                if packet.GameCars[player_index as usize].Demolished {
+                    return (player_index, None);
+                }
+                let game = Game::new(field_info, packet, player_index as usize);
+                let scenario = Scenario::new(&game, &ball_predictor, packet);
+                (player_index, scenario.me_intercept().map(|i| i.time))
+            })
+            .collect();
+
+        let chaser = choose_ball_chaser(&intercept_times, self.ball_chaser);
+        if chaser != self.ball_chaser {
+            eeg.log(
+                name_of_type!(Hivemind),
+                format!("ball chaser {:?} -> {:?}", self.ball_chaser, chaser),
+            );
+            self.ball_chaser = chaser;
+        }
+
+        let mut intents = TeamIntents::new();
+        for drone in &self.drones {
+            let intent = if Some(drone.player_index) == chaser {
+                Intent::GoingForBall
+            } else {
+                Intent::Supporting
+            };
+            intents.set(drone.player_index as usize, intent);
+        }
+        intents
+    }
+}
+
+/// Pick the drone that can reach the ball soonest, but stick with the previous
+/// chaser unless someone else is clearly faster.
+fn choose_ball_chaser(
+    intercept_times: &[(i32, Option<f32>)],
+    previous: Option<i32>,
+) -> Option<i32> {
+    let (best_index, best_time) = intercept_times
+        .iter()
+        .filter_map(|&(index, time)| Some((index, time?)))
+        .min_by_key(|&(_, time)| NotNan::new(time).unwrap())?;
+
+    let previous_time = intercept_times
+        .iter()
+        .find(|&&(index, _)| Some(index) == previous)
+        .and_then(|&(_, time)| time);
+    match previous_time {
+        Some(previous_time) if previous_time < best_time + Hivemind::CHASER_HYSTERESIS => previous,
+        _ => Some(best_index),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hivemind::choose_ball_chaser;
+
+    #[test]
+    fn fastest_goes() {
+        let times = [(0, Some(2.0)), (1, Some(1.0)), (2, Some(3.0))];
+        assert_eq!(choose_ball_chaser(&times, None), Some(1));
+    }
+
+    #[test]
+    fn nobody_can_reach() {
+        let times = [(0, None), (1, None)];
+        assert_eq!(choose_ball_chaser(&times, Some(0)), None);
+    }
+
+    #[test]
+    fn chaser_is_sticky() {
+        let times = [(0, Some(1.2)), (1, Some(1.0))];
+        assert_eq!(choose_ball_chaser(&times, Some(0)), Some(0));
+    }
+
+    #[test]
+    fn chaser_yields_when_clearly_slower() {
+        let times = [(0, Some(2.0)), (1, Some(1.0))];
+        assert_eq!(choose_ball_chaser(&times, Some(0)), Some(1));
+    }
+
+    #[test]
+    fn demolished_chaser_is_replaced() {
+        let times = [(0, None), (1, Some(1.0))];
+        assert_eq!(choose_ball_chaser(&times, Some(0)), Some(1));
+    }
+}
@@ -23,6 +23,7 @@ pub struct TestRunner {
     car_inital_state: Option<(RecordingRigidBodyState, f32)>,
     enemy_recording: Option<(Vec<f32>, Vec<RecordingPlayerTick>)>,
     enemy_initial_boost: f32,
+    seed: u64,
 }
 
 impl TestRunner {
@@ -35,6 +36,7 @@ impl TestRunner {
             car_inital_state: None,
             enemy_recording: None,
             enemy_initial_boost: Self::DEFAULT_STARTING_BOOST,
+            seed: 0,
         }
     }
 
@@ -71,6 +73,14 @@ impl TestRunner {
         self
     }
 
+    /// Seed the bot's random number stream. Tests use a fixed seed by default so
+    /// they behave the same from run to run.
+    #[allow(dead_code)]
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
     fn enemy(
         mut self,
         times: impl Into<Vec<f32>>,
@@ -185,6 +195,7 @@ impl TestRunner {
             car,
             enemy,
             move |p| behavior(p),
+            self.seed,
             ready_wait_send,
             messages_rx,
         );
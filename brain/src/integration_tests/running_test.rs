@@ -89,6 +89,7 @@ impl RunningTest {
         behavior: impl FnOnce(&common::halfway_house::LiveDataPacket) -> Box<dyn Behavior>
             + Send
             + 'static,
+        seed: u64,
         ready_wait: Arc<Barrier>,
         messages: crossbeam_channel::Receiver<Message>,
     ) -> thread::JoinHandle<()> {
@@ -98,6 +99,7 @@ impl RunningTest {
                 car_scenario,
                 enemy_scenario,
                 behavior,
+                seed,
                 ready_wait,
                 messages,
             )
@@ -133,6 +135,7 @@ fn test_thread(
     car_scenario: CarRecording,
     enemy_scenario: CarRecording,
     behavior: impl FnOnce(&common::halfway_house::LiveDataPacket) -> Box<dyn Behavior>,
+    seed: u64,
     ready_wait: Arc<Barrier>,
     messages: crossbeam_channel::Receiver<Message>,
 ) {
@@ -155,6 +158,7 @@ fn test_thread(
 
     let mut brain = Brain::with_behavior(NullBehavior::new());
     brain.set_player_index(0);
+    brain.set_seed(seed);
 
     let mut packets = rlbot.packeteer();
     let mut physicist = rlbot.physicist();
@@ -229,9 +233,9 @@ fn test_thread(
             .update_player_input(0, &translate_player_input(&input))
             .unwrap();
         eeg.show(&packet);
-        if let Some(chat) = eeg.quick_chat {
-            if let Err(_) = rlbot.quick_chat(chat, 0) {
-                log::warn!("could not quick chat {:?}", chat);
+        if let Some(chat) = brain.take_quick_chat() {
+            if let Err(_) = rlbot.quick_chat(chat.selection, 0, chat.team_only) {
+                log::warn!("could not quick chat {:?}", chat.selection);
             }
         }
     }
@@ -3,7 +3,12 @@
 #![warn(clippy::all)]
 #![allow(clippy::unreadable_literal)]
 
-pub use crate::{brain::Brain, eeg::EEG};
+pub use crate::{
+    brain::Brain,
+    eeg::{color, Drawable, EEG},
+    hivemind::{DroneOutput, Hivemind},
+    strategy::OutgoingQuickChat,
+};
 
 macro_rules! return_some {
     ($rule:expr) => {
@@ -26,6 +31,7 @@ mod behavior;
 mod brain;
 mod eeg;
 mod helpers;
+mod hivemind;
 #[cfg(test)]
 mod integration_tests;
 mod routing;
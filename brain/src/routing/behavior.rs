@@ -1,39 +1,66 @@
 use crate::{
     eeg::{color, Drawable},
-    routing::models::{
-        PlanningContext, ProvisionalPlanExpansion, ProvisionalPlanExpansionTail, RoutePlan,
-        RoutePlanError, RoutePlanner, SegmentRunAction, SegmentRunner,
+    routing::{
+        models::{
+            PlanningContext, ProvisionalPlanExpansion, ProvisionalPlanExpansionTail, RoutePlan,
+            RoutePlanError, RoutePlanner, SegmentRunAction, SegmentRunner,
+        },
+        record::{record_error, record_expansion, record_segment},
     },
     rules::SameBallTrajectory,
     strategy::{Action, Behavior, Context},
 };
+use collect::{RecordingPlan, RecordingPlanError, RecordingPlanSegment};
+use common::prelude::*;
+use nalgebra::Point2;
 use nameof::name_of_type;
+use std::f32::consts::PI;
 
 pub struct FollowRoute {
-    /// Option dance: This only holds a planner before the first tick.
-    planner: Option<Box<dyn RoutePlanner>>,
+    planner: Box<dyn RoutePlanner>,
     current: Option<Current>,
     never_recover: bool,
     same_ball_trajectory: Option<SameBallTrajectory>,
+    replan_distance: Option<f32>,
+    replan_angle: Option<f32>,
 }
 
 struct Current {
+    /// The planner that produced this segment, so if we drift too far from
+    /// the plan we can start over from this stage of the route.
+    planner: Box<dyn RoutePlanner>,
     plan: RoutePlan,
     runner: Box<dyn SegmentRunner>,
     provisional_expansion_tail: ProvisionalPlanExpansionTail,
+    /// The game time when the segment started.
+    start_time: f32,
+    /// Where the car has actually been during the segment, for comparing
+    /// against the plan.
+    trace: Vec<Point2<f32>>,
 }
 
 impl FollowRoute {
+    /// Only record a new point in the actual trace once we've moved this far.
+    const TRACE_SPACING: f32 = 50.0;
+    /// The time between points when drawing the planned trace.
+    const TRACE_DT: f32 = 0.05;
+    /// Segments are run closed-loop and absorb small errors on their own.
+    /// Past these, the rest of the plan is built on a state we're not in.
+    const DEFAULT_REPLAN_DISTANCE: f32 = 500.0;
+    const DEFAULT_REPLAN_ANGLE: f32 = PI / 3.0;
+
     pub fn new(planner: impl RoutePlanner + 'static) -> Self {
         Self::new_boxed(Box::new(planner))
     }
 
     pub fn new_boxed(planner: Box<dyn RoutePlanner>) -> Self {
         Self {
-            planner: Some(planner),
+            planner,
             current: None,
             never_recover: false,
             same_ball_trajectory: None,
+            replan_distance: Some(Self::DEFAULT_REPLAN_DISTANCE),
+            replan_angle: Some(Self::DEFAULT_REPLAN_ANGLE),
         }
     }
 
@@ -52,6 +79,36 @@ impl FollowRoute {
         };
         self
     }
+
+    /// Replan the current stage if the car drifts too far from where the
+    /// current segment expects it to be. On by default; routes that expect
+    /// to be pushed around (e.g. a 50/50) can turn it off.
+    pub fn replan_on_drift(self, replan_on_drift: bool) -> Self {
+        if replan_on_drift {
+            self.replan_distance(Self::DEFAULT_REPLAN_DISTANCE)
+                .replan_angle(Self::DEFAULT_REPLAN_ANGLE)
+        } else {
+            Self {
+                replan_distance: None,
+                replan_angle: None,
+                ..self
+            }
+        }
+    }
+
+    /// Replan the current stage if the car ends up this far from where the
+    /// current segment expects it to be.
+    pub fn replan_distance(mut self, replan_distance: f32) -> Self {
+        self.replan_distance = Some(replan_distance);
+        self
+    }
+
+    /// Replan the current stage if the car's heading is off from what the
+    /// current segment expects by this many radians.
+    pub fn replan_angle(mut self, replan_angle: f32) -> Self {
+        self.replan_angle = Some(replan_angle);
+        self
+    }
 }
 
 impl Behavior for FollowRoute {
@@ -65,8 +122,8 @@ impl Behavior for FollowRoute {
         }
 
         if self.current.is_none() {
-            let planner = &*self.planner.take().unwrap();
-            if let Err(action) = self.advance(planner, ctx) {
+            let planner = self.planner.clone();
+            if let Err(action) = self.advance(&*planner, ctx) {
                 return action;
             }
         }
@@ -87,6 +144,18 @@ impl FollowRoute {
         for segment in provisional_expansion.iter() {
             segment.draw(ctx);
         }
+
+        // Draw where the current segment expected us to go next to where we
+        // actually went.
+        let segment = &current.plan.segment;
+        let steps = (segment.duration() / Self::TRACE_DT).ceil() as usize;
+        let planned = (0..=steps).map(|i| segment.sample(i as f32 * Self::TRACE_DT).loc_2d());
+        for (a, b) in planned.clone().zip(planned.skip(1)) {
+            ctx.eeg.draw(Drawable::Line(a, b, color::BLUE));
+        }
+        for (&a, &b) in current.trace.iter().zip(current.trace.iter().skip(1)) {
+            ctx.eeg.draw(Drawable::Line(a, b, color::ORANGE));
+        }
     }
 
     fn advance(&mut self, planner: &dyn RoutePlanner, ctx: &mut Context<'_>) -> Result<(), Action> {
@@ -96,30 +165,117 @@ impl FollowRoute {
             .log(self.name(), format!("planning with {}", planner.name()));
         let (plan, log) = match PlanningContext::plan(planner, ctx) {
             Ok((plan, log)) => (plan, log),
-            Err(err) => return Err(self.handle_error(ctx, planner.name(), err.error, err.log)),
+            Err(err) => {
+                Self::record(
+                    ctx,
+                    Vec::new(),
+                    Some(record_error(planner.name(), &err.error)),
+                );
+                return Err(self.handle_error(ctx, planner.name(), err.error, err.log));
+            }
         };
         ctx.eeg.log(
             self.name(),
             format!("next segment is {}", plan.segment.name()),
         );
-        let tail = plan.provisional_expand(&ctx.scenario).map_err(|error| {
-            self.handle_error(
-                ctx,
-                error.planner_name,
-                error.error,
-                log.into_iter().chain(error.log),
-            )
-        })?;
+        let tail = match plan.provisional_expand(&ctx.scenario) {
+            Ok(tail) => tail,
+            Err(error) => {
+                Self::record(
+                    ctx,
+                    vec![record_segment(planner.name(), &*plan.segment)],
+                    Some(record_error(error.planner_name, &error.error)),
+                );
+                return Err(self.handle_error(
+                    ctx,
+                    error.planner_name,
+                    error.error,
+                    log.into_iter().chain(error.log),
+                ));
+            }
+        };
+        Self::record(
+            ctx,
+            record_expansion(planner.name(), &*plan.segment, &tail),
+            None,
+        );
 
         let runner = plan.segment.run();
         self.current = Some(Current {
+            planner: planner.clone_box(),
             plan,
             runner,
             provisional_expansion_tail: tail,
+            start_time: ctx.packet.GameInfo.TimeSeconds,
+            trace: Vec::new(),
         });
         Ok(())
     }
 
+    /// Save the plan (or as much of it as we got) for looking at offline.
+    fn record(
+        ctx: &mut Context<'_>,
+        segments: Vec<RecordingPlanSegment>,
+        error: Option<RecordingPlanError>,
+    ) {
+        ctx.eeg.record_plan(&RecordingPlan {
+            time: ctx.packet.GameInfo.TimeSeconds,
+            player_index: ctx.game.player_index(),
+            segments,
+            error,
+        });
+    }
+
+    /// Compare where we are with where the segment expects us to be, and replan
+    /// the current stage if they've drifted too far apart.
+    fn track(&mut self, ctx: &mut Context<'_>) -> Result<(), Action> {
+        let current = self.current.as_mut().unwrap();
+        let me = ctx.me();
+        let elapsed = ctx.packet.GameInfo.TimeSeconds - current.start_time;
+        let expected = current.plan.segment.sample(elapsed);
+
+        let me_loc = me.Physics.loc_2d();
+        match current.trace.last() {
+            Some(&last) if (me_loc - last).norm() < Self::TRACE_SPACING => {}
+            _ => current.trace.push(me_loc),
+        }
+
+        // Samples don't model the car tumbling through a dodge or a flip, and
+        // there's no steering back onto the plan mid-air anyway.
+        if !me.OnGround {
+            return Ok(());
+        }
+
+        let distance_error = (me.Physics.loc() - expected.loc).norm();
+        let angle_error = me.Physics.forward_axis().angle(&expected.forward_axis());
+        ctx.eeg.print_distance("tracking distance", distance_error);
+        ctx.eeg.print_angle("tracking angle", angle_error);
+
+        let off_course = self
+            .replan_distance
+            .map(|max| distance_error >= max)
+            .unwrap_or_default()
+            || self
+                .replan_angle
+                .map(|max| angle_error >= max)
+                .unwrap_or_default();
+        if !off_course {
+            return Ok(());
+        }
+
+        ctx.eeg.log(
+            name_of_type!(FollowRoute),
+            format!(
+                "drifted off {} by {:.0} uu, {:.0}°; replanning",
+                current.plan.segment.name(),
+                distance_error,
+                angle_error.to_degrees(),
+            ),
+        );
+        let planner = self.current.take().unwrap().planner;
+        self.advance(&*planner, ctx)
+    }
+
     fn handle_error(
         &mut self,
         ctx: &mut Context<'_>,
@@ -137,6 +293,11 @@ impl FollowRoute {
             format!("error {:?} from planner {}", error, planner_name),
         );
 
+        if let RoutePlanError::BlockedByCar { car_loc } = error {
+            ctx.eeg.log_pretty(self.name(), "blocking car", car_loc);
+            ctx.eeg.draw(Drawable::Crosshair(car_loc));
+        }
+
         match error.recover(ctx) {
             Some(b) => {
                 if self.never_recover {
@@ -156,6 +317,10 @@ impl FollowRoute {
     }
 
     fn go(&mut self, ctx: &mut Context<'_>) -> Action {
+        if let Err(action) = self.track(ctx) {
+            return action;
+        }
+
         let current = self.current.as_mut().unwrap();
         ctx.eeg
             .draw(Drawable::print(current.plan.segment.name(), color::YELLOW));
@@ -181,3 +346,92 @@ impl FollowRoute {
         self.go(ctx)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        routing::{
+            behavior::FollowRoute,
+            models::{PlanningContext, PlanningDump, RoutePlan, RoutePlanError, RoutePlanner},
+            plan::GroundStraightPlanner,
+            test::{car_on_floor, with_offline_context},
+            StraightMode,
+        },
+        strategy::{Action, Behavior},
+    };
+    use common::rl;
+    use nalgebra::{Point2, Point3, Vector3};
+    use std::{
+        f32::consts::PI,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+
+    /// Wraps a planner and counts how many times it's asked for a plan.
+    #[derive(Clone)]
+    struct CountingPlanner<P> {
+        inner: P,
+        count: Arc<AtomicUsize>,
+    }
+
+    impl<P: RoutePlanner + Clone + 'static> RoutePlanner for CountingPlanner<P> {
+        fn name(&self) -> &'static str {
+            self.inner.name()
+        }
+
+        fn plan(
+            &self,
+            ctx: &PlanningContext<'_, '_>,
+            dump: &mut PlanningDump<'_>,
+        ) -> Result<RoutePlan, RoutePlanError> {
+            self.count.fetch_add(1, Ordering::SeqCst);
+            self.inner.plan(ctx, dump)
+        }
+    }
+
+    fn assert_yield(action: Action) {
+        match action {
+            Action::Yield(_) => {}
+            _ => panic!("expected Yield"),
+        }
+    }
+
+    /// Follow a straight route, get knocked back partway through, and return
+    /// how many times the planner ran.
+    fn plans_after_drift(follow: impl FnOnce(FollowRoute) -> FollowRoute) -> usize {
+        let count = Arc::new(AtomicUsize::new(0));
+        let mut follow = follow(FollowRoute::new(CountingPlanner {
+            inner: GroundStraightPlanner::new(Point2::new(0.0, 4000.0), StraightMode::Asap),
+            count: count.clone(),
+        }));
+        let ball_loc = Point3::new(0.0, 4500.0, rl::BALL_RADIUS);
+
+        let mut tick = |y: f32, time: f32| {
+            let car = car_on_floor(Point2::new(0.0, y), PI / 2.0, 1000.0, 50.0);
+            with_offline_context(&car, ball_loc, Vector3::zeros(), time, |ctx| {
+                follow.execute_old(ctx)
+            })
+        };
+
+        // Right where the plan expects us to be.
+        assert_yield(tick(0.0, 100.0));
+        assert_yield(tick(100.0, 100.1));
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+
+        // Knocked well back, but still lined up so the straight can replan.
+        assert_yield(tick(-600.0, 100.2));
+        count.load(Ordering::SeqCst)
+    }
+
+    #[test]
+    fn replans_after_drift() {
+        assert_eq!(plans_after_drift(|follow| follow), 2);
+    }
+
+    #[test]
+    fn ignores_drift_when_turned_off() {
+        assert_eq!(plans_after_drift(|follow| follow.replan_on_drift(false)), 1);
+    }
+}
@@ -0,0 +1,276 @@
+//! Throw random but plausible game states at every planner, and check that
+//! the plans that come back make sense.
+//!
+//! The cases come from a fixed seed, so a failure reproduces on every run. To
+//! dig up new failures, set `FUZZ_SEED` to something else and `FUZZ_CASES`
+//! to something bigger. Each failure is reported with a seed that reproduces
+//! just that case with `FUZZ_CASES=1`.
+
+use crate::{
+    routing::{
+        models::{CarState, PlanningContext, ProvisionalPlanExpansion, RoutePlanner, SegmentPlan},
+        plan::{
+            AvoidCars, GetDollar, GroundArrive, GroundDrive, GroundHalfFlip, GroundIntercept,
+            GroundReverse, GroundSpeedFlip, GroundStraightPlanner, GroundToWall, SearchPlanner,
+            TurnPlanner, ViaWall, WallIntercept, WallToCeilingPlanner, WallToGround,
+        },
+        segments::StraightMode,
+        test::with_offline_planning_context,
+    },
+    utils::Rng,
+};
+use common::{prelude::*, rl};
+use nalgebra::{Point2, Point3, UnitComplex, UnitQuaternion, Vector2, Vector3};
+use std::{env, f32::consts::PI, panic, panic::AssertUnwindSafe};
+use vec_box::vec_box;
+
+const DEFAULT_SEED: u64 = 0;
+const DEFAULT_CASES: u64 = 50;
+
+/// How far apart two states can be and still count as the same. Segments
+/// pass through `CarState2D` and back, so they don't always line up to the
+/// bit.
+const LOC_TOLERANCE: f32 = 5.0;
+const VEL_TOLERANCE: f32 = 5.0;
+const ROT_TOLERANCE: f32 = 0.05;
+
+#[test]
+fn planners_survive_random_states() {
+    let seed = env_u64("FUZZ_SEED", DEFAULT_SEED);
+    let cases = env_u64("FUZZ_CASES", DEFAULT_CASES);
+
+    let mut failures = Vec::new();
+    for case_seed in seed..seed + cases {
+        let mut rng = Rng::new(case_seed);
+        let start = random_car_state(&mut rng);
+        let (ball_loc, ball_vel) = random_ball(&mut rng);
+
+        for planner in random_planners(&mut rng) {
+            let result = with_offline_planning_context(&start, ball_loc, ball_vel, |ctx| {
+                check_planner(&*planner, ctx)
+            });
+            if let Err(message) = result {
+                failures.push(format!(
+                    "FUZZ_SEED={} {}: {}",
+                    case_seed,
+                    planner.name(),
+                    message,
+                ));
+            }
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{} failures:\n{}",
+        failures.len(),
+        failures.join("\n"),
+    );
+}
+
+fn env_u64(name: &str, default: u64) -> u64 {
+    match env::var(name) {
+        Ok(s) => s
+            .parse()
+            .unwrap_or_else(|_| panic!("{} must be a non-negative integer, got {:?}", name, s)),
+        Err(_) => default,
+    }
+}
+
+/// Mostly cars driving around on the ground, since that's what most planners
+/// expect, with some in the air to make sure they bail out cleanly.
+fn random_car_state(rng: &mut Rng) -> CarState {
+    let boost = rng.gen_range(0.0, 100.0).round();
+
+    if rng.gen_bool(0.8) {
+        let loc = random_field_loc(rng, 200.0);
+        let rot = UnitComplex::new(rng.gen_range(-PI, PI));
+        let forward = rot * Vector2::x();
+        // Sometimes rolling backwards, sometimes skidding sideways.
+        let speed = rng.gen_range(-500.0, rl::CAR_MAX_SPEED);
+        let slip = if rng.gen_bool(0.2) {
+            rng.gen_range(-300.0, 300.0)
+        } else {
+            0.0
+        };
+        let vel = forward * speed + Vector2::new(-forward.y, forward.x) * slip;
+        return CarState {
+            loc: loc.to_3d(rl::OCTANE_NEUTRAL_Z),
+            rot: rot.around_z_axis(),
+            vel: cap_speed(vel).to_3d(0.0),
+            boost,
+        };
+    }
+
+    let loc = random_field_loc(rng, 200.0).to_3d(rng.gen_range(100.0, 1500.0));
+    let rot = UnitQuaternion::from_euler_angles(
+        rng.gen_range(-PI, PI),
+        rng.gen_range(-PI / 2.0, PI / 2.0),
+        rng.gen_range(-PI, PI),
+    );
+    let vel = Vector3::new(
+        rng.gen_range(-1500.0, 1500.0),
+        rng.gen_range(-1500.0, 1500.0),
+        rng.gen_range(-800.0, 800.0),
+    );
+    CarState {
+        loc,
+        rot,
+        vel,
+        boost,
+    }
+}
+
+fn random_ball(rng: &mut Rng) -> (Point3<f32>, Vector3<f32>) {
+    let loc = random_field_loc(rng, rl::BALL_RADIUS)
+        .to_3d(rng.gen_range(rl::BALL_RADIUS, rl::FIELD_MAX_Z - rl::BALL_RADIUS));
+    let vel = Vector3::new(
+        rng.gen_range(-1500.0, 1500.0),
+        rng.gen_range(-1500.0, 1500.0),
+        rng.gen_range(-800.0, 800.0),
+    );
+    (loc, vel)
+}
+
+/// A point on the field, at least `margin` away from the walls.
+fn random_field_loc(rng: &mut Rng, margin: f32) -> Point2<f32> {
+    let max_x = rl::FIELD_MAX_X - margin;
+    let max_y = rl::FIELD_MAX_Y - margin;
+    Point2::new(rng.gen_range(-max_x, max_x), rng.gen_range(-max_y, max_y))
+}
+
+fn cap_speed(vel: Vector2<f32>) -> Vector2<f32> {
+    if vel.norm() > rl::CAR_MAX_SPEED {
+        vel.normalize() * rl::CAR_MAX_SPEED
+    } else {
+        vel
+    }
+}
+
+/// Every public planner, pointed at random targets.
+fn random_planners(rng: &mut Rng) -> Vec<Box<dyn RoutePlanner>> {
+    let target = random_field_loc(rng, 100.0);
+    let face = random_field_loc(rng, 100.0);
+    let mode = *rng.choose(&[StraightMode::Asap, StraightMode::Fake]);
+    let target_time = rng.gen_range(0.0, 5.0);
+    let wall_side = *rng.choose(&[-1.0_f32, 1.0]);
+    let wall_loc = Point3::new(
+        rl::FIELD_MAX_X * wall_side,
+        rng.gen_range(-3000.0, 3000.0),
+        rng.gen_range(200.0, 1500.0),
+    );
+
+    vec_box![
+        GroundIntercept::new(),
+        GroundIntercept::new().allow_dodging(rng.gen_bool(0.5)),
+        GroundStraightPlanner::new(target, mode),
+        GroundStraightPlanner::new(target, mode).target_time(target_time),
+        TurnPlanner::new(face, None),
+        TurnPlanner::new(
+            face,
+            Some(Box::new(GroundStraightPlanner::new(target, mode))),
+        ),
+        GroundDrive::new(target).allow_reverse(rng.gen_bool(0.5)),
+        GroundArrive::new(target, target_time),
+        GroundReverse::new(target),
+        GroundHalfFlip::new(face),
+        GroundSpeedFlip::new(target),
+        GetDollar::new(target).target_face(face),
+        WallIntercept::new(),
+        WallToCeilingPlanner::new(wall_loc),
+        GroundToWall::new(wall_loc),
+        WallToGround::new(target),
+        ViaWall::new(wall_loc, target),
+        SearchPlanner::new(target.to_3d(rl::OCTANE_NEUTRAL_Z)).target_face(face),
+        AvoidCars::new(GroundDrive::new(target)),
+    ]
+}
+
+/// Planning errors are fine, since plenty of these states are hopeless. What
+/// isn't fine is panicking, or a plan that doesn't hang together.
+fn check_planner(planner: &dyn RoutePlanner, ctx: &PlanningContext<'_, '_>) -> Result<(), String> {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let plan = match PlanningContext::plan_2(planner, ctx) {
+            Ok((plan, _log)) => plan,
+            Err(_) => return Ok(()),
+        };
+        let tail = match plan.provisional_expand_2(ctx.game, ctx.ball_prediction) {
+            Ok(tail) => tail,
+            Err(_) => return Ok(()),
+        };
+
+        let expansion = ProvisionalPlanExpansion::new(&*plan.segment, &tail);
+        let segments: Vec<_> = expansion.iter().collect();
+        for segment in &segments {
+            check_segment(*segment)?;
+        }
+        for pair in segments.windows(2) {
+            check_same_state(
+                &format!("{} -> {}", pair[0].name(), pair[1].name()),
+                &pair[0].end(),
+                &pair[1].start(),
+            )?;
+        }
+        Ok(())
+    }));
+    result.unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| (*s).to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        Err(format!("panicked: {}", message))
+    })
+}
+
+fn check_segment(segment: &dyn SegmentPlan) -> Result<(), String> {
+    let name = segment.name();
+    let duration = segment.duration();
+    if !duration.is_finite() || duration < 0.0 {
+        return Err(format!("{} has duration {}", name, duration));
+    }
+
+    let start = segment.start();
+    let end = segment.end();
+    check_finite(&format!("{} start", name), &start)?;
+    check_finite(&format!("{} end", name), &end)?;
+
+    for i in 0..=10 {
+        let t = duration * i as f32 / 10.0;
+        check_finite(&format!("{} sample({})", name, t), &segment.sample(t))?;
+    }
+    check_same_state(&format!("{} sample(0)", name), &start, &segment.sample(0.0))?;
+    check_same_state(
+        &format!("{} sample(duration)", name),
+        &end,
+        &segment.sample(duration),
+    )?;
+    Ok(())
+}
+
+fn check_finite(what: &str, state: &CarState) -> Result<(), String> {
+    let finite = state.loc.iter().all(|x| x.is_finite())
+        && state.rot.coords.iter().all(|x| x.is_finite())
+        && state.vel.iter().all(|x| x.is_finite())
+        && state.boost.is_finite();
+    if !finite {
+        return Err(format!(
+            "{} is not finite: loc {:?} rot {:?} vel {:?} boost {}",
+            what, state.loc, state.rot.coords, state.vel, state.boost,
+        ));
+    }
+    Ok(())
+}
+
+fn check_same_state(what: &str, expected: &CarState, actual: &CarState) -> Result<(), String> {
+    let loc_diff = (actual.loc - expected.loc).norm();
+    let vel_diff = (actual.vel - expected.vel).norm();
+    let rot_diff = actual.rot.angle_to(&expected.rot);
+    if loc_diff > LOC_TOLERANCE || vel_diff > VEL_TOLERANCE || rot_diff > ROT_TOLERANCE {
+        return Err(format!(
+            "{} is discontinuous: loc off by {:.1}, vel off by {:.1}, rot off by {:.3}",
+            what, loc_diff, vel_diff, rot_diff,
+        ));
+    }
+    Ok(())
+}
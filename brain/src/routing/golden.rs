@@ -0,0 +1,253 @@
+//! Golden-file tests for the route planners.
+//!
+//! Each test runs one planner from a grid of start states against a few
+//! targets, and writes a line for every segment of the expanded plan. The
+//! result is compared to `brain/golden/<planner>.txt`. When a change to the
+//! planners is intentional, rerun with `BLESS_GOLDEN=1` to rewrite the files,
+//! and review the diff like any other change.
+
+use crate::routing::{
+    models::{CarState, PlanningContext, RoutePlanner},
+    plan::{GetDollar, GroundIntercept, GroundStraightPlanner, TurnPlanner, WallIntercept},
+    record::{record_error, record_segment},
+    segments::StraightMode,
+    test::with_offline_planning_context,
+};
+use collect::{RecordingPlanError, RecordingPlanSegment};
+use common::{prelude::*, rl};
+use nalgebra::{Point2, Point3, UnitComplex, Vector2, Vector3};
+use std::{env, fmt::Write, fs, path::PathBuf};
+
+#[test]
+fn ground_intercept() {
+    let balls = [
+        (
+            "resting",
+            Point3::new(0.0, 0.0, rl::BALL_RADIUS),
+            Vector3::zeros(),
+        ),
+        (
+            "rolling",
+            Point3::new(1500.0, 1500.0, rl::BALL_RADIUS),
+            Vector3::new(-800.0, 300.0, 0.0),
+        ),
+        (
+            "bouncing",
+            Point3::new(-1000.0, 2000.0, 600.0),
+            Vector3::new(500.0, -500.0, 300.0),
+        ),
+    ];
+    check_planner("ground_intercept", &balls, &[(
+        "ball",
+        GroundIntercept::new(),
+    )]);
+}
+
+#[test]
+fn ground_straight() {
+    let planners: Vec<_> = targets()
+        .into_iter()
+        .map(|(name, loc)| (name, GroundStraightPlanner::new(loc, StraightMode::Asap)))
+        .collect();
+    check_planner("ground_straight", &resting_ball(), &planners);
+}
+
+#[test]
+fn turn() {
+    let planners: Vec<_> = targets()
+        .into_iter()
+        .map(|(name, loc)| (name, TurnPlanner::new(loc, None)))
+        .collect();
+    check_planner("turn", &resting_ball(), &planners);
+}
+
+#[test]
+fn wall_intercept() {
+    let balls = [
+        (
+            "to side wall",
+            Point3::new(3000.0, 500.0, rl::BALL_RADIUS),
+            Vector3::new(1500.0, 200.0, 0.0),
+        ),
+        (
+            "up side wall",
+            Point3::new(-3800.0, -1000.0, 600.0),
+            Vector3::new(-300.0, 800.0, 600.0),
+        ),
+        (
+            "resting",
+            Point3::new(0.0, 0.0, rl::BALL_RADIUS),
+            Vector3::zeros(),
+        ),
+    ];
+    check_planner("wall_intercept", &balls, &[("ball", WallIntercept::new())]);
+}
+
+#[test]
+fn get_dollar() {
+    let planners: Vec<_> = targets()
+        .into_iter()
+        .map(|(name, loc)| (name, GetDollar::new(loc)))
+        .collect();
+    check_planner("get_dollar", &resting_ball(), &planners);
+}
+
+type Ball = (&'static str, Point3<f32>, Vector3<f32>);
+
+fn resting_ball() -> [Ball; 1] {
+    [(
+        "resting",
+        Point3::new(0.0, 0.0, rl::BALL_RADIUS),
+        Vector3::zeros(),
+    )]
+}
+
+fn targets() -> Vec<(&'static str, Point2<f32>)> {
+    vec![
+        ("center", Point2::new(0.0, 0.0)),
+        ("blue corner", Point2::new(-3000.0, -4000.0)),
+        ("orange side", Point2::new(3500.0, 1500.0)),
+    ]
+}
+
+/// Every combination of a few locations, headings and speeds on the ground.
+fn starts() -> Vec<(String, CarState)> {
+    let locs = [
+        Point2::new(0.0, -4608.0),
+        Point2::new(-2000.0, -1000.0),
+        Point2::new(2500.0, 2500.0),
+    ];
+    let yaws: [f32; 4] = [0.0, 90.0, 180.0, -45.0];
+    let speeds: [f32; 2] = [0.0, 1200.0];
+
+    let mut result = Vec::new();
+    for &loc in &locs {
+        for &yaw in &yaws {
+            for &speed in &speeds {
+                let rot = UnitComplex::new(yaw.to_radians());
+                let name = format!(
+                    "loc ({:.0}, {:.0}) yaw {:.0} speed {:.0}",
+                    loc.x, loc.y, yaw, speed,
+                );
+                let state = CarState {
+                    loc: loc.to_3d(rl::OCTANE_NEUTRAL_Z),
+                    rot: rot.around_z_axis(),
+                    vel: (rot * Vector2::x() * speed).to_3d(0.0),
+                    boost: 50.0,
+                };
+                result.push((name, state));
+            }
+        }
+    }
+    result
+}
+
+fn check_planner(name: &str, balls: &[Ball], planners: &[(&str, impl RoutePlanner)]) {
+    let mut actual = String::new();
+    for (start_name, start) in starts() {
+        for &(ball_name, ball_loc, ball_vel) in balls {
+            for (target_name, planner) in planners {
+                writeln!(
+                    actual,
+                    "{} | {} ball | {}",
+                    start_name, ball_name, target_name
+                )
+                .unwrap();
+                let lines = with_offline_planning_context(&start, ball_loc, ball_vel, |ctx| {
+                    describe_plan(planner, ctx)
+                });
+                for line in lines {
+                    writeln!(actual, "    {}", line).unwrap();
+                }
+            }
+        }
+    }
+    check_golden(name, &actual);
+}
+
+/// Expand the plan all the way out, and describe each segment on its own line.
+fn describe_plan(planner: &dyn RoutePlanner, ctx: &PlanningContext<'_, '_>) -> Vec<String> {
+    let mut lines = Vec::new();
+    let plan = match PlanningContext::plan_2(planner, ctx) {
+        Ok((plan, _log)) => plan,
+        Err(err) => {
+            lines.push(describe_error(&record_error(err.planner_name, &err.error)));
+            return lines;
+        }
+    };
+    lines.push(describe_segment(&record_segment(
+        planner.name(),
+        &*plan.segment,
+    )));
+    match plan.provisional_expand_2(ctx.game, ctx.ball_prediction) {
+        Ok(tail) => {
+            lines.extend(
+                tail.iter()
+                    .map(|(planner, s)| describe_segment(&record_segment(planner, s))),
+            );
+        }
+        Err(err) => {
+            lines.push(describe_error(&record_error(err.planner_name, &err.error)));
+        }
+    }
+    lines
+}
+
+fn describe_segment(segment: &RecordingPlanSegment) -> String {
+    let end = &segment.end;
+    format!(
+        "{} / {} {:.2}s -> loc ({:.0}, {:.0}, {:.0}) yaw {:.0} speed {:.0} boost {:.0}",
+        segment.planner,
+        segment.segment,
+        segment.duration,
+        end.loc.x,
+        end.loc.y,
+        end.loc.z,
+        end.rot.to_2d().angle().to_degrees(),
+        end.vel.norm(),
+        end.boost,
+    )
+}
+
+fn describe_error(error: &RecordingPlanError) -> String {
+    format!("{} error: {}", error.planner, error.error)
+}
+
+fn check_golden(name: &str, actual: &str) {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("golden");
+    let path = dir.join(format!("{}.txt", name));
+
+    if env::var_os("BLESS_GOLDEN").is_some() {
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path).unwrap_or_else(|err| {
+        panic!(
+            "can't read {}: {}. To create it, rerun with BLESS_GOLDEN=1.",
+            path.display(),
+            err,
+        )
+    });
+    if expected == actual {
+        return;
+    }
+
+    let expected: Vec<_> = expected.lines().collect();
+    let actual: Vec<_> = actual.lines().collect();
+    let mut diff = String::new();
+    for i in 0..expected.len().max(actual.len()) {
+        let e = expected.get(i).cloned().unwrap_or("");
+        let a = actual.get(i).cloned().unwrap_or("");
+        if e != a {
+            writeln!(diff, "line {}:\n  - {}\n  + {}", i + 1, e, a).unwrap();
+        }
+    }
+    panic!(
+        "{} doesn't match {}. If the change is intended, rerun with BLESS_GOLDEN=1.\n{}",
+        name,
+        path.display(),
+        diff,
+    );
+}
@@ -1,9 +1,14 @@
-pub use self::segments::StraightMode;
+pub use self::segments::{CeilingChart, StraightMode};
 
 pub mod behavior;
+#[cfg(test)]
+mod fuzz;
+#[cfg(test)]
+mod golden;
 pub mod models;
 pub mod plan;
+mod record;
 pub mod recover;
 mod segments;
 #[cfg(test)]
-mod test;
+pub mod test;
@@ -66,6 +66,26 @@ impl CarState {
             boost: self.boost,
         }
     }
+
+    /// Carry over how far `start` is from `model_start`, fading it out by
+    /// `frac` = 1. Segments whose models can't represent the start exactly
+    /// (e.g. a car sitting a little off the surface) use this so their samples
+    /// still pick up where the car really is.
+    pub fn with_start_error(
+        &self,
+        start: &CarState,
+        model_start: &CarState,
+        frac: f32,
+    ) -> CarState {
+        let keep = 1.0 - frac.max(0.0).min(1.0);
+        let rot_error = start.rot * model_start.rot.inverse();
+        CarState {
+            loc: self.loc + (start.loc - model_start.loc) * keep,
+            rot: rot_error.powf(keep) * self.rot,
+            vel: self.vel + (start.vel - model_start.vel) * keep,
+            boost: self.boost,
+        }
+    }
 }
 
 impl<'a> From<&'a common::halfway_house::PlayerInfo> for CarState {
@@ -237,7 +257,16 @@ impl<'a> PlanningDump<'a> {
 }
 
 pub struct ProvisionalPlanExpansionTail {
-    items: Vec<Box<dyn SegmentPlan>>,
+    /// Each segment, along with the name of the planner that produced it.
+    items: Vec<(&'static str, Box<dyn SegmentPlan>)>,
+}
+
+impl ProvisionalPlanExpansionTail {
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &dyn SegmentPlan)> {
+        self.items
+            .iter()
+            .map(|(planner, s)| -> (&'static str, &dyn SegmentPlan) { (*planner, &**s) })
+    }
 }
 
 #[derive(new)]
@@ -248,7 +277,7 @@ pub struct ProvisionalPlanExpansion<'a> {
 
 impl<'a> ProvisionalPlanExpansion<'a> {
     pub fn iter(&'a self) -> impl Iterator<Item = &'a (dyn SegmentPlan + 'a)> {
-        iter::once(self.head).chain(self.tail.items.iter().map(|s| &**s))
+        iter::once(self.head).chain(self.tail.iter().map(|(_, s)| s))
     }
 
     pub fn duration(&self) -> f32 {
@@ -266,6 +295,8 @@ pub enum RoutePlanError {
     CannotOperateWall,
     NoWallIntercept,
     TurnAngleTooLarge,
+    BlockedByCar { car_loc: Point2<f32> },
+    CannotArriveInTime,
     OtherError(&'static str),
 }
 
@@ -281,6 +312,8 @@ impl fmt::Debug for RoutePlanError {
             RoutePlanError::CannotOperateWall => f.write_str(stringify!(CannotOperateWall)),
             RoutePlanError::NoWallIntercept => f.write_str(stringify!(NoWallIntercept)),
             RoutePlanError::TurnAngleTooLarge => f.write_str(stringify!(TurnAngleTooLarge)),
+            RoutePlanError::BlockedByCar { .. } => f.write_str(stringify!(BlockedByCar)),
+            RoutePlanError::CannotArriveInTime => f.write_str(stringify!(CannotArriveInTime)),
             RoutePlanError::OtherError(msg) => write!(f, "{}({:?})", stringify!(OtherError), msg),
         }
     }
@@ -314,7 +347,7 @@ impl RoutePlan {
             };
             let mut log = Vec::new();
             let mut dump = PlanningDump { log: &mut log };
-            match Self::expand_round(&**planner, &context, &mut dump, |s| tail.push(s)) {
+            match Self::expand_round(&**planner, &context, &mut dump, |p, s| tail.push((p, s))) {
                 Ok(()) => {}
                 Err((planner_name, error)) => {
                     return Err(ProvisionalExpandError {
@@ -332,7 +365,7 @@ impl RoutePlan {
         planner: &dyn RoutePlanner,
         ctx: &PlanningContext<'_, '_>,
         dump: &mut PlanningDump<'_>,
-        mut sink: impl FnMut(Box<dyn SegmentPlan>),
+        mut sink: impl FnMut(&'static str, Box<dyn SegmentPlan>),
     ) -> Result<(), (&'static str, RoutePlanError)> {
         dump.log.push(format!("-{}----------", planner.name()));
         let plan = planner.plan(ctx, dump).map_err(|e| (planner.name(), e))?;
@@ -340,7 +373,7 @@ impl RoutePlan {
 
         let state = plan.segment.end();
         let duration = plan.segment.duration();
-        sink(plan.segment);
+        sink(planner.name(), plan.segment);
 
         match plan.next {
             Some(planner) => {
@@ -367,6 +400,9 @@ pub trait SegmentPlan: SegmentPlanCloneBox + Send {
     fn start(&self) -> CarState;
     fn end(&self) -> CarState;
     fn duration(&self) -> f32;
+    /// The state the plan expects the car to be in `t` seconds into the
+    /// segment. `t` is clamped to `[0, duration]`.
+    fn sample(&self, t: f32) -> CarState;
     fn run(&self) -> Box<dyn SegmentRunner>;
     fn draw(&self, ctx: &mut Context<'_>);
 }
@@ -404,39 +440,34 @@ pub enum SegmentRunAction {
 
 #[cfg(test)]
 mod tests {
-    use crate::{
-        routing::models::{CarState, PlanningContext, PlanningDump, RoutePlanner},
-        strategy::Scenario,
+    use crate::routing::{
+        models::{CarState, PlanningContext, RoutePlanner},
+        plan::GroundIntercept,
+        test::with_offline_planning_context,
     };
     use common::prelude::*;
     use nalgebra::{Point3, UnitComplex, Vector3};
-    use std::{f32::consts::PI, mem};
+    use std::f32::consts::PI;
 
     #[test]
     #[ignore = "Use this as needed to debug a plan."]
     fn debug_plan() {
-        // I'm very lucky that this works.
-        let scenario: Scenario<'_> = unsafe { mem::zeroed() };
-        let ball_prediction = unsafe { mem::zeroed() };
-
-        let planner: &dyn RoutePlanner = unsafe { mem::uninitialized() }; // Fill this in.
-        let ctx = PlanningContext {
-            game: &scenario.game,
-            start: CarState {
-                loc: Point3::new(0.0, -4608.0, 17.01),
-                rot: UnitComplex::new(PI / 2.0).around_z_axis(),
-                vel: Vector3::zeros(),
-                boost: 33.0,
-            },
-            ball_prediction: &ball_prediction,
+        let planner: &dyn RoutePlanner = &GroundIntercept::new(); // Fill this in.
+        let start = CarState {
+            loc: Point3::new(0.0, -4608.0, 17.01),
+            rot: UnitComplex::new(PI / 2.0).around_z_axis(),
+            vel: Vector3::zeros(),
+            boost: 33.0,
         };
-        let mut log = Vec::new();
-        let mut dump = PlanningDump { log: &mut log };
-        planner
-            .plan(&ctx, &mut dump)
-            .unwrap()
-            .provisional_expand(&scenario)
-            .ok()
-            .unwrap();
+        let ball_loc = Point3::new(0.0, 0.0, 92.74);
+        with_offline_planning_context(&start, ball_loc, Vector3::zeros(), |ctx| {
+            let (plan, log) = PlanningContext::plan_2(planner, ctx).ok().unwrap();
+            for line in log {
+                println!("{}", line);
+            }
+            plan.provisional_expand_2(ctx.game, ctx.ball_prediction)
+                .ok()
+                .unwrap();
+        });
     }
 }
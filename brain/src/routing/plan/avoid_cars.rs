@@ -0,0 +1,288 @@
+use crate::{
+    routing::{
+        models::{
+            PlanningContext, PlanningDump, ProvisionalPlanExpansion, RoutePlan, RoutePlanError,
+            RoutePlanner, SegmentPlan,
+        },
+        plan::{ground_drive::GroundDrive, higher_order::ChainedPlanner},
+    },
+    strategy::Game,
+};
+use common::prelude::*;
+use nalgebra::{Point2, Vector2};
+use nameof::name_of_type;
+
+/// Check the route from `inner` against where the enemy cars are headed. If
+/// it runs into one, try to bend around it, and failing that, report
+/// `RoutePlanError::BlockedByCar`.
+#[derive(Clone)]
+pub struct AvoidCars {
+    inner: Box<dyn RoutePlanner>,
+    clearance: f32,
+}
+
+impl AvoidCars {
+    /// Center-to-center distance, so this is roughly a car length of room.
+    const DEFAULT_CLEARANCE: f32 = 200.0;
+    /// Extrapolating a car's velocity gets silly after a couple seconds.
+    const MAX_PREDICTION_TIME: f32 = 2.0;
+    const SAMPLE_DT: f32 = 0.05;
+    /// Anything we'd run into this soon is too close to steer around, and is
+    /// more likely a 50/50 than a car in the way.
+    const MIN_CONFLICT_TIME: f32 = 0.25;
+    /// A car already this close (as a multiple of the clearance) is racing
+    /// alongside us or challenging us, not blocking the route.
+    const ADJACENT_FACTOR: f32 = 1.5;
+    /// Cars near the end of the route are contesting whatever we're going
+    /// for, which is the point, so don't count them.
+    const FINAL_APPROACH_TIME: f32 = 0.3;
+    /// How far out from the blocking car to put the detour waypoint, as a
+    /// multiple of the clearance.
+    const DETOUR_FACTOR: f32 = 1.5;
+
+    pub fn new(inner: impl RoutePlanner + 'static) -> Self {
+        Self {
+            inner: Box::new(inner),
+            clearance: Self::DEFAULT_CLEARANCE,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn clearance(mut self, clearance: f32) -> Self {
+        self.clearance = clearance;
+        self
+    }
+}
+
+impl RoutePlanner for AvoidCars {
+    fn name(&self) -> &'static str {
+        name_of_type!(AvoidCars)
+    }
+
+    fn plan(
+        &self,
+        ctx: &PlanningContext<'_, '_>,
+        dump: &mut PlanningDump<'_>,
+    ) -> Result<RoutePlan, RoutePlanError> {
+        dump.log_start(self, &ctx.start);
+
+        let plan = self.inner.plan(ctx, dump)?;
+        let adjacent_distance = self.clearance * Self::ADJACENT_FACTOR;
+        let obstacles: Vec<_> = CarObstacle::enemies(ctx.game)
+            .into_iter()
+            .filter(|car| (car.loc - ctx.start.loc_2d()).norm() >= adjacent_distance)
+            .collect();
+        if obstacles.is_empty() {
+            return Ok(plan);
+        }
+
+        let conflict = some_or_else!(self.first_conflict(ctx, &plan, &obstacles), {
+            return Ok(plan);
+        });
+        dump.log_pretty(self, "blocked by car at", conflict.car_loc);
+
+        let waypoint = self.detour_waypoint(&conflict);
+        dump.log_pretty(self, "detour waypoint", waypoint);
+        let detour = ChainedPlanner::new(
+            Box::new(GroundDrive::new(waypoint)),
+            Some(self.inner.clone()),
+        );
+        if let Ok(plan) = detour.plan(ctx, dump) {
+            if self.first_conflict(ctx, &plan, &obstacles).is_none() {
+                return Ok(plan);
+            }
+        }
+
+        dump.log(self, "no way around");
+        Err(RoutePlanError::BlockedByCar {
+            car_loc: conflict.car_loc,
+        })
+    }
+}
+
+impl AvoidCars {
+    /// Walk the whole route (including the parts that haven't been planned
+    /// yet) and find the first time it comes too close to an enemy.
+    fn first_conflict(
+        &self,
+        ctx: &PlanningContext<'_, '_>,
+        plan: &RoutePlan,
+        obstacles: &[CarObstacle],
+    ) -> Option<Conflict> {
+        // If the rest of the route fails to expand, the follower will find out
+        // soon enough. Check what we can.
+        let tail = plan
+            .provisional_expand_2(ctx.game, ctx.ball_prediction)
+            .ok();
+        let expansion;
+        let segments: Vec<&dyn SegmentPlan> = match tail {
+            Some(ref tail) => {
+                expansion = ProvisionalPlanExpansion::new(&*plan.segment, tail);
+                expansion.iter().collect()
+            }
+            None => vec![&*plan.segment],
+        };
+
+        let total_duration: f32 = segments.iter().map(|s| s.duration()).sum();
+        let max_time = (total_duration - Self::FINAL_APPROACH_TIME).min(Self::MAX_PREDICTION_TIME);
+
+        let mut start_time = 0.0;
+        for segment in segments {
+            let duration = segment.duration();
+            let steps = (duration / Self::SAMPLE_DT).ceil().max(1.0) as usize;
+            for i in 0..=steps {
+                let segment_time = duration * i as f32 / steps as f32;
+                let t = start_time + segment_time;
+                if t >= max_time {
+                    return None;
+                }
+                if t < Self::MIN_CONFLICT_TIME {
+                    continue;
+                }
+                let route = segment.sample(segment_time);
+                let route_loc = route.loc_2d();
+                for obstacle in obstacles {
+                    let car_loc = obstacle.loc_at(t);
+                    if (car_loc - route_loc).norm() < self.clearance {
+                        return Some(Conflict {
+                            car_loc,
+                            route_loc,
+                            route_dir: route.forward_axis_2d().into_inner(),
+                        });
+                    }
+                }
+            }
+            start_time += duration;
+        }
+        None
+    }
+
+    /// A point beside the blocking car, across the route's direction of
+    /// travel, on the same side the route was already passing it.
+    fn detour_waypoint(&self, conflict: &Conflict) -> Point2<f32> {
+        let across = conflict.route_dir.ortho();
+        let side = if (conflict.route_loc - conflict.car_loc).dot(&across) >= 0.0 {
+            1.0
+        } else {
+            -1.0
+        };
+        conflict.car_loc + across * side * self.clearance * Self::DETOUR_FACTOR
+    }
+}
+
+struct Conflict {
+    /// Where the enemy will be when we run into them.
+    car_loc: Point2<f32>,
+    /// Where we will be at that moment.
+    route_loc: Point2<f32>,
+    /// Which way we will be facing.
+    route_dir: Vector2<f32>,
+}
+
+/// An enemy car, assumed to keep driving at its current velocity.
+struct CarObstacle {
+    loc: Point2<f32>,
+    vel: Vector2<f32>,
+}
+
+impl CarObstacle {
+    fn enemies(game: &Game<'_>) -> Vec<Self> {
+        game.cars(game.enemy_team)
+            .filter(|car| !car.Demolished)
+            .map(|car| Self {
+                loc: car.Physics.loc_2d(),
+                vel: car.Physics.vel_2d(),
+            })
+            .collect()
+    }
+
+    fn loc_at(&self, t: f32) -> Point2<f32> {
+        self.loc + self.vel * t
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::routing::{
+        models::{CarState, PlanningContext, RoutePlanError},
+        plan::{avoid_cars::AvoidCars, ground_drive::GroundDrive},
+        test::with_offline_planning_context_and_enemies,
+    };
+    use common::{prelude::*, rl};
+    use nalgebra::{Point2, Point3, UnitComplex, Vector3};
+    use std::f32::consts::PI;
+
+    /// Driving up the middle of the field.
+    fn start() -> CarState {
+        CarState {
+            loc: Point3::new(0.0, -3000.0, rl::OCTANE_NEUTRAL_Z),
+            rot: UnitComplex::new(PI / 2.0).around_z_axis(),
+            vel: Vector3::new(0.0, 1000.0, 0.0),
+            boost: 50.0,
+        }
+    }
+
+    fn parked_at(x: f32, y: f32) -> CarState {
+        CarState {
+            loc: Point3::new(x, y, rl::OCTANE_NEUTRAL_Z),
+            rot: UnitComplex::new(-PI / 2.0).around_z_axis(),
+            vel: Vector3::zeros(),
+            boost: 0.0,
+        }
+    }
+
+    fn planner() -> AvoidCars {
+        AvoidCars::new(GroundDrive::new(Point2::new(0.0, 1000.0)))
+    }
+
+    fn plan(enemies: &[CarState], check: impl FnOnce(&PlanningContext<'_, '_>, &AvoidCars)) {
+        let ball_loc = Point3::new(0.0, 2000.0, rl::BALL_RADIUS);
+        with_offline_planning_context_and_enemies(
+            &start(),
+            enemies,
+            ball_loc,
+            Vector3::zeros(),
+            |ctx| check(ctx, &planner()),
+        );
+    }
+
+    #[test]
+    fn detours_around_car_in_the_way() {
+        plan(&[parked_at(0.0, -1000.0)], |ctx, planner| {
+            let obstacles = super::CarObstacle::enemies(ctx.game);
+            let (direct, _log) = PlanningContext::plan_2(&*planner.inner, ctx).ok().unwrap();
+            assert!(planner.first_conflict(ctx, &direct, &obstacles).is_some());
+
+            let (detour, _log) = PlanningContext::plan_2(planner, ctx)
+                .unwrap_or_else(|err| panic!("{:?}", err.error));
+            assert!(planner.first_conflict(ctx, &detour, &obstacles).is_none());
+        });
+    }
+
+    #[test]
+    fn blocked_by_wall_of_cars() {
+        let enemies = [
+            parked_at(-300.0, -1000.0),
+            parked_at(0.0, -1000.0),
+            parked_at(300.0, -1000.0),
+        ];
+        plan(&enemies, |ctx, planner| {
+            match PlanningContext::plan_2(planner, ctx) {
+                Err(err) => match err.error {
+                    RoutePlanError::BlockedByCar { .. } => {}
+                    error => panic!("{:?}", error),
+                },
+                Ok(_) => panic!("drove through a wall of cars"),
+            }
+        });
+    }
+
+    #[test]
+    fn ignores_car_alongside() {
+        let mut alongside = start();
+        alongside.loc.x += 150.0;
+        plan(&[alongside], |ctx, planner| {
+            assert!(PlanningContext::plan_2(planner, ctx).is_ok());
+        });
+    }
+}
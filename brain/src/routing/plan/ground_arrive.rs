@@ -0,0 +1,179 @@
+use crate::routing::{
+    models::{PlanningContext, PlanningDump, RoutePlan, RoutePlanError, RoutePlanner, SegmentPlan},
+    plan::{ground_straight::GroundStraightPlanner, ground_turn::TurnPlanner},
+    recover::{IsSkidding, NotOnFlatGround},
+    segments::{StraightMode, TimedStraight},
+};
+use common::{prelude::*, Speed, Time};
+use nalgebra::{Point2, Unit, Vector2};
+use nameof::name_of_type;
+use simulate::{CarArrive, CarArriveError};
+use std::f32::consts::PI;
+
+/// Get to a location at a specific time, optionally with a given speed and
+/// heading. Unlike the other ground planners, this doesn't just go as fast as
+/// possible; it slows down or coasts if we'd otherwise be early.
+#[derive(Clone)]
+pub struct GroundArrive {
+    target_loc: Point2<f32>,
+    /// Seconds from the start of the plan.
+    target_time: f32,
+    target_speed: Option<f32>,
+    target_heading: Option<Unit<Vector2<f32>>>,
+}
+
+impl GroundArrive {
+    /// How closely we need to be facing the target before the final straight.
+    const FACE_TOLERANCE: f32 = PI / 36.0;
+    /// How far off the requested heading the final straight is allowed to be.
+    const HEADING_TOLERANCE: f32 = PI / 12.0;
+    /// When we need to arrive from a specific direction, line up this far
+    /// back from the target.
+    const APPROACH_DISTANCE: f32 = 1000.0;
+
+    pub fn new(target_loc: Point2<f32>, target_time: f32) -> Self {
+        Self {
+            target_loc,
+            target_time,
+            target_speed: None,
+            target_heading: None,
+        }
+    }
+
+    pub fn target_speed(mut self, target_speed: f32) -> Self {
+        assert!(target_speed >= 0.0);
+        self.target_speed = Some(target_speed);
+        self
+    }
+
+    pub fn target_heading(mut self, target_heading: Unit<Vector2<f32>>) -> Self {
+        self.target_heading = Some(target_heading);
+        self
+    }
+}
+
+impl RoutePlanner for GroundArrive {
+    fn name(&self) -> &'static str {
+        name_of_type!(GroundArrive)
+    }
+
+    fn plan(
+        &self,
+        ctx: &PlanningContext<'_, '_>,
+        dump: &mut PlanningDump<'_>,
+    ) -> Result<RoutePlan, RoutePlanError> {
+        dump.log_start(self, &ctx.start);
+        dump.log_pretty(self, "target_loc", self.target_loc);
+        dump.log_pretty(self, "target_time", Time(self.target_time));
+        if let Some(target_speed) = self.target_speed {
+            dump.log_pretty(self, "target_speed", Speed(target_speed));
+        }
+
+        guard!(
+            ctx.start,
+            NotOnFlatGround,
+            RoutePlanError::MustBeOnFlatGround,
+        );
+        guard!(ctx.start, IsSkidding, RoutePlanError::MustNotBeSkidding {
+            recover_target_loc: self.target_loc,
+        });
+        if self.target_time <= 0.0 {
+            return Err(RoutePlanError::CannotArriveInTime);
+        }
+
+        // This plans one segment at a time, so each step knows exactly how much
+        // time is left.
+        let waypoint = self.waypoint(ctx);
+        let to_waypoint = waypoint - ctx.start.loc_2d();
+        if ctx.start.forward_axis_2d().angle_to(&to_waypoint).abs() >= Self::FACE_TOLERANCE {
+            dump.log(self, "turning to face the waypoint");
+            let turn = TurnPlanner::new(waypoint, None).plan(ctx, dump)?;
+            return self.then(turn.segment);
+        }
+        if waypoint != self.target_loc {
+            dump.log(self, "driving to the approach point");
+            let straight = GroundStraightPlanner::new(waypoint, StraightMode::Asap)
+                .allow_dodging(false)
+                .plan(ctx, dump)?;
+            return self.then(straight.segment);
+        }
+
+        let start = ctx.start.to_2d_assume();
+        let distance = (self.target_loc - start.loc).norm();
+        let schedule = CarArrive::calc_1d(
+            start.vel.norm(),
+            start.boost,
+            distance,
+            self.target_time,
+            self.target_speed,
+        )
+        .map_err(|error| match error {
+            CarArriveError::TooFar => RoutePlanError::CannotArriveInTime,
+            CarArriveError::TooFast => RoutePlanError::MovingTooFast,
+        })?;
+        dump.log_pretty(self, "cruise_speed", Speed(schedule.cruise_speed));
+
+        let segment = TimedStraight::new(start, self.target_loc, self.target_speed, schedule);
+        Ok(RoutePlan {
+            segment: Box::new(segment),
+            next: None,
+        })
+    }
+}
+
+impl GroundArrive {
+    /// Where to head next: straight to the target, unless that would arrive
+    /// from the wrong direction.
+    fn waypoint(&self, ctx: &PlanningContext<'_, '_>) -> Point2<f32> {
+        let heading = some_or_else!(self.target_heading, {
+            return self.target_loc;
+        });
+        let to_target = self.target_loc - ctx.start.loc_2d();
+        if heading.angle_to(&to_target).abs() < Self::HEADING_TOLERANCE {
+            return self.target_loc;
+        }
+        self.target_loc - heading.into_inner() * Self::APPROACH_DISTANCE
+    }
+
+    /// Follow `segment` with another round of planning, with whatever time is
+    /// left over.
+    fn then(&self, segment: Box<dyn SegmentPlan>) -> Result<RoutePlan, RoutePlanError> {
+        let target_time = self.target_time - segment.duration();
+        if target_time <= 0.0 {
+            return Err(RoutePlanError::CannotArriveInTime);
+        }
+        Ok(RoutePlan {
+            segment,
+            next: Some(Box::new(Self {
+                target_time,
+                ..self.clone()
+            })),
+        })
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        integration_tests::{TestRunner, TestScenario},
+        routing::{behavior::FollowRoute, plan::ground_arrive::GroundArrive},
+    };
+    use common::prelude::*;
+    use nalgebra::Point2;
+
+    #[test]
+    fn dont_arrive_early() {
+        let test = TestRunner::new()
+            .scenario(TestScenario::default())
+            .behavior(FollowRoute::new(GroundArrive::new(
+                Point2::new(0.0, 2000.0),
+                3.0,
+            )))
+            .run_for_millis(1500);
+
+        // Going flat out, we'd be there by now.
+        let packet = test.sniff_packet();
+        let loc = packet.GameCars[0].Physics.loc();
+        assert!(500.0 < loc.y && loc.y < 1500.0);
+    }
+}
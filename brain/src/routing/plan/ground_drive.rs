@@ -1,8 +1,8 @@
 use crate::routing::{
     models::{PlanningContext, PlanningDump, RoutePlan, RoutePlanError, RoutePlanner},
     plan::{
-        ground_straight::GroundStraightPlanner, ground_turn::TurnPlanner,
-        higher_order::ChainedPlanner,
+        ground_reverse::GroundReverse, ground_straight::GroundStraightPlanner,
+        ground_turn::TurnPlanner, higher_order::ChainedPlanner,
     },
     recover::{IsSkidding, NotOnFlatGround},
     segments::StraightMode,
@@ -16,6 +16,7 @@ pub struct GroundDrive {
     end_chop: f32,
     straight_mode: StraightMode,
     always_prefer_dodge: bool,
+    allow_reverse: bool,
 }
 
 impl GroundDrive {
@@ -25,6 +26,7 @@ impl GroundDrive {
             end_chop: 0.0,
             straight_mode: StraightMode::Asap,
             always_prefer_dodge: false,
+            allow_reverse: false,
         }
     }
 
@@ -42,6 +44,13 @@ impl GroundDrive {
         self.always_prefer_dodge = always_prefer_dodge;
         self
     }
+
+    /// Back up if the target is close behind us. We'll arrive facing away
+    /// from it, so this is only for callers that just need to be there.
+    pub fn allow_reverse(mut self, allow_reverse: bool) -> Self {
+        self.allow_reverse = allow_reverse;
+        self
+    }
 }
 
 impl RoutePlanner for GroundDrive {
@@ -66,6 +75,11 @@ impl RoutePlanner for GroundDrive {
             recover_target_loc: self.target_loc,
         });
 
+        if self.allow_reverse && GroundReverse::applicable(&ctx.start, self.target_loc) {
+            dump.log(self, "target is close behind; reversing");
+            return GroundReverse::new(self.target_loc).plan(ctx, dump);
+        }
+
         let turn = TurnPlanner::new(self.target_loc, None).plan(ctx, dump)?;
         let straight = GroundStraightPlanner::new(self.target_loc, self.straight_mode)
             .always_prefer_dodge(self.always_prefer_dodge)
@@ -0,0 +1,90 @@
+use crate::routing::{
+    models::{CarState, PlanningContext, PlanningDump, RoutePlan, RoutePlanError, RoutePlanner},
+    recover::{IsSkidding, NotOnFlatGround},
+    segments::HalfFlip,
+};
+use common::prelude::*;
+use derive_new::new;
+use nalgebra::Point2;
+use nameof::name_of_type;
+use simulate::CarHalfFlip;
+use std::f32::consts::PI;
+
+/// Turn around with a half-flip, leaving the car facing (roughly) away from
+/// where it started.
+#[derive(Clone, new)]
+pub struct GroundHalfFlip {
+    target_face: Point2<f32>,
+}
+
+impl GroundHalfFlip {
+    /// Anything less than this is faster to just turn towards.
+    const MIN_TURN_ANGLE: f32 = PI * 5.0 / 6.0;
+    /// Past this speed, the dodge isn't enough to stop us moving forward, and
+    /// a powerslide will do better.
+    const MAX_START_SPEED: f32 = 500.0;
+    /// Leave room to actually drive towards the target after landing.
+    const MIN_CLEARANCE: f32 = 500.0;
+
+    /// Is the target behind us and far enough away that a half-flip is the
+    /// quickest way to face it?
+    pub fn applicable(start: &CarState, target_face: Point2<f32>) -> bool {
+        Self::check(start, target_face).is_ok()
+    }
+
+    fn check(start: &CarState, target_face: Point2<f32>) -> Result<(), RoutePlanError> {
+        let forward_axis = start.forward_axis_2d();
+        let to_target = target_face - start.loc_2d();
+        if forward_axis.angle_to(&to_target).abs() < Self::MIN_TURN_ANGLE {
+            return Err(RoutePlanError::OtherError("target is not behind us"));
+        }
+
+        let speed = start.vel.to_2d().dot(&forward_axis.into_inner());
+        if speed >= Self::MAX_START_SPEED {
+            return Err(RoutePlanError::MovingTooFast);
+        }
+
+        let flip = CarHalfFlip::calc_1d(speed);
+        if to_target.norm() < -flip.end_dist + Self::MIN_CLEARANCE {
+            return Err(RoutePlanError::OtherError(
+                "target is too close to half-flip",
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl RoutePlanner for GroundHalfFlip {
+    fn name(&self) -> &'static str {
+        name_of_type!(GroundHalfFlip)
+    }
+
+    fn plan(
+        &self,
+        ctx: &PlanningContext<'_, '_>,
+        dump: &mut PlanningDump<'_>,
+    ) -> Result<RoutePlan, RoutePlanError> {
+        dump.log_start(self, &ctx.start);
+        dump.log_pretty(self, "target_face", self.target_face);
+
+        guard!(
+            ctx.start,
+            NotOnFlatGround,
+            RoutePlanError::MustBeOnFlatGround,
+        );
+        guard!(ctx.start, IsSkidding, RoutePlanError::MustNotBeSkidding {
+            recover_target_loc: self.target_face,
+        });
+        Self::check(&ctx.start, self.target_face)?;
+
+        let forward_axis = ctx.start.forward_axis_2d();
+        let speed = ctx.start.vel.to_2d().dot(&forward_axis.into_inner());
+        let flip = CarHalfFlip::calc_1d(speed);
+        let roll_right = forward_axis.angle_to(&(self.target_face - ctx.start.loc_2d())) >= 0.0;
+
+        Ok(RoutePlan {
+            segment: Box::new(HalfFlip::new(ctx.start.clone(), flip, roll_right)),
+            next: None,
+        })
+    }
+}
@@ -24,6 +24,10 @@ use nameof::name_of_type;
 pub struct GroundIntercept {
     #[new(value = "GroundInterceptAllowDodging::OnlyIfSlow")]
     allow_dodging: GroundInterceptAllowDodging,
+    /// Go for the ball at this time (relative to the start of the ball
+    /// prediction) instead of at the earliest intercept.
+    #[new(default)]
+    target_time: Option<f32>,
 }
 
 #[derive(Copy, Clone)]
@@ -42,6 +46,13 @@ impl GroundIntercept {
         };
         self
     }
+
+    /// For when the caller has picked a later intercept than the earliest one,
+    /// e.g. a better shot.
+    pub fn target_time(mut self, target_time: f32) -> Self {
+        self.target_time = Some(target_time);
+        self
+    }
 }
 
 impl RoutePlanner for GroundIntercept {
@@ -63,8 +74,11 @@ impl RoutePlanner for GroundIntercept {
         );
 
         // Naive first pass to get a rough location.
-        let guess = Self::calc_intercept(&ctx.start, ctx.ball_prediction)
-            .ok_or_else(|| RoutePlanError::UnknownIntercept)?;
+        let guess = match self.target_time {
+            Some(time) => Self::with_penalty(&ctx.start, ctx.ball_prediction, time),
+            None => Self::calc_intercept(&ctx.start, ctx.ball_prediction),
+        }
+        .ok_or_else(|| RoutePlanError::UnknownIntercept)?;
 
         guard!(ctx.start, IsSkidding, RoutePlanError::MustNotBeSkidding {
             recover_target_loc: guess.loc.to_2d(),
@@ -124,8 +138,54 @@ impl GroundIntercept {
         let intercept = naive_ground_intercept_2(start, ball_prediction, |ball| {
             ball.loc.z < GroundedHit::MAX_BALL_Z
         })?;
-        let intercept = ball_prediction.at_time(intercept.time).unwrap();
+        Self::with_penalty(start, ball_prediction, intercept.time)
+    }
+
+    /// The ball frame at `time`, pushed back by the time it takes to line up.
+    fn with_penalty<'ball>(
+        start: &CarState,
+        ball_prediction: &'ball BallTrajectory,
+        time: f32,
+    ) -> Option<&'ball BallFrame> {
+        let intercept = ball_prediction.at_time(time)?;
         let penalty = naive_intercept_penalty(start, intercept);
         Some(ball_prediction.at_time_or_last(intercept.t + penalty))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::routing::{
+        models::{CarState, PlanningContext},
+        plan::ground_intercept::GroundIntercept,
+        test::with_offline_planning_context,
+    };
+    use common::{prelude::*, rl};
+    use nalgebra::{Point3, UnitComplex, Vector3};
+    use std::f32::consts::PI;
+
+    #[test]
+    fn goes_for_the_target_time() {
+        let start = CarState {
+            loc: Point3::new(0.0, -3000.0, rl::OCTANE_NEUTRAL_Z),
+            rot: UnitComplex::new(PI / 2.0).around_z_axis(),
+            vel: Vector3::new(0.0, 1000.0, 0.0),
+            boost: 50.0,
+        };
+        let ball_loc = Point3::new(-2000.0, 0.0, rl::BALL_RADIUS);
+        let ball_vel = Vector3::new(1000.0, 0.0, 0.0);
+        with_offline_planning_context(&start, ball_loc, ball_vel, |ctx| {
+            let earliest = GroundIntercept::calc_intercept(&ctx.start, ctx.ball_prediction)
+                .unwrap()
+                .clone();
+            let later = earliest.t + 1.0;
+            let planner = GroundIntercept::new().target_time(later);
+            let target =
+                GroundIntercept::with_penalty(&ctx.start, ctx.ball_prediction, later).unwrap();
+            assert!(target.t >= later);
+            // The ball rolls to +x, so the later intercept is further along.
+            assert!(target.loc.x > earliest.loc.x + 500.0);
+            assert!(PlanningContext::plan_2(&planner, ctx).is_ok());
+        });
+    }
+}
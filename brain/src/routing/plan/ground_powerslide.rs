@@ -140,6 +140,7 @@ impl RoutePlanner for GroundPowerslideEssence {
         let blueprint = {
             let straight = GroundStraightPlanner::new(self.target_loc, StraightMode::Asap)
                     .end_chop(1.0) // Leave some time for the actual slide.
+                    .allow_dodging(false)
                     .plan(ctx, dump)?;
             let straight_end = straight.segment.end();
 
@@ -161,8 +162,9 @@ impl RoutePlanner for GroundPowerslideEssence {
             let straight_dist = start_to_target.norm() - dist_reduction;
             let straight_end_loc =
                 ctx.start.loc.to_2d() + start_to_target.normalize() * straight_dist;
-            let straight =
-                GroundStraightPlanner::new(straight_end_loc, StraightMode::Asap).plan(ctx, dump)?;
+            let straight = GroundStraightPlanner::new(straight_end_loc, StraightMode::Asap)
+                .allow_dodging(false)
+                .plan(ctx, dump)?;
             let straight_end = straight.segment.end();
 
             CarPowerslideTurn::evaluate(
@@ -176,8 +178,11 @@ impl RoutePlanner for GroundPowerslideEssence {
         };
 
         let straight_end_loc = self.target_loc - (blueprint.end_loc - blueprint.start_loc);
-        let straight =
-            GroundStraightPlanner::new(straight_end_loc, StraightMode::Asap).plan(ctx, dump)?;
+        // No dodging, since a dodge plan ends at the dodge and leaves the rest of the
+        // straight to `next`. The slide has to start where the straight really ends.
+        let straight = GroundStraightPlanner::new(straight_end_loc, StraightMode::Asap)
+            .allow_dodging(false)
+            .plan(ctx, dump)?;
         let straight_end = straight.segment.end();
 
         let blueprint = CarPowerslideTurn::evaluate(
@@ -0,0 +1,135 @@
+use crate::routing::{
+    models::{CarState, PlanningContext, PlanningDump, RoutePlan, RoutePlanError, RoutePlanner},
+    recover::{IsSkidding, NotOnFlatGround},
+    segments::{ReverseArc, ReverseStraight},
+};
+use common::prelude::*;
+use derive_new::new;
+use nalgebra::Point2;
+use nameof::name_of_type;
+use std::f32::consts::PI;
+
+/// Back up to a target that's a short distance behind us, instead of turning
+/// around. The car ends up facing away from the target.
+#[derive(Clone, new)]
+pub struct GroundReverse {
+    target_loc: Point2<f32>,
+}
+
+impl GroundReverse {
+    /// Past this, it's worth turning around to drive forwards (and boost).
+    const MAX_DISTANCE: f32 = 1500.0;
+    /// How far the target can be from directly behind us.
+    const MAX_ANGLE: f32 = PI / 4.0;
+    /// Close enough to directly behind to back up in a straight line.
+    const STRAIGHT_ANGLE: f32 = PI / 36.0;
+    /// Past this speed, stopping eats up too much time.
+    const MAX_START_SPEED: f32 = 500.0;
+
+    /// Is the target close enough behind us that backing up is the quickest
+    /// way to get there?
+    pub fn applicable(start: &CarState, target_loc: Point2<f32>) -> bool {
+        Self::check(start, target_loc).is_ok()
+    }
+
+    fn check(start: &CarState, target_loc: Point2<f32>) -> Result<(), RoutePlanError> {
+        let forward_axis = start.forward_axis_2d();
+        let to_target = target_loc - start.loc_2d();
+        if to_target.norm() >= Self::MAX_DISTANCE {
+            return Err(RoutePlanError::OtherError("target is too far to reverse"));
+        }
+        if (-forward_axis.into_inner()).angle_to(&to_target).abs() >= Self::MAX_ANGLE {
+            return Err(RoutePlanError::OtherError("target is not behind us"));
+        }
+
+        let speed = start.vel.to_2d().dot(&forward_axis.into_inner());
+        if speed >= Self::MAX_START_SPEED {
+            return Err(RoutePlanError::MovingTooFast);
+        }
+        Ok(())
+    }
+}
+
+impl RoutePlanner for GroundReverse {
+    fn name(&self) -> &'static str {
+        name_of_type!(GroundReverse)
+    }
+
+    fn plan(
+        &self,
+        ctx: &PlanningContext<'_, '_>,
+        dump: &mut PlanningDump<'_>,
+    ) -> Result<RoutePlan, RoutePlanError> {
+        dump.log_start(self, &ctx.start);
+        dump.log_pretty(self, "target_loc", self.target_loc);
+
+        guard!(
+            ctx.start,
+            NotOnFlatGround,
+            RoutePlanError::MustBeOnFlatGround,
+        );
+        guard!(ctx.start, IsSkidding, RoutePlanError::MustNotBeSkidding {
+            recover_target_loc: self.target_loc,
+        });
+        Self::check(&ctx.start, self.target_loc)?;
+
+        let start = ctx.start.to_2d_assume();
+        let to_target = self.target_loc - start.loc;
+        let backward_axis = -start.forward_axis().into_inner();
+        if backward_axis.angle_to(&to_target).abs() < Self::STRAIGHT_ANGLE {
+            return Ok(RoutePlan {
+                segment: Box::new(ReverseStraight::new(start, self.target_loc)),
+                next: None,
+            });
+        }
+
+        // Find the circle that's tangent to the car and passes through the
+        // target. Its center is on the car's side axis, the same distance from
+        // the car and the target.
+        let lateral = to_target.dot(&start.right_axis());
+        let offset = to_target.norm_squared() / (2.0 * lateral);
+        let center = start.loc + start.right_axis().into_inner() * offset;
+        let arc = ReverseArc::new(start, center, self.target_loc);
+
+        let min_radius = 1.0 / chip::max_curvature(arc.end_speed());
+        if arc.radius() < min_radius {
+            return Err(RoutePlanError::TurningRadiusTooTight);
+        }
+        Ok(RoutePlan {
+            segment: Box::new(arc),
+            next: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        integration_tests::{TestRunner, TestScenario},
+        routing::{behavior::FollowRoute, plan::ground_reverse::GroundReverse},
+    };
+    use common::prelude::*;
+    use nalgebra::{Point2, Point3, Rotation3};
+    use std::f32::consts::PI;
+
+    #[test]
+    fn back_up_to_target() {
+        let target_loc = Point2::new(300.0, -800.0);
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                car_loc: Point3::new(0.0, 0.0, 17.01),
+                car_rot: Rotation3::from_unreal_angles(0.0, PI / 2.0, 0.0),
+                ..Default::default()
+            })
+            .behavior(FollowRoute::new(GroundReverse::new(target_loc)))
+            .run_for_millis(1500);
+
+        let packet = test.sniff_packet();
+        let me = &packet.GameCars[0];
+        let loc = me.Physics.loc_2d();
+        assert!(loc.x > 100.0);
+        assert!(loc.y < -600.0);
+        // We backed up, so we're still facing (more or less) the same way.
+        assert!(me.Physics.forward_axis_2d().y > 0.5);
+    }
+}
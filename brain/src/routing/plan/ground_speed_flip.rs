@@ -0,0 +1,62 @@
+use crate::routing::{
+    models::{PlanningContext, PlanningDump, RoutePlan, RoutePlanError, RoutePlanner},
+    recover::{IsSkidding, NotFacingTarget2D, NotOnFlatGround},
+    segments::SpeedFlip,
+};
+use common::prelude::*;
+use derive_new::new;
+use nalgebra::Point2;
+use nameof::name_of_type;
+use simulate::CarSpeedFlip;
+
+/// Get up to speed in a hurry with a speed flip towards `target_loc`.
+#[derive(Clone, new)]
+pub struct GroundSpeedFlip {
+    target_loc: Point2<f32>,
+}
+
+impl RoutePlanner for GroundSpeedFlip {
+    fn name(&self) -> &'static str {
+        name_of_type!(GroundSpeedFlip)
+    }
+
+    fn plan(
+        &self,
+        ctx: &PlanningContext<'_, '_>,
+        dump: &mut PlanningDump<'_>,
+    ) -> Result<RoutePlan, RoutePlanError> {
+        dump.log_start(self, &ctx.start);
+        dump.log_pretty(self, "target_loc", self.target_loc);
+
+        guard!(
+            ctx.start,
+            NotOnFlatGround,
+            RoutePlanError::MustBeOnFlatGround,
+        );
+        guard!(ctx.start, IsSkidding, RoutePlanError::MustNotBeSkidding {
+            recover_target_loc: self.target_loc,
+        });
+        guard!(
+            ctx.start,
+            NotFacingTarget2D::new(self.target_loc),
+            RoutePlanError::MustBeFacingTarget,
+        );
+
+        let forward_axis = ctx.start.forward_axis_2d();
+        let to_target = self.target_loc - ctx.start.loc_2d();
+        let speed = ctx.start.vel.to_2d().dot(&forward_axis.into_inner());
+        let flip = CarSpeedFlip::calc_1d(speed);
+        if flip.boost_used > ctx.start.boost {
+            return Err(RoutePlanError::OtherError("not enough boost to speed flip"));
+        }
+        if flip.end_dist > to_target.norm() {
+            return Err(RoutePlanError::OtherError("speed flip would overshoot"));
+        }
+
+        let dodge_right = forward_axis.angle_to(&to_target) >= 0.0;
+        Ok(RoutePlan {
+            segment: Box::new(SpeedFlip::new(ctx.start.clone(), flip, dodge_right)),
+            next: None,
+        })
+    }
+}
@@ -5,7 +5,8 @@ use crate::{
             RoutePlanner,
         },
         plan::{
-            ground_powerslide::GroundSimplePowerslideTurn, higher_order::ChainedPlanner, pathing,
+            ground_half_flip::GroundHalfFlip, ground_powerslide::GroundSimplePowerslideTurn,
+            higher_order::ChainedPlanner, pathing,
         },
         recover::{IsSkidding, NotOnFlatGround},
         segments::{NullSegment, SimpleArc, Turn},
@@ -93,7 +94,15 @@ impl RoutePlanner for PathingUnawareTurnPlanner {
         dump.log_start(self, &ctx.start);
         dump.log_pretty(self, "target_face", self.target_face);
 
-        if self.should_powerslide(&ctx.start) {
+        if GroundHalfFlip::applicable(&ctx.start, self.target_face) {
+            // The half-flip only gets us facing roughly backwards, so clean up
+            // whatever angle is left afterwards.
+            ChainedPlanner::chain(vec_box![
+                GroundHalfFlip::new(self.target_face),
+                SimpleTurnPlanner::new(self.target_face, None),
+            ])
+            .plan(ctx, dump)
+        } else if self.should_powerslide(&ctx.start) {
             self.powerslide_with_angle_hint_hack(ctx, dump)
         } else {
             SimpleTurnPlanner::new(self.target_face, None).plan(ctx, dump)
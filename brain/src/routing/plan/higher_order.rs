@@ -69,7 +69,7 @@ impl ChainedPlanner {
     pub fn chain(planners: Vec<Box<dyn RoutePlanner>>) -> Self {
         let mut iter = planners.into_iter();
         let first = iter.next().unwrap();
-        let mut result = Self::new(first, Some(iter.next().unwrap()));
+        let mut result = Self::new(first, iter.next());
         for planner in iter {
             result = Self::new(Box::new(result), Some(planner));
         }
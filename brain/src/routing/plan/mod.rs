@@ -1,7 +1,20 @@
 pub use self::{
-    boost::GetDollar, ground_drive::GroundDrive, ground_intercept::GroundIntercept,
-    ground_straight::GroundStraightPlanner, ground_turn::TurnPlanner, higher_order::ChainedPlanner,
-    pathing::avoid_goal_wall_waypoint, wall_intercept::WallIntercept,
+    avoid_cars::AvoidCars,
+    boost::GetDollar,
+    ground_arrive::GroundArrive,
+    ground_drive::GroundDrive,
+    ground_half_flip::GroundHalfFlip,
+    ground_intercept::GroundIntercept,
+    ground_reverse::GroundReverse,
+    ground_speed_flip::GroundSpeedFlip,
+    ground_straight::GroundStraightPlanner,
+    ground_turn::TurnPlanner,
+    higher_order::ChainedPlanner,
+    pathing::avoid_goal_wall_waypoint,
+    search::SearchPlanner,
+    wall_intercept::WallIntercept,
+    wall_to_ceiling::WallToCeilingPlanner,
+    wall_transition::{GroundToWall, ViaWall, WallToGround},
 };
 
 macro_rules! guard {
@@ -12,16 +25,24 @@ macro_rules! guard {
     };
 }
 
+mod avoid_cars;
 mod boost;
+mod ground_arrive;
 mod ground_drive;
+mod ground_half_flip;
 mod ground_intercept;
 mod ground_jump_and_dodge;
 mod ground_powerslide;
+mod ground_reverse;
+mod ground_speed_flip;
 mod ground_straight;
 mod ground_turn;
 mod higher_order;
 mod pathing;
+mod search;
 mod wall_intercept;
 mod wall_straight;
+mod wall_to_ceiling;
+mod wall_transition;
 mod wall_turn;
 mod wall_utils;
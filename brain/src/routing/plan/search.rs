@@ -0,0 +1,394 @@
+use crate::routing::{
+    models::{
+        CarState, PlanningContext, PlanningDump, RoutePlan, RoutePlanError, RoutePlanner,
+        SegmentPlan,
+    },
+    plan::{
+        ground_half_flip::GroundHalfFlip, ground_powerslide::GroundSimplePowerslideTurn,
+        ground_reverse::GroundReverse, ground_straight::GroundStraightPlanner,
+        ground_turn::TurnPlanner, higher_order::StaticPlanner, wall_straight::WallStraightPlanner,
+        wall_turn::WallTurnPlanner,
+    },
+    recover::{NotFacingTarget2D, NotOnFlatGround},
+    segments::{Brake, StraightMode},
+};
+use common::{prelude::*, rl};
+use nalgebra::{Point2, Point3, Vector2};
+use nameof::name_of_type;
+use ordered_float::NotNan;
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashSet},
+    f32::consts::PI,
+};
+
+/// Search over sequences of segments for the quickest way to reach a target,
+/// instead of following a fixed recipe the way `ChainedPlanner` does.
+///
+/// This is a best-first (A*) search. Each step tries the existing one-segment
+/// planners from the current state, so every segment it returns has passed
+/// the same checks as it would anywhere else.
+#[derive(Clone)]
+pub struct SearchPlanner {
+    target_loc: Point3<f32>,
+    /// If set, the car should be facing this point when it arrives.
+    target_face: Option<Point2<f32>>,
+    /// If set, discard routes that take longer than this.
+    deadline: Option<f32>,
+    max_expansions: usize,
+}
+
+impl SearchPlanner {
+    /// Give up after considering this many states.
+    const MAX_EXPANSIONS: usize = 200;
+    const MAX_SEGMENTS: usize = 5;
+    /// Close enough to count as arrived.
+    const ARRIVE_DISTANCE: f32 = 50.0;
+    const FACE_TOLERANCE: f32 = PI / 12.0;
+    /// The spacing of intermediate stops on a long straightaway, which leave
+    /// room to slow down or line up before the end.
+    const STRAIGHT_STEP: f32 = 1000.0;
+    /// How far back from the target to line up when `target_face` is set.
+    const APPROACH_DISTANCE: f32 = 1000.0;
+    /// Don't bother braking below this speed.
+    const MIN_BRAKE_SPEED: f32 = 500.0;
+    /// Lattice resolution, for recognizing states we've already expanded.
+    const CELL_SIZE: f32 = 100.0;
+    const HEADING_BUCKETS: f32 = 16.0;
+    const SPEED_BUCKET: f32 = 250.0;
+
+    pub fn new(target_loc: Point3<f32>) -> Self {
+        Self {
+            target_loc,
+            target_face: None,
+            deadline: None,
+            max_expansions: Self::MAX_EXPANSIONS,
+        }
+    }
+
+    pub fn target_face(mut self, target_face: Point2<f32>) -> Self {
+        self.target_face = Some(target_face);
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn deadline(mut self, deadline: f32) -> Self {
+        assert!(deadline >= 0.0);
+        self.deadline = Some(deadline);
+        self
+    }
+}
+
+impl RoutePlanner for SearchPlanner {
+    fn name(&self) -> &'static str {
+        name_of_type!(SearchPlanner)
+    }
+
+    fn plan(
+        &self,
+        ctx: &PlanningContext<'_, '_>,
+        dump: &mut PlanningDump<'_>,
+    ) -> Result<RoutePlan, RoutePlanError> {
+        dump.log_start(self, &ctx.start);
+        dump.log_pretty(self, "target_loc", self.target_loc);
+
+        let mut open = BinaryHeap::new();
+        open.push(Node {
+            cost: self.cost(0.0, &ctx.start),
+            time: 0.0,
+            state: ctx.start.clone(),
+            segments: Vec::new(),
+        });
+        let mut visited = HashSet::new();
+        let mut first_error = None;
+        let mut expansions = 0;
+
+        while let Some(node) = open.pop() {
+            if !node.segments.is_empty() && self.arrived(&node.state) {
+                dump.log(
+                    self,
+                    format!(
+                        "found a {}-segment route after {} expansions",
+                        node.segments.len(),
+                        expansions,
+                    ),
+                );
+                return Ok(Self::chain(node.segments));
+            }
+            if expansions >= self.max_expansions {
+                break;
+            }
+            if node.segments.len() >= Self::MAX_SEGMENTS {
+                continue;
+            }
+            if !visited.insert(Self::lattice_key(&node.state)) {
+                continue;
+            }
+            expansions += 1;
+
+            for successor in self.successors(ctx, &node.state) {
+                let segment = match successor {
+                    Ok(segment) => segment,
+                    Err(error) => {
+                        // Errors from the very first step are the ones a caller can
+                        // act on (e.g. by recovering from a skid).
+                        if node.segments.is_empty() && first_error.is_none() {
+                            first_error = Some(error);
+                        }
+                        continue;
+                    }
+                };
+
+                let time = node.time + segment.duration();
+                if self.deadline.map(|d| time > d).unwrap_or_default() {
+                    continue;
+                }
+                let state = segment.end();
+                if state.loc.x.is_nan() || state.vel.x.is_nan() {
+                    continue;
+                }
+
+                let mut segments = node.segments.clone();
+                segments.push(segment);
+                open.push(Node {
+                    cost: self.cost(time, &state),
+                    time,
+                    state,
+                    segments,
+                });
+            }
+        }
+
+        dump.log(self, format!("no route after {} expansions", expansions));
+        Err(first_error.unwrap_or(RoutePlanError::OtherError("search found no route")))
+    }
+}
+
+impl SearchPlanner {
+    /// Every segment we could take next from `state`.
+    fn successors(
+        &self,
+        ctx: &PlanningContext<'_, '_>,
+        state: &CarState,
+    ) -> Vec<Result<Box<dyn SegmentPlan>, RoutePlanError>> {
+        // The segment planners don't look at the ball, so there's no need to
+        // slice the prediction to match the time spent so far.
+        let child_ctx = PlanningContext {
+            game: ctx.game,
+            start: state.clone(),
+            ball_prediction: ctx.ball_prediction,
+        };
+        let mut log = Vec::new();
+        let mut dump = PlanningDump { log: &mut log };
+
+        let mut result: Vec<_> = self
+            .primitives(state)
+            .into_iter()
+            .map(|planner| planner.plan(&child_ctx, &mut dump).map(|plan| plan.segment))
+            .collect();
+
+        let speed = state.vel.norm();
+        if !NotOnFlatGround.evaluate(state) && speed >= Self::MIN_BRAKE_SPEED {
+            let brake = Brake::new(state.to_2d_assume(), speed / 2.0);
+            result.push(Ok(Box::new(brake)));
+        }
+        result
+    }
+
+    /// The planners to try from `state`. Each one contributes (at most) its
+    /// first segment.
+    fn primitives(&self, state: &CarState) -> Vec<Box<dyn RoutePlanner>> {
+        let mut result: Vec<Box<dyn RoutePlanner>> = Vec::new();
+
+        if NotOnFlatGround.evaluate(state) {
+            result.push(Box::new(WallTurnPlanner::new(self.target_loc)));
+            result.push(Box::new(WallStraightPlanner::new(self.target_loc)));
+            return result;
+        }
+
+        let mut waypoints = vec![self.target_loc.to_2d()];
+        if let Some(target_face) = self.target_face {
+            let target_loc = self.target_loc.to_2d();
+            let approach = (target_loc - target_face).normalize() * Self::APPROACH_DISTANCE;
+            waypoints.push(target_loc + approach);
+        }
+
+        for waypoint in waypoints {
+            if NotFacingTarget2D::new(waypoint).evaluate(state) {
+                result.push(Box::new(TurnPlanner::new(waypoint, None)));
+                result.push(Box::new(GroundSimplePowerslideTurn::new(waypoint)));
+                if GroundHalfFlip::applicable(state, waypoint) {
+                    result.push(Box::new(GroundHalfFlip::new(waypoint)));
+                }
+                if GroundReverse::applicable(state, waypoint) {
+                    result.push(Box::new(GroundReverse::new(waypoint)));
+                }
+                continue;
+            }
+
+            let straight = GroundStraightPlanner::new(waypoint, StraightMode::Asap);
+            result.push(Box::new(straight.clone().allow_dodging(false)));
+            result.push(Box::new(straight));
+
+            let to_waypoint = waypoint - state.loc_2d();
+            if to_waypoint.norm() >= Self::STRAIGHT_STEP * 1.5 {
+                let step = state.loc_2d() + to_waypoint.normalize() * Self::STRAIGHT_STEP;
+                let straight = GroundStraightPlanner::new(step, StraightMode::Asap);
+                result.push(Box::new(straight.allow_dodging(false)));
+            }
+        }
+        result
+    }
+
+    fn arrived(&self, state: &CarState) -> bool {
+        // Grounded targets are given at ball height or whatever, so only
+        // compare the horizontal distance.
+        let distance = if NotOnFlatGround.evaluate(state) {
+            (state.loc - self.target_loc).norm()
+        } else {
+            (state.loc_2d() - self.target_loc.to_2d()).norm()
+        };
+        if distance >= Self::ARRIVE_DISTANCE {
+            return false;
+        }
+
+        match self.target_face {
+            Some(target_face) => {
+                let to_face = target_face - state.loc_2d();
+                state.forward_axis_2d().angle_to(&to_face).abs() < Self::FACE_TOLERANCE
+            }
+            None => true,
+        }
+    }
+
+    /// Elapsed time plus an optimistic estimate of the time remaining.
+    fn cost(&self, time: f32, state: &CarState) -> NotNan<f32> {
+        let distance = (state.loc - self.target_loc).norm();
+        NotNan::new(time + distance / rl::CAR_MAX_SPEED).unwrap()
+    }
+
+    fn lattice_key(state: &CarState) -> (i32, i32, i32, i32, i32) {
+        let heading = state.forward_axis_2d().angle_to(&Vector2::x());
+        (
+            (state.loc.x / Self::CELL_SIZE).round() as i32,
+            (state.loc.y / Self::CELL_SIZE).round() as i32,
+            (state.loc.z / Self::CELL_SIZE).round() as i32,
+            (heading / (2.0 * PI) * Self::HEADING_BUCKETS).round() as i32,
+            (state.vel.norm() / Self::SPEED_BUCKET).round() as i32,
+        )
+    }
+
+    /// Turn a list of segments into a plan that runs them in order.
+    fn chain(segments: Vec<Box<dyn SegmentPlan>>) -> RoutePlan {
+        let mut segments = segments.into_iter().rev();
+        let last = segments.next().unwrap();
+        segments.fold(
+            RoutePlan {
+                segment: last,
+                next: None,
+            },
+            |next, segment| RoutePlan {
+                segment,
+                next: Some(Box::new(StaticPlanner::new(next))),
+            },
+        )
+    }
+}
+
+struct Node {
+    /// Time spent so far plus the heuristic estimate of time remaining.
+    cost: NotNan<f32>,
+    time: f32,
+    state: CarState,
+    segments: Vec<Box<dyn SegmentPlan>>,
+}
+
+impl PartialEq for Node {
+    fn eq(&self, other: &Self) -> bool {
+        self.cost == other.cost
+    }
+}
+
+impl Eq for Node {}
+
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Node {
+    /// `BinaryHeap` is a max-heap, so reverse the order to pop the cheapest
+    /// node first.
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.cmp(&self.cost)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::routing::{
+        models::{CarState, PlanningContext},
+        plan::search::SearchPlanner,
+        test::with_offline_planning_context,
+    };
+    use common::{prelude::*, rl};
+    use nalgebra::{Point2, Point3, UnitComplex, Vector3};
+
+    /// Parked at center field, facing sideways, with the target up the field.
+    /// That takes at least a turn and then a straight.
+    fn start() -> CarState {
+        CarState {
+            loc: Point3::new(0.0, 0.0, rl::OCTANE_NEUTRAL_Z),
+            rot: UnitComplex::identity().around_z_axis(),
+            vel: Vector3::zeros(),
+            boost: 50.0,
+        }
+    }
+
+    fn target_loc() -> Point3<f32> {
+        Point3::new(0.0, 3000.0, rl::OCTANE_NEUTRAL_Z)
+    }
+
+    fn ball_loc() -> Point3<f32> {
+        Point3::new(-3000.0, -3000.0, rl::BALL_RADIUS)
+    }
+
+    #[test]
+    fn finds_multi_segment_route() {
+        let planner = SearchPlanner::new(target_loc()).target_face(Point2::new(0.0, 5000.0));
+        with_offline_planning_context(&start(), ball_loc(), Vector3::zeros(), |ctx| {
+            let (plan, _log) = PlanningContext::plan_2(&planner, ctx)
+                .unwrap_or_else(|err| panic!("{:?}", err.error));
+            let tail = plan
+                .provisional_expand_2(ctx.game, ctx.ball_prediction)
+                .unwrap_or_else(|err| panic!("{:?}", err.error));
+            let segments: Vec<_> = tail.iter().map(|(_, s)| s).collect();
+            assert!(!segments.is_empty());
+
+            let end = segments.last().unwrap().end();
+            let to_target = target_loc().to_2d() - end.loc_2d();
+            assert!(to_target.norm() < SearchPlanner::ARRIVE_DISTANCE);
+            let to_face = Point2::new(0.0, 5000.0) - end.loc_2d();
+            assert!(end.forward_axis_2d().angle_to(&to_face).abs() < SearchPlanner::FACE_TOLERANCE);
+        });
+    }
+
+    #[test]
+    fn gives_up_after_max_expansions() {
+        let planner = SearchPlanner {
+            max_expansions: 1,
+            ..SearchPlanner::new(target_loc())
+        };
+        with_offline_planning_context(&start(), ball_loc(), Vector3::zeros(), |ctx| {
+            let err = match PlanningContext::plan_2(&planner, ctx) {
+                Ok(_) => panic!("found a route without expanding past the start"),
+                Err(err) => err,
+            };
+            assert!(err
+                .log
+                .iter()
+                .any(|line| line.ends_with("no route after 1 expansions")));
+        });
+    }
+}
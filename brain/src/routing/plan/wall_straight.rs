@@ -1,7 +1,7 @@
 use crate::{
     routing::{
         models::{PlanningContext, PlanningDump, RoutePlan, RoutePlanError, RoutePlanner},
-        plan::wall_utils::which_surface,
+        plan::wall_utils::{is_on_surface, which_surface},
         segments::WallStraight,
     },
     utils::geometry::flattener::Flattener,
@@ -29,6 +29,11 @@ impl RoutePlanner for WallStraightPlanner {
 
         let start_surface = which_surface(ctx.game.pitch(), &ctx.start.loc)
             .map_err(|_| RoutePlanError::CannotOperateWall)?;
+        // The straight is flattened onto the surface, so the car has to be driving on
+        // it.
+        if !is_on_surface(start_surface, &ctx.start) {
+            return Err(RoutePlanError::CannotOperateWall);
+        }
         let target_surface = which_surface(ctx.game.pitch(), &self.target_loc)
             .map_err(|_| RoutePlanError::CannotOperateWall)?;
         let target_to_start = target_surface
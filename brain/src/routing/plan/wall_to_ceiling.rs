@@ -0,0 +1,65 @@
+use crate::routing::{
+    models::{PlanningContext, PlanningDump, RoutePlan, RoutePlanError, RoutePlanner},
+    plan::{ground_turn::SimpleTurnPlanner, higher_order::ChainedPlanner},
+    recover::{IsSkidding, NotFacingTarget2D},
+    segments::{CeilingChart, WallToCeiling},
+};
+use derive_new::new;
+use nalgebra::Point3;
+use nameof::name_of_type;
+use vec_box::vec_box;
+
+/// Drive up the nearest side wall to `target_loc` on the ceiling.
+#[derive(Clone, new)]
+pub struct WallToCeilingPlanner {
+    target_loc: Point3<f32>,
+}
+
+impl RoutePlanner for WallToCeilingPlanner {
+    fn name(&self) -> &'static str {
+        name_of_type!(WallToCeilingPlanner)
+    }
+
+    fn plan(
+        &self,
+        ctx: &PlanningContext<'_, '_>,
+        dump: &mut PlanningDump<'_>,
+    ) -> Result<RoutePlan, RoutePlanError> {
+        dump.log_start(self, &ctx.start);
+        dump.log_pretty(self, "target_loc", self.target_loc);
+
+        let pitch = ctx.game.pitch();
+        if *pitch.closest_plane(&self.target_loc) != *pitch.ceiling() {
+            return Err(RoutePlanError::OtherError("target is not on the ceiling"));
+        }
+        let chart = CeilingChart::new(pitch, self.target_loc.x)
+            .map_err(|_| RoutePlanError::CannotOperateWall)?;
+        let flattener = chart
+            .flattener(pitch, &ctx.start.loc)
+            .ok_or(RoutePlanError::CannotOperateWall)?;
+        let flat_start = ctx.start.flatten(&flattener);
+        let flat_target_loc = chart.flatten_ceiling(self.target_loc);
+
+        guard!(ctx.start, IsSkidding, RoutePlanError::MustNotBeSkidding {
+            recover_target_loc: flat_target_loc,
+        });
+
+        // Starting from the floor, turn to face the wall first. The floor is
+        // unflattened in the chart, so the flat target works as a 2D target.
+        let on_floor = *pitch.closest_plane(&ctx.start.loc) == *pitch.ground();
+        if on_floor && NotFacingTarget2D::new(flat_target_loc).evaluate(&ctx.start) {
+            dump.log(self, "turning to face the wall");
+            return ChainedPlanner::chain(vec_box![
+                SimpleTurnPlanner::new(flat_target_loc, None),
+                self.clone(),
+            ])
+            .plan(ctx, dump);
+        }
+
+        let segment = WallToCeiling::new(ctx.start.clone(), &flat_start, self.target_loc, chart);
+        Ok(RoutePlan {
+            segment: Box::new(segment),
+            next: None,
+        })
+    }
+}
@@ -0,0 +1,220 @@
+use crate::{
+    routing::{
+        models::{PlanningContext, PlanningDump, RoutePlan, RoutePlanError, RoutePlanner},
+        plan::{
+            ground_turn::SimpleTurnPlanner, higher_order::ChainedPlanner,
+            wall_turn::WallTurnPlanner, wall_utils::which_surface,
+        },
+        recover::{IsSkidding, NotFacingTarget2D, NotOnFlatGround},
+        segments::WallTransition,
+    },
+    utils::geometry::flattener::Flattener,
+};
+use common::{prelude::*, rl};
+use derive_new::new;
+use nalgebra::{Point2, Point3};
+use nameof::name_of_type;
+use std::f32::consts::PI;
+use vec_box::vec_box;
+
+/// Drive from the floor up onto the wall `target_loc` is on, and stop at
+/// `target_loc`.
+#[derive(Clone, new)]
+pub struct GroundToWall {
+    target_loc: Point3<f32>,
+}
+
+impl RoutePlanner for GroundToWall {
+    fn name(&self) -> &'static str {
+        name_of_type!(GroundToWall)
+    }
+
+    fn plan(
+        &self,
+        ctx: &PlanningContext<'_, '_>,
+        dump: &mut PlanningDump<'_>,
+    ) -> Result<RoutePlan, RoutePlanError> {
+        dump.log_start(self, &ctx.start);
+        dump.log_pretty(self, "target_loc", self.target_loc);
+
+        let pitch = ctx.game.pitch();
+        let ground = *pitch.ground();
+        let wall = *pitch.closest_plane(&self.target_loc);
+        if wall.normal.z != 0.0 {
+            return Err(RoutePlanError::OtherError("target is not on a wall"));
+        }
+        let wall_to_flat = wall
+            .unfold(&ground)
+            .map_err(|_| RoutePlanError::CannotOperateWall)?;
+        let flat_target_loc = Flattener::new(wall_to_flat) * self.target_loc;
+
+        guard!(
+            ctx.start,
+            NotOnFlatGround,
+            RoutePlanError::MustBeOnFlatGround
+        );
+        guard!(ctx.start, IsSkidding, RoutePlanError::MustNotBeSkidding {
+            recover_target_loc: flat_target_loc,
+        });
+
+        // The floor is unflattened in the chart, so the flat target works as a
+        // 2D target for turning.
+        if NotFacingTarget2D::new(flat_target_loc).evaluate(&ctx.start) {
+            dump.log(self, "turning to face the wall");
+            return ChainedPlanner::chain(vec_box![
+                SimpleTurnPlanner::new(flat_target_loc, None),
+                self.clone(),
+            ])
+            .plan(ctx, dump);
+        }
+
+        let segment = WallTransition::new(ctx.start.clone(), self.target_loc, ground, wall)
+            .map_err(|_| RoutePlanError::OtherError("can't drive onto the wall from here"))?;
+        Ok(RoutePlan {
+            segment: Box::new(segment),
+            next: None,
+        })
+    }
+}
+
+/// Drive from a wall down onto the floor, and stop at `target_loc`.
+#[derive(Clone)]
+pub struct WallToGround {
+    target_loc: Point2<f32>,
+    allow_turn: bool,
+}
+
+impl WallToGround {
+    pub fn new(target_loc: Point2<f32>) -> Self {
+        Self {
+            target_loc,
+            allow_turn: true,
+        }
+    }
+}
+
+impl RoutePlanner for WallToGround {
+    fn name(&self) -> &'static str {
+        name_of_type!(WallToGround)
+    }
+
+    fn plan(
+        &self,
+        ctx: &PlanningContext<'_, '_>,
+        dump: &mut PlanningDump<'_>,
+    ) -> Result<RoutePlan, RoutePlanError> {
+        dump.log_start(self, &ctx.start);
+        dump.log_pretty(self, "target_loc", self.target_loc);
+
+        let pitch = ctx.game.pitch();
+        let ground = *pitch.ground();
+        let wall =
+            *which_surface(pitch, &ctx.start.loc).map_err(|_| RoutePlanError::CannotOperateWall)?;
+        if wall.normal.z != 0.0 {
+            return Err(RoutePlanError::OtherError("must start on a wall"));
+        }
+
+        let target_loc = self.target_loc.to_3d(rl::OCTANE_NEUTRAL_Z);
+        match WallTransition::new(ctx.start.clone(), target_loc, ground, wall) {
+            Ok(segment) => Ok(RoutePlan {
+                segment: Box::new(segment),
+                next: None,
+            }),
+            // Most likely we're pointed up or along the wall. Turn to face the
+            // target, then try once more.
+            Err(()) if self.allow_turn => {
+                dump.log(self, "turning to face the floor");
+                ChainedPlanner::chain(vec_box![
+                    WallTurnPlanner::new(target_loc).maximum_turn_angle(PI),
+                    Self {
+                        allow_turn: false,
+                        ..self.clone()
+                    },
+                ])
+                .plan(ctx, dump)
+            }
+            Err(()) => Err(RoutePlanError::OtherError(
+                "can't drive down to the floor from here",
+            )),
+        }
+    }
+}
+
+/// Drive up onto the wall at `wall_loc`, then back down to `target_loc` on the
+/// floor.
+#[derive(Clone, new)]
+pub struct ViaWall {
+    wall_loc: Point3<f32>,
+    target_loc: Point2<f32>,
+}
+
+impl RoutePlanner for ViaWall {
+    fn name(&self) -> &'static str {
+        name_of_type!(ViaWall)
+    }
+
+    fn plan(
+        &self,
+        ctx: &PlanningContext<'_, '_>,
+        dump: &mut PlanningDump<'_>,
+    ) -> Result<RoutePlan, RoutePlanError> {
+        dump.log_start(self, &ctx.start);
+        ChainedPlanner::chain(vec_box![
+            GroundToWall::new(self.wall_loc),
+            WallToGround::new(self.target_loc),
+        ])
+        .plan(ctx, dump)
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        integration_tests::{TestRunner, TestScenario},
+        routing::{
+            behavior::FollowRoute,
+            plan::wall_transition::{GroundToWall, ViaWall},
+        },
+    };
+    use common::{prelude::*, rl};
+    use nalgebra::{Point2, Point3, Rotation3, Vector3};
+
+    #[test]
+    fn drive_up_side_wall() {
+        let target_loc = Point3::new(rl::FIELD_MAX_X, 1000.0, 800.0);
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                car_loc: Point3::new(2500.0, 0.0, 17.01),
+                car_rot: Rotation3::from_unreal_angles(0.0, 0.5, 0.0),
+                car_vel: Vector3::new(800.0, 400.0, 0.0),
+                ..Default::default()
+            })
+            .behavior(FollowRoute::new(GroundToWall::new(target_loc)))
+            .run_for_millis(2500);
+
+        let packet = test.sniff_packet();
+        let loc = packet.GameCars[0].Physics.loc();
+        assert!(loc.x > rl::FIELD_MAX_X - 100.0);
+        assert!(loc.z > 500.0);
+    }
+
+    #[test]
+    fn up_and_back_down() {
+        let wall_loc = Point3::new(rl::FIELD_MAX_X, 0.0, 600.0);
+        let target_loc = Point2::new(2500.0, 1500.0);
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                car_loc: Point3::new(2500.0, -1500.0, 17.01),
+                car_rot: Rotation3::from_unreal_angles(0.0, 0.8, 0.0),
+                car_vel: Vector3::new(700.0, 700.0, 0.0),
+                ..Default::default()
+            })
+            .behavior(FollowRoute::new(ViaWall::new(wall_loc, target_loc)))
+            .run_for_millis(5000);
+
+        let packet = test.sniff_packet();
+        let loc = packet.GameCars[0].Physics.loc();
+        assert!(loc.z < 50.0);
+        assert!((loc.to_2d() - target_loc).norm() < 500.0);
+    }
+}
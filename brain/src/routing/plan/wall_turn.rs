@@ -1,7 +1,10 @@
 use crate::{
     routing::{
         models::{PlanningContext, PlanningDump, RoutePlan, RoutePlanError, RoutePlanner},
-        plan::{ground_turn::calculate_circle_turn, wall_utils::which_surface},
+        plan::{
+            ground_turn::calculate_circle_turn,
+            wall_utils::{is_on_surface, which_surface},
+        },
         segments::{NullSegment, WallTurn},
     },
     utils::geometry::flattener::Flattener,
@@ -46,6 +49,10 @@ impl RoutePlanner for WallTurnPlanner {
 
         let start_surface = which_surface(ctx.game.pitch(), &ctx.start.loc)
             .map_err(|_| RoutePlanError::CannotOperateWall)?;
+        // The turn is flattened onto the surface, so the car has to be driving on it.
+        if !is_on_surface(start_surface, &ctx.start) {
+            return Err(RoutePlanError::CannotOperateWall);
+        }
         let target_surface = which_surface(ctx.game.pitch(), &self.target_loc)
             .map_err(|_| RoutePlanError::CannotOperateWall)?;
         let target_to_start = target_surface
@@ -1,7 +1,12 @@
-use crate::{strategy::Pitch, utils::geometry::Plane};
+use crate::{routing::models::CarState, strategy::Pitch, utils::geometry::Plane};
 use nalgebra::Point3;
 
 const SURFACE_DIST_THRESHOLD: f32 = 500.0;
+/// How far the car's center can be from a surface and still count as driving
+/// on it. At rest it sits `rl::OCTANE_NEUTRAL_Z` above it.
+const ON_SURFACE_DIST: f32 = 50.0;
+/// How closely the car's roof has to line up with the surface's normal.
+const ON_SURFACE_ALIGNMENT: f32 = 0.9;
 
 pub fn which_surface<'p>(pitch: &'p Pitch, loc: &Point3<f32>) -> Result<&'p Plane, ()> {
     let wall = pitch.closest_plane(loc);
@@ -10,3 +15,10 @@ pub fn which_surface<'p>(pitch: &'p Pitch, loc: &Point3<f32>) -> Result<&'p Plan
     }
     Ok(wall)
 }
+
+/// Whether the car is driving on `surface`, as opposed to flying past it or
+/// sitting on the seam with a neighboring surface.
+pub fn is_on_surface(surface: &Plane, state: &CarState) -> bool {
+    surface.distance_to_point(&state.loc).abs() < ON_SURFACE_DIST
+        && state.roof_axis().dot(&surface.normal) >= ON_SURFACE_ALIGNMENT
+}
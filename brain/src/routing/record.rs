@@ -0,0 +1,41 @@
+use crate::routing::models::{CarState, ProvisionalPlanExpansionTail, RoutePlanError, SegmentPlan};
+use collect::{RecordingCarState, RecordingPlanError, RecordingPlanSegment};
+
+/// Flatten a segment into the format `collect::PlanCollector` writes out.
+pub fn record_segment(planner: &str, segment: &dyn SegmentPlan) -> RecordingPlanSegment {
+    RecordingPlanSegment {
+        planner: planner.to_string(),
+        segment: segment.name().to_string(),
+        duration: segment.duration(),
+        start: record_car_state(&segment.start()),
+        end: record_car_state(&segment.end()),
+    }
+}
+
+/// The first segment of a plan, followed by everything the provisional
+/// expansion came up with.
+pub fn record_expansion(
+    planner: &str,
+    head: &dyn SegmentPlan,
+    tail: &ProvisionalPlanExpansionTail,
+) -> Vec<RecordingPlanSegment> {
+    let mut result = vec![record_segment(planner, head)];
+    result.extend(tail.iter().map(|(planner, s)| record_segment(planner, s)));
+    result
+}
+
+pub fn record_error(planner: &str, error: &RoutePlanError) -> RecordingPlanError {
+    RecordingPlanError {
+        planner: planner.to_string(),
+        error: format!("{:?}", error),
+    }
+}
+
+fn record_car_state(state: &CarState) -> RecordingCarState {
+    RecordingCarState {
+        loc: state.loc,
+        rot: state.rot,
+        vel: state.vel,
+        boost: state.boost,
+    }
+}
@@ -1,14 +1,16 @@
 use crate::{
     behavior::{
         higher_order::{Chain, Predicate, TimeLimit, TryChoose},
-        movement::{DriveTowards, GetToFlatGround, QuickJumpAndDodge, SkidRecover, Yielder},
+        movement::{
+            DriveTowards, GetToFlatGround, HalfFlip, QuickJumpAndDodge, SkidRecover, Yielder,
+        },
         offense::ResetBehindBall,
     },
     helpers::ball::BallTrajectory,
     routing::{
         behavior::FollowRoute,
         models::{CarState, RoutePlanError},
-        plan::GroundDrive,
+        plan::{GroundDrive, WallToGround},
         StraightMode,
     },
     strategy::{Behavior, Context, Priority, Scenario},
@@ -25,7 +27,12 @@ const SKIDDING_THRESHOLD: f32 = 0.95;
 impl RoutePlanError {
     pub fn recover(&self, ctx: &mut Context<'_>) -> Option<Box<dyn Behavior>> {
         match *self {
-            RoutePlanError::MustBeOnFlatGround => Some(Box::new(GetToFlatGround::new())),
+            RoutePlanError::MustBeOnFlatGround => {
+                if let Some(b) = check_drive_down_wall(ctx) {
+                    return Some(b);
+                }
+                Some(Box::new(GetToFlatGround::new()))
+            }
             RoutePlanError::MustNotBeSkidding { recover_target_loc } => {
                 Some(Box::new(SkidRecover::new(recover_target_loc)))
             }
@@ -41,7 +48,9 @@ impl RoutePlanError {
 
                 let ball_loc = ctx.scenario.ball_prediction().at_time_or_last(2.5).loc;
                 let mut choices = Vec::<Box<dyn Behavior>>::new();
-                if !is_ball_directly_behind_car(ctx.scenario.ball_prediction(), &ctx.me().into()) {
+                if is_ball_directly_behind_car(ctx.scenario.ball_prediction(), &ctx.me().into()) {
+                    choices.push(Box::new(HalfFlip::new()));
+                } else {
                     choices.push(Box::new(
                         FollowRoute::new(
                             GroundDrive::new(ball_loc.to_2d())
@@ -72,15 +81,46 @@ impl RoutePlanError {
                 }
                 None
             }
+            RoutePlanError::BlockedByCar { .. } => {
+                // There's no going through another car. Back off behind the
+                // ball and come at it again from a fresh angle.
+                let ball_loc = ctx.scenario.ball_prediction().at_time_or_last(2.5).loc;
+                Some(Box::new(
+                    ResetBehindBall::behind_loc(ball_loc.to_2d(), 1500.0).never_recover(true),
+                ))
+            }
             RoutePlanError::MovingTooFast
             | RoutePlanError::CannotOperateWall
             | RoutePlanError::NoWallIntercept
             | RoutePlanError::TurnAngleTooLarge
+            | RoutePlanError::CannotArriveInTime
             | RoutePlanError::OtherError(_) => None,
         }
     }
 }
 
+/// If we're driving on a wall, drive down it instead of jumping off.
+fn check_drive_down_wall(ctx: &mut Context<'_>) -> Option<Box<dyn Behavior>> {
+    let me = ctx.me();
+    if !me.OnGround {
+        return None;
+    }
+    let me_loc = me.Physics.loc();
+    let wall = ctx.game.pitch().closest_plane(&me_loc);
+    if wall.normal.z != 0.0 {
+        return None;
+    }
+
+    // Land as far out from the wall as we are up it, so the path down isn't
+    // too shallow.
+    let target_loc = me_loc.to_2d() + wall.normal.into_inner().to_2d() * (me_loc.z + 500.0);
+    ctx.eeg.log(stringify!(recover), "driving down the wall");
+    Some(Box::new(TryChoose::new(Priority::Idle, vec_box![
+        FollowRoute::new(WallToGround::new(target_loc)).never_recover(true),
+        GetToFlatGround::new(),
+    ])))
+}
+
 /// Check if the ball is roughly in front of us and we can easily just smack it
 /// for free.
 fn check_easy_flip_recover(ctx: &mut Context<'_>) -> Option<Box<dyn Behavior>> {
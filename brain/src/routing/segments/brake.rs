@@ -65,6 +65,30 @@ impl SegmentPlan for Brake {
         (start_speed - self.target_speed) * (1.0 / rl::CAR_MAX_SPEED)
     }
 
+    fn sample(&self, t: f32) -> CarState {
+        let duration = self.duration();
+        if duration <= 0.0 || self.start.vel.norm() < 1.0 {
+            return self.start.to_3d();
+        }
+
+        // Assume the speed drops linearly, and scale the distance so we land on
+        // the same spot as `end`.
+        let start_speed = self.start.vel.norm();
+        let frac = (t / duration).max(0.0).min(1.0);
+        let dist_frac = (start_speed * frac
+            - (start_speed - self.target_speed) * frac * frac / 2.0)
+            / ((start_speed + self.target_speed) / 2.0);
+        let end = self.end();
+        let speed = start_speed + (self.target_speed - start_speed) * frac;
+        CarState2D {
+            loc: self.start.loc + (end.loc.to_2d() - self.start.loc) * dist_frac,
+            rot: self.start.rot,
+            vel: self.start.vel.normalize() * speed,
+            boost: self.start.boost,
+        }
+        .to_3d()
+    }
+
     fn run(&self) -> Box<dyn SegmentRunner> {
         Box::new(Braker::new(self.clone()))
     }
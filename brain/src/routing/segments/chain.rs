@@ -28,6 +28,16 @@ impl SegmentPlan for Chain {
         self.segments.iter().map(|s| s.duration()).sum()
     }
 
+    fn sample(&self, mut t: f32) -> CarState {
+        for segment in &self.segments {
+            if t < segment.duration() {
+                return segment.sample(t);
+            }
+            t -= segment.duration();
+        }
+        self.end()
+    }
+
     fn run(&self) -> Box<dyn SegmentRunner> {
         Box::new(Chainer::new(
             self.segments.iter().map(|s| s.run()).collect(),
@@ -46,6 +46,23 @@ impl SegmentPlan for ForwardDodge {
         self.dodge.duration()
     }
 
+    fn sample(&self, t: f32) -> CarState {
+        // Same path as `end`: the start velocity until the dodge, then the dodge's
+        // boost along the car's nose on top of it.
+        let (dist, speed) = self.dodge.sample(t);
+        let forward_axis = self.start.forward_axis_2d().into_inner();
+        let end_vel =
+            self.start.vel.to_2d() + forward_axis * (self.dodge.end_speed - self.dodge.start_speed);
+        let vel = self.start.vel.to_2d() + forward_axis * (speed - self.dodge.start_speed);
+        CarState2D {
+            loc: self.start.loc.to_2d() + end_vel.normalize() * dist,
+            rot: self.start.rot.to_2d(),
+            vel,
+            boost: self.start.boost,
+        }
+        .to_3d()
+    }
+
     fn run(&self) -> Box<dyn SegmentRunner> {
         Box::new(ForwardDodgeRunner::new(self.clone()))
     }
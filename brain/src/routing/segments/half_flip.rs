@@ -0,0 +1,143 @@
+use crate::{
+    behavior::movement,
+    eeg::{color, Drawable},
+    routing::models::{CarState, CarState2D, SegmentPlan, SegmentRunAction, SegmentRunner},
+    strategy::{Action, Behavior, Context},
+};
+use common::prelude::*;
+use derive_new::new;
+use nalgebra::UnitComplex;
+use nameof::name_of_type;
+use simulate::CarHalfFlip1D;
+use std::f32::consts::PI;
+
+#[derive(Clone, new)]
+pub struct HalfFlip {
+    start: CarState,
+    flip: CarHalfFlip1D,
+    roll_right: bool,
+}
+
+impl SegmentPlan for HalfFlip {
+    fn name(&self) -> &str {
+        name_of_type!(HalfFlip)
+    }
+
+    fn start(&self) -> CarState {
+        self.start.clone()
+    }
+
+    fn end(&self) -> CarState {
+        let forward_axis = self.start.forward_axis_2d().into_inner();
+        CarState2D {
+            loc: self.start.loc.to_2d() + forward_axis * self.flip.end_dist,
+            rot: self.start.rot.to_2d() * UnitComplex::new(PI),
+            vel: forward_axis * self.flip.end_speed,
+            boost: self.start.boost,
+        }
+        .to_3d()
+    }
+
+    fn duration(&self) -> f32 {
+        self.flip.duration()
+    }
+
+    fn sample(&self, t: f32) -> CarState {
+        let (dist, speed) = self.flip.sample(t);
+        let forward_axis = self.start.forward_axis_2d().into_inner();
+        // Start from the actual velocity, sideways drift and all, and settle onto
+        // the line by the end.
+        let drift = self.start.vel.to_2d() - forward_axis * self.flip.start_speed;
+        let settle = 1.0 - (t / self.duration()).max(0.0).min(1.0);
+        CarState2D {
+            loc: self.start.loc.to_2d() + forward_axis * dist,
+            rot: self.start.rot.to_2d() * UnitComplex::new(PI * self.flip.turn_fraction(t)),
+            vel: forward_axis * speed + drift * settle,
+            boost: self.start.boost,
+        }
+        .to_3d()
+    }
+
+    fn run(&self) -> Box<dyn SegmentRunner> {
+        Box::new(HalfFlipRunner::new(self.roll_right))
+    }
+
+    fn draw(&self, ctx: &mut Context<'_>) {
+        ctx.eeg.draw(Drawable::Line(
+            self.start.loc.to_2d(),
+            self.end().loc.to_2d(),
+            color::GREEN,
+        ));
+    }
+}
+
+struct HalfFlipRunner {
+    behavior: movement::HalfFlip,
+}
+
+impl HalfFlipRunner {
+    pub fn new(roll_right: bool) -> Self {
+        let behavior = movement::HalfFlip::new().roll_right(roll_right);
+        Self { behavior }
+    }
+}
+
+impl SegmentRunner for HalfFlipRunner {
+    fn name(&self) -> &str {
+        name_of_type!(HalfFlipRunner)
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> SegmentRunAction {
+        match self.behavior.execute_old(ctx) {
+            Action::Yield(i) => SegmentRunAction::Yield(i),
+            Action::TailCall(_) => panic!("TailCall not yet supported in SegmentRunner"),
+            Action::RootCall(_) => SegmentRunAction::Failure,
+            Action::Return => SegmentRunAction::Success,
+            Action::Abort => SegmentRunAction::Failure,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::{Point2, Vector2};
+    use simulate::CarHalfFlip;
+
+    #[test]
+    fn turns_around() {
+        let start = CarState2D {
+            loc: Point2::origin(),
+            rot: UnitComplex::identity(),
+            vel: Vector2::zeros(),
+            boost: 0.0,
+        }
+        .to_3d();
+        let flip = CarHalfFlip::calc_1d(0.0);
+        let segment = HalfFlip::new(start, flip, true);
+        let end = segment.end();
+        assert!(end.loc.x < -300.0);
+        assert!(end.vel.x < 0.0);
+        assert!(end.forward_axis_2d().x < -0.99);
+    }
+
+    #[test]
+    fn sample_matches_endpoints() {
+        let start = CarState2D {
+            loc: Point2::origin(),
+            rot: UnitComplex::identity(),
+            vel: Vector2::zeros(),
+            boost: 0.0,
+        }
+        .to_3d();
+        let flip = CarHalfFlip::calc_1d(0.0);
+        let segment = HalfFlip::new(start, flip, true);
+        let end = segment.end();
+        assert_eq!(segment.sample(0.0).loc, segment.start().loc);
+        assert_eq!(segment.sample(segment.duration()).loc, end.loc);
+        // A half turn is the one case a quaternion slerp can't handle, so make
+        // sure the halfway point doesn't blow up.
+        let mid = segment.sample(segment.duration() / 2.0);
+        assert!(mid.loc.x < 0.0 && mid.loc.x > end.loc.x);
+    }
+}
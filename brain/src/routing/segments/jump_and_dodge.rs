@@ -52,6 +52,29 @@ impl SegmentPlan for JumpAndDodge {
         JUMP_TIME + WAIT_TIME + FLOAT_TIME
     }
 
+    fn sample(&self, t: f32) -> CarState {
+        let t = t.max(0.0).min(self.duration());
+        let start_vel = self.start.vel.to_2d();
+        let jump_time = t.min(JUMP_TIME + WAIT_TIME);
+        let float_time = t - jump_time;
+        let (loc, vel) = if float_time > 0.0 {
+            let dodge_vel = self.end().vel.to_2d();
+            (
+                self.start.loc.to_2d() + jump_time * start_vel + float_time * dodge_vel,
+                dodge_vel,
+            )
+        } else {
+            (self.start.loc.to_2d() + jump_time * start_vel, start_vel)
+        };
+        CarState2D {
+            loc,
+            rot: self.start.rot.to_2d(),
+            vel,
+            boost: self.start.boost,
+        }
+        .to_3d()
+    }
+
     fn run(&self) -> Box<dyn SegmentRunner> {
         Box::new(JumpAndDodgeRunner::new(self.clone()))
     }
@@ -2,24 +2,38 @@ pub use crate::routing::segments::{
     brake::Brake,
     chain::Chain,
     forward_dodge::ForwardDodge,
+    half_flip::HalfFlip,
     jump_and_dodge::JumpAndDodge,
     null::NullSegment,
     powerslide_turn::PowerslideTurn,
+    reverse_arc::ReverseArc,
+    reverse_straight::ReverseStraight,
     simple_arc::SimpleArc,
+    speed_flip::SpeedFlip,
     straight::{Straight, StraightMode},
+    timed_straight::TimedStraight,
     turn::Turn,
     wall_straight::WallStraight,
+    wall_to_ceiling::{CeilingChart, WallToCeiling},
+    wall_transition::WallTransition,
     wall_turn::WallTurn,
 };
 
 mod brake;
 mod chain;
 mod forward_dodge;
+mod half_flip;
 mod jump_and_dodge;
 mod null;
 mod powerslide_turn;
+mod reverse_arc;
+mod reverse_straight;
 mod simple_arc;
+mod speed_flip;
 mod straight;
+mod timed_straight;
 mod turn;
 mod wall_straight;
+mod wall_to_ceiling;
+mod wall_transition;
 mod wall_turn;
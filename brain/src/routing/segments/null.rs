@@ -27,6 +27,10 @@ impl SegmentPlan for NullSegment {
         0.0
     }
 
+    fn sample(&self, _t: f32) -> CarState {
+        self.start.clone()
+    }
+
     fn run(&self) -> Box<dyn SegmentRunner> {
         Box::new(NullSegmentRunner::new())
     }
@@ -52,6 +52,25 @@ impl SegmentPlan for PowerslideTurn {
         self.blueprint.duration
     }
 
+    fn sample(&self, t: f32) -> CarState {
+        let (loc, rot, vel) = self.blueprint.sample(t);
+        let frac = if self.duration() > 0.0 {
+            (t / self.duration()).max(0.0).min(1.0)
+        } else {
+            0.0
+        };
+        // Start from the actual velocity rather than the recorded one, and since
+        // `end` pretends we've recovered to a standstill, fade out towards that.
+        let drift = self.blueprint.start_vel - self.blueprint.sample(0.0).2;
+        CarState2D {
+            loc,
+            rot,
+            vel: (vel + drift * (1.0 - frac)) * (1.0 - frac),
+            boost: self.boost,
+        }
+        .to_3d()
+    }
+
     fn run(&self) -> Box<dyn SegmentRunner> {
         Box::new(PowerslideTurnRunner::new(self.clone()))
     }
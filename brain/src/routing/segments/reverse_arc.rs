@@ -0,0 +1,181 @@
+use crate::{
+    behavior::movement::{simple_reverse_steer_towards, GetToFlatGround},
+    eeg::{color, Drawable},
+    routing::{
+        models::{CarState, CarState2D, SegmentPlan, SegmentRunAction, SegmentRunner},
+        segments::reverse_straight::ReverseProfile,
+    },
+    strategy::Context,
+};
+use common::prelude::*;
+use nalgebra::{Point2, UnitComplex, Vector2};
+use nameof::name_of_type;
+use std::f32::consts::PI;
+
+/// Back up along a circle that starts tangent to the car.
+#[derive(Clone)]
+pub struct ReverseArc {
+    start: CarState2D,
+    center: Point2<f32>,
+    radius: f32,
+    sweep: f32,
+    profile: ReverseProfile,
+    duration: f32,
+}
+
+impl ReverseArc {
+    /// `center` must be somewhere along the car's side axis, so the circle is
+    /// tangent to the car. The arc ends at `end_loc`, which should be on the
+    /// circle.
+    pub fn new(start: CarState2D, center: Point2<f32>, end_loc: Point2<f32>) -> Self {
+        let radius = (start.loc - center).norm();
+
+        // Backing up, the radius sweeps towards the car's rear.
+        let backward_axis = -start.forward_axis().into_inner();
+        let direction = (start.loc - center).perp(&backward_axis).signum();
+        let sweep = (start.loc - center).angle_to(&(end_loc - center));
+        let sweep = if direction > 0.0 && sweep < 0.0 {
+            sweep + 2.0 * PI
+        } else if direction < 0.0 && sweep > 0.0 {
+            sweep - 2.0 * PI
+        } else {
+            sweep
+        };
+
+        let profile = ReverseProfile::new(&start);
+        let duration = profile.time_to_distance(radius * sweep.abs());
+        Self {
+            start,
+            center,
+            radius,
+            sweep,
+            profile,
+            duration,
+        }
+    }
+
+    /// The fastest the car goes on this arc, which limits how tightly it can
+    /// turn.
+    pub fn end_speed(&self) -> f32 {
+        self.profile.at(self.duration).1
+    }
+
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    /// The angle swept so far, traveling in this plan's direction.
+    fn swept(&self, loc: Point2<f32>) -> f32 {
+        let result = (self.start.loc - self.center).angle_to(&(loc - self.center));
+        let result = result * self.sweep.signum();
+        // If we're still rolling forwards, we'll be slightly behind the start.
+        if result < -PI / 2.0 {
+            result + 2.0 * PI
+        } else {
+            result
+        }
+    }
+}
+
+impl SegmentPlan for ReverseArc {
+    fn name(&self) -> &str {
+        name_of_type!(ReverseArc)
+    }
+
+    fn start(&self) -> CarState {
+        self.start.to_3d()
+    }
+
+    fn end(&self) -> CarState {
+        self.sample(self.duration)
+    }
+
+    fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    fn sample(&self, t: f32) -> CarState {
+        let t = t.max(0.0).min(self.duration);
+        let (distance, speed) = self.profile.at(t);
+        let sweep = UnitComplex::new(distance / self.radius * self.sweep.signum());
+        let backward_axis = -self.start.forward_axis().into_inner();
+        // Start from the actual velocity, and settle onto the arc by the end.
+        let drift = self.start.vel - backward_axis * self.profile.at(0.0).1;
+        let settle = if self.duration > 0.0 {
+            1.0 - t / self.duration
+        } else {
+            1.0
+        };
+        CarState2D {
+            loc: self.center + sweep * (self.start.loc - self.center),
+            rot: sweep * self.start.rot,
+            vel: sweep * (backward_axis * speed + drift * settle),
+            boost: self.start.boost,
+        }
+        .to_3d()
+    }
+
+    fn run(&self) -> Box<dyn SegmentRunner> {
+        Box::new(ReverseArcRunner::new(self.clone()))
+    }
+
+    fn draw(&self, ctx: &mut Context<'_>) {
+        let theta1 = Vector2::x().angle_to(&(self.start.loc - self.center));
+        let theta2 = theta1 + self.sweep;
+        ctx.eeg.draw(Drawable::Arc(
+            self.center,
+            self.radius,
+            theta1.min(theta2),
+            theta1.max(theta2),
+            color::ORANGE,
+        ));
+    }
+}
+
+struct ReverseArcRunner {
+    plan: ReverseArc,
+}
+
+impl ReverseArcRunner {
+    fn new(plan: ReverseArc) -> Self {
+        Self { plan }
+    }
+
+    fn calculate_ahead_loc(&self, loc: Point2<f32>, angle: f32) -> Point2<f32> {
+        let center_to_loc = loc - self.plan.center;
+        let center_to_ahead = UnitComplex::new(angle * self.plan.sweep.signum()) * center_to_loc;
+        self.plan.center + center_to_ahead.normalize() * self.plan.radius
+    }
+}
+
+impl SegmentRunner for ReverseArcRunner {
+    fn name(&self) -> &str {
+        name_of_type!(ReverseArcRunner)
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> SegmentRunAction {
+        let me = ctx.me();
+        let car_loc = me.Physics.loc_2d();
+
+        if !GetToFlatGround::on_flat_ground(me) {
+            ctx.eeg.log(self.name(), "not on flat ground");
+            return SegmentRunAction::Failure;
+        }
+
+        // Check if we're finished.
+        if self.plan.swept(car_loc) >= self.plan.sweep.abs() {
+            return SegmentRunAction::Success;
+        }
+
+        let target_loc = self.calculate_ahead_loc(car_loc, 15.0_f32.to_radians());
+
+        ctx.eeg
+            .draw(Drawable::ghost_car_ground(target_loc, me.Physics.rot()));
+
+        SegmentRunAction::Yield(common::halfway_house::PlayerInput {
+            Throttle: -1.0,
+            Steer: simple_reverse_steer_towards(&me.Physics, target_loc),
+            ..Default::default()
+        })
+    }
+}
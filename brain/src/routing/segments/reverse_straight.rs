@@ -0,0 +1,189 @@
+use crate::{
+    behavior::movement::{simple_reverse_steer_towards, GetToFlatGround},
+    eeg::{color, Drawable},
+    routing::models::{CarState, CarState2D, SegmentPlan, SegmentRunAction, SegmentRunner},
+    strategy::Context,
+};
+use common::{prelude::*, rl};
+use nalgebra::Point2;
+use nameof::name_of_type;
+use simulate::Car1D;
+
+/// Back up in a straight line to a point behind the car.
+#[derive(Clone)]
+pub struct ReverseStraight {
+    start: CarState2D,
+    end_loc: Point2<f32>,
+    profile: ReverseProfile,
+    duration: f32,
+}
+
+impl ReverseStraight {
+    pub fn new(start: CarState2D, end_loc: Point2<f32>) -> Self {
+        let profile = ReverseProfile::new(&start);
+        let distance = (end_loc - start.loc).norm();
+        let duration = if distance < 1.0 {
+            0.0
+        } else {
+            profile.time_to_distance(distance)
+        };
+        Self {
+            start,
+            end_loc,
+            profile,
+            duration,
+        }
+    }
+}
+
+impl SegmentPlan for ReverseStraight {
+    fn name(&self) -> &str {
+        name_of_type!(ReverseStraight)
+    }
+
+    fn start(&self) -> CarState {
+        self.start.to_3d()
+    }
+
+    fn end(&self) -> CarState {
+        self.sample(self.duration)
+    }
+
+    fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    fn sample(&self, t: f32) -> CarState {
+        if self.duration == 0.0 {
+            return self.start.to_3d();
+        }
+
+        let t = t.max(0.0).min(self.duration);
+        let (distance, speed) = self.profile.at(t);
+        let dir = (self.end_loc - self.start.loc).normalize();
+        // Same as `Straight`, start from the actual velocity and settle onto the line.
+        let drift = self.start.vel - dir * self.profile.at(0.0).1;
+        CarState2D {
+            loc: self.start.loc + dir * distance,
+            rot: self.start.rot,
+            vel: dir * speed + drift * (1.0 - t / self.duration),
+            boost: self.start.boost,
+        }
+        .to_3d()
+    }
+
+    fn run(&self) -> Box<dyn SegmentRunner> {
+        Box::new(ReverseStraightRunner::new(self.clone()))
+    }
+
+    fn draw(&self, ctx: &mut Context<'_>) {
+        ctx.eeg
+            .draw(Drawable::Line(self.start.loc, self.end_loc, color::ORANGE));
+    }
+}
+
+struct ReverseStraightRunner {
+    plan: ReverseStraight,
+}
+
+impl ReverseStraightRunner {
+    fn new(plan: ReverseStraight) -> Self {
+        Self { plan }
+    }
+}
+
+impl SegmentRunner for ReverseStraightRunner {
+    fn name(&self) -> &str {
+        name_of_type!(ReverseStraightRunner)
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> SegmentRunAction {
+        let me = ctx.me();
+        let me_loc = me.Physics.loc_2d();
+        let start_to_end = self.plan.end_loc - self.plan.start.loc;
+        if start_to_end.norm() < 1.0 {
+            return SegmentRunAction::Success;
+        }
+        let cur_dist = (me_loc - self.plan.start.loc).dot(&start_to_end.normalize());
+
+        if cur_dist >= start_to_end.norm() {
+            return SegmentRunAction::Success;
+        }
+
+        if !GetToFlatGround::on_flat_ground(me) {
+            ctx.eeg.log(self.name(), "not on flat ground");
+            return SegmentRunAction::Failure;
+        }
+
+        // Hug the line, same as `Straight`, except it's the rear of the car
+        // that needs to point at the target.
+        let target_loc = self.plan.start.loc + start_to_end.normalize() * (cur_dist + 500.0);
+
+        ctx.eeg
+            .draw(Drawable::ghost_car_ground(target_loc, me.Physics.rot()));
+
+        SegmentRunAction::Yield(common::halfway_house::PlayerInput {
+            Throttle: -1.0,
+            Steer: simple_reverse_steer_towards(&me.Physics, target_loc),
+            ..Default::default()
+        })
+    }
+}
+
+/// Speed along the direction of travel while holding full reverse. If the
+/// car is still rolling forwards, that brakes first and then backs up.
+#[derive(Clone)]
+pub struct ReverseProfile {
+    forward_speed: f32,
+    backward_speed: f32,
+}
+
+impl ReverseProfile {
+    pub fn new(start: &CarState2D) -> Self {
+        let speed = start.vel.dot(&start.forward_axis());
+        Self {
+            forward_speed: speed.max(0.0),
+            backward_speed: (-speed).max(0.0).min(rl::CAR_NORMAL_SPEED),
+        }
+    }
+
+    fn stop_time(&self) -> f32 {
+        self.forward_speed / rl::CAR_BRAKE_ACCEL
+    }
+
+    fn stop_distance(&self) -> f32 {
+        self.forward_speed * self.forward_speed / (2.0 * rl::CAR_BRAKE_ACCEL)
+    }
+
+    /// How long it takes to end up `distance` behind the start.
+    pub fn time_to_distance(&self, distance: f32) -> f32 {
+        let total_distance = distance + self.stop_distance();
+        if total_distance <= 0.0 {
+            return self.stop_time();
+        }
+        let mut sim = Car1D::new()
+            .with_speed(self.backward_speed)
+            .with_boost(0.0)
+            .reversing();
+        sim.advance_by_distance(total_distance, 1.0, false);
+        self.stop_time() + sim.time()
+    }
+
+    /// Returns the distance behind the start and the speed at time `t`. While
+    /// still braking, both are negative.
+    pub fn at(&self, t: f32) -> (f32, f32) {
+        let stop_time = self.stop_time();
+        if t < stop_time {
+            let mut sim = Car1D::new().with_speed(self.forward_speed);
+            sim.advance(t, -1.0, false);
+            return (-sim.distance(), -sim.speed());
+        }
+
+        let mut sim = Car1D::new()
+            .with_speed(self.backward_speed)
+            .with_boost(0.0)
+            .reversing();
+        sim.advance(t - stop_time, 1.0, false);
+        (sim.distance() - self.stop_distance(), sim.speed())
+    }
+}
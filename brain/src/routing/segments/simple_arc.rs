@@ -144,6 +144,20 @@ impl SegmentPlan for SimpleArc {
         self.radius * self.sweep.abs() / self.start_vel.norm()
     }
 
+    fn sample(&self, t: f32) -> CarState {
+        // `new` rejects low speeds, so the duration is always positive.
+        let frac = (t / self.duration()).max(0.0).min(1.0);
+        let sweep = UnitComplex::new(self.sweep * frac);
+        let vel = sweep * self.start_vel;
+        CarState2D {
+            loc: self.center + sweep * (self.start_loc - self.center),
+            rot: CAR_LOCAL_FORWARD_AXIS_2D.rotation_to(&Unit::new_normalize(vel)),
+            vel,
+            boost: self.start_boost,
+        }
+        .to_3d()
+    }
+
     fn run(&self) -> Box<dyn SegmentRunner> {
         Box::new(SimpleArcRunner::new(self.clone()))
     }
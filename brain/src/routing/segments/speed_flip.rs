@@ -0,0 +1,98 @@
+use crate::{
+    behavior::movement,
+    eeg::{color, Drawable},
+    routing::models::{CarState, CarState2D, SegmentPlan, SegmentRunAction, SegmentRunner},
+    strategy::{Action, Behavior, Context},
+};
+use common::prelude::*;
+use derive_new::new;
+use nameof::name_of_type;
+use simulate::CarSpeedFlip1D;
+
+#[derive(Clone, new)]
+pub struct SpeedFlip {
+    start: CarState,
+    flip: CarSpeedFlip1D,
+    dodge_right: bool,
+}
+
+impl SegmentPlan for SpeedFlip {
+    fn name(&self) -> &str {
+        name_of_type!(SpeedFlip)
+    }
+
+    fn start(&self) -> CarState {
+        self.start.clone()
+    }
+
+    fn end(&self) -> CarState {
+        let forward_axis = self.start.forward_axis_2d().into_inner();
+        CarState2D {
+            loc: self.start.loc.to_2d() + forward_axis * self.flip.end_dist,
+            rot: self.start.rot.to_2d(),
+            vel: forward_axis * self.flip.end_speed,
+            boost: (self.start.boost - self.flip.boost_used).max(0.0),
+        }
+        .to_3d()
+    }
+
+    fn duration(&self) -> f32 {
+        self.flip.duration()
+    }
+
+    fn sample(&self, t: f32) -> CarState {
+        let (dist, speed) = self.flip.sample(t);
+        let forward_axis = self.start.forward_axis_2d().into_inner();
+        // Start from the actual velocity, sideways drift and all, and settle onto
+        // the line by the end.
+        let drift = self.start.vel.to_2d() - forward_axis * self.flip.start_speed;
+        let frac = (t / self.duration()).max(0.0).min(1.0);
+        CarState2D {
+            loc: self.start.loc.to_2d() + forward_axis * dist,
+            rot: self.start.rot.to_2d(),
+            vel: forward_axis * speed + drift * (1.0 - frac),
+            // Boost is held the whole way through.
+            boost: (self.start.boost - self.flip.boost_used * frac).max(0.0),
+        }
+        .to_3d()
+    }
+
+    fn run(&self) -> Box<dyn SegmentRunner> {
+        Box::new(SpeedFlipRunner::new(self.dodge_right))
+    }
+
+    fn draw(&self, ctx: &mut Context<'_>) {
+        ctx.eeg.draw(Drawable::Line(
+            self.start.loc.to_2d(),
+            self.end().loc.to_2d(),
+            color::GREEN,
+        ));
+    }
+}
+
+struct SpeedFlipRunner {
+    behavior: movement::SpeedFlip,
+}
+
+impl SpeedFlipRunner {
+    pub fn new(dodge_right: bool) -> Self {
+        let behavior = movement::SpeedFlip::new().dodge_right(dodge_right);
+        Self { behavior }
+    }
+}
+
+impl SegmentRunner for SpeedFlipRunner {
+    fn name(&self) -> &str {
+        name_of_type!(SpeedFlipRunner)
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> SegmentRunAction {
+        match self.behavior.execute_old(ctx) {
+            Action::Yield(i) => SegmentRunAction::Yield(i),
+            Action::TailCall(_) => panic!("TailCall not yet supported in SegmentRunner"),
+            Action::RootCall(_) => SegmentRunAction::Failure,
+            Action::Return => SegmentRunAction::Success,
+            Action::Abort => SegmentRunAction::Failure,
+        }
+    }
+}
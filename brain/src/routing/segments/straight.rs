@@ -8,6 +8,7 @@ use common::{prelude::*, rl};
 use nalgebra::{Point2, Vector2};
 use nameof::name_of_type;
 use simulate::Car1D;
+use std::cell::RefCell;
 
 #[derive(Clone)]
 pub struct Straight {
@@ -18,6 +19,9 @@ pub struct Straight {
     duration: f32,
     mode: StraightMode,
     allow_boost: bool,
+    /// The simulation as of the last `sample`. Samples usually come in order,
+    /// so this lets each one pick up where the last left off.
+    sample_sim: RefCell<Option<Car1D>>,
 }
 
 /// This is a workaround for the lack of "arrive-at-time" behavior.
@@ -79,6 +83,7 @@ impl Straight {
             duration: sim.time(),
             mode,
             allow_boost,
+            sample_sim: RefCell::new(None),
         }
     }
 
@@ -91,6 +96,7 @@ impl Straight {
             duration: 0.0,
             mode: StraightMode::Fake,
             allow_boost: true,
+            sample_sim: RefCell::new(None),
         }
     }
 }
@@ -120,6 +126,36 @@ impl SegmentPlan for Straight {
         self.duration
     }
 
+    fn sample(&self, t: f32) -> CarState {
+        if self.duration == 0.0 {
+            return self.start.to_3d();
+        }
+
+        let t = t.max(0.0).min(self.duration);
+        let mut cache = self.sample_sim.borrow_mut();
+        if cache.as_ref().map_or(true, |sim| sim.time() > t) {
+            *cache = Some(
+                Car1D::new()
+                    .with_speed(self.start.vel.norm())
+                    .with_boost(self.start.boost),
+            );
+        }
+        let sim = cache.as_mut().unwrap();
+        sim.advance(t - sim.time(), 1.0, self.allow_boost);
+
+        // Start from the car's actual velocity, sideways drift and all, and
+        // settle onto the line by the end.
+        let dir = (self.end_loc - self.start.loc).normalize();
+        let drift = self.start.vel - dir * self.start.vel.norm();
+        CarState2D {
+            loc: self.start.loc + dir * sim.distance(),
+            rot: self.start.rot,
+            vel: dir * sim.speed() + drift * (1.0 - t / self.duration),
+            boost: sim.boost(),
+        }
+        .to_3d()
+    }
+
     fn run(&self) -> Box<dyn SegmentRunner> {
         Box::new(StraightRunner::new(self.clone()))
     }
@@ -0,0 +1,234 @@
+use crate::{
+    behavior::movement::{simple_steer_towards, GetToFlatGround},
+    eeg::{color, Drawable},
+    routing::models::{CarState, CarState2D, SegmentPlan, SegmentRunAction, SegmentRunner},
+    strategy::Context,
+};
+use common::{prelude::*, rl, Speed};
+use nalgebra::Point2;
+use nameof::name_of_type;
+use simulate::{CarArrive, CarArrive1D, CarArriveError};
+
+/// Drive straight, scheduling throttle, coasting and braking so we arrive at
+/// the end at a specific time (and optionally a specific speed).
+#[derive(Clone)]
+pub struct TimedStraight {
+    start: CarState2D,
+    end_loc: Point2<f32>,
+    end_speed: Option<f32>,
+    schedule: CarArrive1D,
+}
+
+impl TimedStraight {
+    pub fn new(
+        start: CarState2D,
+        end_loc: Point2<f32>,
+        end_speed: Option<f32>,
+        schedule: CarArrive1D,
+    ) -> Self {
+        Self {
+            start,
+            end_loc,
+            end_speed,
+            schedule,
+        }
+    }
+}
+
+impl SegmentPlan for TimedStraight {
+    fn name(&self) -> &str {
+        name_of_type!(TimedStraight)
+    }
+
+    fn start(&self) -> CarState {
+        self.start.to_3d()
+    }
+
+    fn end(&self) -> CarState {
+        let mut end = self.sample(self.duration());
+        end.boost = self.schedule.end_boost();
+        end
+    }
+
+    fn duration(&self) -> f32 {
+        self.schedule.duration()
+    }
+
+    fn sample(&self, t: f32) -> CarState {
+        let (distance, speed) = self.schedule.at(t);
+        let dir = (self.end_loc - self.start.loc).normalize();
+        // Like `Straight`, blend from the car's actual velocity onto the line.
+        let (_, start_speed) = self.schedule.at(0.0);
+        let drift = self.start.vel - dir * start_speed;
+        let remaining = if self.duration() > 0.0 {
+            1.0 - (t / self.duration()).max(0.0).min(1.0)
+        } else {
+            1.0
+        };
+        CarState2D {
+            loc: self.start.loc + dir * distance,
+            rot: self.start.rot,
+            vel: dir * speed + drift * remaining,
+            boost: self.start.boost,
+        }
+        .to_3d()
+    }
+
+    fn run(&self) -> Box<dyn SegmentRunner> {
+        Box::new(TimedStraightRunner::new(self.clone()))
+    }
+
+    fn draw(&self, ctx: &mut Context<'_>) {
+        ctx.eeg
+            .draw(Drawable::Line(self.start.loc, self.end_loc, color::GREEN));
+    }
+}
+
+struct TimedStraightRunner {
+    plan: TimedStraight,
+    start_time: Option<f32>,
+    solved: Option<Solved>,
+}
+
+/// The schedule being followed, which started at game time `time` when we
+/// were `dist` along the straight.
+struct Solved {
+    schedule: CarArrive1D,
+    time: f32,
+    dist: f32,
+}
+
+impl TimedStraightRunner {
+    /// Within this much of the cruise speed, coast instead of braking.
+    const COAST_MARGIN: f32 = 100.0;
+    /// When time runs out, being this close to the end still counts.
+    const ARRIVAL_TOLERANCE: f32 = 100.0;
+    /// Solving a schedule runs a few hundred simulations, so stick with the
+    /// current one until we're this far off it.
+    const RESOLVE_DISTANCE: f32 = 50.0;
+    const RESOLVE_SPEED: f32 = 100.0;
+
+    fn new(plan: TimedStraight) -> Self {
+        Self {
+            plan,
+            start_time: None,
+            solved: None,
+        }
+    }
+}
+
+impl SegmentRunner for TimedStraightRunner {
+    fn name(&self) -> &str {
+        name_of_type!(TimedStraightRunner)
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> SegmentRunAction {
+        let now = ctx.packet.GameInfo.TimeSeconds;
+        let start_time = *self.start_time.get_or_insert(now);
+        let time_remaining = self.plan.duration() - (now - start_time);
+
+        let me = ctx.me();
+        let start_to_end = self.plan.end_loc - self.plan.start.loc;
+        let dir = start_to_end.normalize();
+        let cur_dist = (me.Physics.loc_2d() - self.plan.start.loc).dot(&dir);
+        let dist_remaining = start_to_end.norm() - cur_dist;
+
+        if dist_remaining <= 0.0 {
+            return SegmentRunAction::Success;
+        }
+        if time_remaining <= 0.0 {
+            if dist_remaining < Self::ARRIVAL_TOLERANCE {
+                return SegmentRunAction::Success;
+            }
+            ctx.eeg.log(
+                self.name(),
+                format!("out of time with {:.0} uu to go", dist_remaining),
+            );
+            return SegmentRunAction::Failure;
+        }
+
+        if !GetToFlatGround::on_flat_ground(me) {
+            ctx.eeg.log(self.name(), "not on flat ground");
+            return SegmentRunAction::Failure;
+        }
+
+        let speed = me
+            .Physics
+            .vel_2d()
+            .dot(&me.Physics.forward_axis_2d())
+            .max(0.0);
+
+        // Start with the planned schedule, and re-solve from where we actually
+        // are once we've fallen off it, so errors don't pile up.
+        let plan = &self.plan;
+        let solved = self.solved.get_or_insert_with(|| Solved {
+            schedule: plan.schedule.clone(),
+            time: start_time,
+            dist: 0.0,
+        });
+        let (expected_dist, expected_speed) = solved.schedule.at(now - solved.time);
+        if (cur_dist - solved.dist - expected_dist).abs() >= Self::RESOLVE_DISTANCE
+            || (speed - expected_speed).abs() >= Self::RESOLVE_SPEED
+        {
+            match CarArrive::calc_1d(
+                speed,
+                me.Boost as f32,
+                dist_remaining,
+                time_remaining,
+                plan.end_speed,
+            ) {
+                Ok(schedule) => {
+                    *solved = Solved {
+                        schedule,
+                        time: now,
+                        dist: cur_dist,
+                    };
+                }
+                // We've fallen off the schedule. Do whatever gets us closest.
+                Err(error) => {
+                    let (throttle, boost) = match error {
+                        CarArriveError::TooFar => (1.0, true),
+                        CarArriveError::TooFast => (-1.0, false),
+                    };
+                    return self.drive(ctx, cur_dist, throttle, boost);
+                }
+            }
+        }
+
+        let (throttle, boost) = match solved.schedule.phase_at(now - solved.time) {
+            Some(phase) => (phase.throttle, phase.boost),
+            None => {
+                let cruise_speed = solved.schedule.cruise_speed;
+                ctx.eeg.print_value("cruise_speed", Speed(cruise_speed));
+                if speed < cruise_speed {
+                    (1.0, cruise_speed > rl::CAR_NORMAL_SPEED)
+                } else if speed < cruise_speed + Self::COAST_MARGIN {
+                    (0.0, false)
+                } else {
+                    (-1.0, false)
+                }
+            }
+        };
+        self.drive(ctx, cur_dist, throttle, boost)
+    }
+}
+
+impl TimedStraightRunner {
+    fn drive(
+        &self,
+        ctx: &mut Context<'_>,
+        cur_dist: f32,
+        throttle: f32,
+        boost: bool,
+    ) -> SegmentRunAction {
+        let me = ctx.me();
+        let dir = (self.plan.end_loc - self.plan.start.loc).normalize();
+        let target_loc = self.plan.start.loc + dir * (cur_dist + 500.0);
+        SegmentRunAction::Yield(common::halfway_house::PlayerInput {
+            Throttle: throttle,
+            Steer: simple_steer_towards(&me.Physics, target_loc),
+            Boost: boost && me.Boost > 0,
+            ..Default::default()
+        })
+    }
+}
@@ -30,7 +30,7 @@ impl Turn {
             loc: start.loc.to_2d(),
             rot: start.rot.to_2d(),
             vel: start.vel.to_2d(),
-            boost: 0.0,
+            boost: start.boost,
         };
 
         let sweep = (start.loc - center).angle_to(&(projected_end_loc - center));
@@ -83,6 +83,23 @@ impl SegmentPlan for Turn {
         self.radius * self.sweep.abs() / assume_speed
     }
 
+    fn sample(&self, t: f32) -> CarState {
+        let duration = self.duration();
+        let frac = if duration > 0.0 {
+            (t / duration).max(0.0).min(1.0)
+        } else {
+            1.0
+        };
+        let sweep = UnitComplex::new(self.sweep * frac);
+        CarState2D {
+            loc: self.center + sweep * (self.start.loc - self.center),
+            rot: sweep * self.start.rot,
+            vel: sweep * self.start.vel,
+            boost: self.start.boost,
+        }
+        .to_3d()
+    }
+
     fn run(&self) -> Box<dyn SegmentRunner> {
         Box::new(Turner::new(self.clone()))
     }
@@ -16,6 +16,10 @@ pub struct WallStraight {
     target_to_flat: Flattener,
     flat_end_loc: Point2<f32>,
     flat_end_vel: Vector2<f32>,
+    end_boost: f32,
+    /// How far along the flattened path the car crosses from the start surface
+    /// onto the target surface.
+    seam_dist: f32,
     duration: f32,
 }
 
@@ -41,6 +45,21 @@ impl WallStraight {
         let flat_end_loc = flat_start.loc + flat_dir * sim.distance();
         let flat_end_vel = flat_dir * sim.speed();
 
+        // Unflattening a point back onto either surface gives the same result only
+        // along the seam. Find where the path comes closest to that.
+        let unflatten_gap = |flat_loc: Point2<f32>| {
+            let flat_loc = flat_loc.to_3d(0.0);
+            start_to_flat.inverse() * flat_loc - target_to_flat.inverse() * flat_loc
+        };
+        let gap = unflatten_gap(flat_start.loc);
+        let gap_per_dist = unflatten_gap(flat_start.loc + flat_dir) - gap;
+        let seam_dist = if gap_per_dist.norm() < 1e-3 {
+            // Same surface, or parallel ones.
+            std::f32::INFINITY
+        } else {
+            -gap.dot(&gap_per_dist) / gap_per_dist.norm_squared()
+        };
+
         Self {
             start,
             target_loc,
@@ -48,11 +67,41 @@ impl WallStraight {
             target_to_flat,
             flat_end_loc,
             flat_end_vel,
+            end_boost: sim.boost(),
+            seam_dist,
             duration: sim.time(),
         }
     }
 }
 
+impl WallStraight {
+    /// Drive along the flattened line, then unflatten onto whichever surface
+    /// that puts us on.
+    fn model(&self, t: f32) -> CarState {
+        let flat_start = self.start.flatten(&self.start_to_flat);
+        let mut sim = Car1D::new()
+            .with_speed(flat_start.vel.norm())
+            .with_boost(flat_start.boost);
+        sim.advance(t, 1.0, true);
+
+        let flat_span = self.flat_end_loc - flat_start.loc;
+        let flat_dir = flat_span.normalize();
+        let dist = sim.distance().min(flat_span.norm());
+        let flat_loc = flat_start.loc + flat_dir * dist;
+        let unflatten = if dist < self.seam_dist {
+            self.start_to_flat.inverse()
+        } else {
+            self.target_to_flat.inverse()
+        };
+        CarState {
+            loc: unflatten * flat_loc.to_3d(rl::OCTANE_NEUTRAL_Z),
+            rot: unflatten.rotation * (self.start_to_flat * self.start.rot).around_z_axis(),
+            vel: unflatten * (flat_dir * sim.speed()).to_3d(0.0),
+            boost: sim.boost(),
+        }
+    }
+}
+
 impl SegmentPlan for WallStraight {
     fn name(&self) -> &str {
         name_of_type!(WallStraight)
@@ -68,7 +117,7 @@ impl SegmentPlan for WallStraight {
             loc: flat_to_target * self.flat_end_loc.to_3d(rl::OCTANE_NEUTRAL_Z),
             rot: flat_to_target.rotation * (self.start_to_flat * self.start.rot).around_z_axis(),
             vel: flat_to_target * self.flat_end_vel.to_3d(0.0),
-            boost: 0.0,
+            boost: self.end_boost,
         }
     }
 
@@ -76,6 +125,15 @@ impl SegmentPlan for WallStraight {
         self.duration
     }
 
+    fn sample(&self, t: f32) -> CarState {
+        if self.duration == 0.0 {
+            return self.start.clone();
+        }
+        let t = t.max(0.0).min(self.duration);
+        self.model(t)
+            .with_start_error(&self.start, &self.model(0.0), t / self.duration)
+    }
+
     fn run(&self) -> Box<dyn SegmentRunner> {
         Box::new(WallStraightRunner)
     }
@@ -0,0 +1,352 @@
+use crate::{
+    eeg::Drawable,
+    routing::models::{CarState, CarState2D, SegmentPlan, SegmentRunAction, SegmentRunner},
+    strategy::{Context, Pitch},
+    utils::geometry::{flattener::Flattener, Plane},
+};
+use common::{physics::CAR_LOCAL_FORWARD_AXIS_2D, prelude::*, rl};
+use nalgebra::{Isometry3, Point2, Point3, Vector2};
+use nameof::name_of_type;
+use simulate::Car1D;
+
+/// A single flat map covering the floor, one side wall, and the ceiling. The
+/// wall is unfolded down onto the floor around their seam, and the ceiling is
+/// unfolded onto the wall the same way, so a straight line on the map is a
+/// straight drive up the wall and onto the ceiling.
+#[derive(Copy, Clone)]
+pub struct CeilingChart {
+    ground: Plane,
+    wall: Plane,
+    ceiling: Plane,
+    wall_to_flat: Flattener,
+    ceiling_to_flat: Flattener,
+}
+
+impl CeilingChart {
+    /// `side` picks the side wall by the sign of its x coordinate.
+    pub fn new(pitch: &Pitch, side: f32) -> Result<Self, ()> {
+        let ground = *pitch.ground();
+        let ceiling = *pitch.ceiling();
+        let wall_loc = Point3::new(rl::FIELD_MAX_X * side.signum(), 0.0, rl::FIELD_MAX_Z / 2.0);
+        let wall = *pitch.closest_plane(&wall_loc);
+        let wall_to_ground = wall.unfold(&ground)?;
+        let ceiling_to_wall = ceiling.unfold(&wall)?;
+        Ok(Self {
+            ground,
+            wall,
+            ceiling,
+            wall_to_flat: Flattener::new(wall_to_ground),
+            ceiling_to_flat: Flattener::new(wall_to_ground * ceiling_to_wall),
+        })
+    }
+
+    /// The flattener for whichever surface `loc` is on, or `None` if that
+    /// surface isn't part of this chart.
+    pub fn flattener(&self, pitch: &Pitch, loc: &Point3<f32>) -> Option<Flattener> {
+        let surface = pitch.closest_plane(loc);
+        if *surface == self.ground {
+            Some(Flattener::identity())
+        } else if *surface == self.wall {
+            Some(self.wall_to_flat)
+        } else if *surface == self.ceiling {
+            Some(self.ceiling_to_flat)
+        } else {
+            None
+        }
+    }
+
+    pub fn flatten_ceiling(&self, loc: Point3<f32>) -> Point2<f32> {
+        self.ceiling_to_flat * loc
+    }
+
+    pub fn is_ceiling(&self, surface: &Plane) -> bool {
+        *surface == self.ceiling
+    }
+
+    /// Takes a point on the map back to whichever surface it lands on.
+    fn unflatten(&self, flat_loc: Point2<f32>) -> Isometry3<f32> {
+        // Unflattened onto the wrong surface, a point ends up past the edge
+        // where that surface meets its neighbor, i.e. outside the field.
+        let onto = |flattener: Flattener| flattener.inverse() * flat_loc.to_3d(0.0);
+        if self.wall.distance_to_point(&onto(self.ceiling_to_flat)) >= 0.0 {
+            self.ceiling_to_flat.inverse()
+        } else if self.ground.distance_to_point(&onto(self.wall_to_flat)) >= 0.0 {
+            self.wall_to_flat.inverse()
+        } else {
+            Isometry3::identity()
+        }
+    }
+}
+
+/// Drive straight up a side wall and onto the ceiling.
+#[derive(Clone)]
+pub struct WallToCeiling {
+    start: CarState,
+    flat_start: CarState2D,
+    chart: CeilingChart,
+    flat_target_loc: Point2<f32>,
+    flat_end_loc: Point2<f32>,
+    flat_end_vel: Vector2<f32>,
+    end_boost: f32,
+    duration: f32,
+}
+
+impl WallToCeiling {
+    pub fn new(
+        start: CarState,
+        flat_start: &CarState2D,
+        target_loc: Point3<f32>,
+        chart: CeilingChart,
+    ) -> Self {
+        let flat_target_loc = chart.flatten_ceiling(target_loc);
+        let flat_target_dist = (flat_target_loc - flat_start.loc).norm();
+
+        let mut sim = Car1D::new()
+            .with_speed(flat_start.vel.norm())
+            .with_boost(flat_start.boost);
+
+        // XXX: Like `WallStraight`, this ignores gravity and the speed lost at
+        // each seam.
+        sim.advance_by_distance(flat_target_dist, 1.0, true);
+
+        let flat_dir = (flat_target_loc - flat_start.loc).normalize();
+        Self {
+            start,
+            flat_start: flat_start.clone(),
+            chart,
+            flat_target_loc,
+            flat_end_loc: flat_start.loc + flat_dir * sim.distance(),
+            flat_end_vel: flat_dir * sim.speed(),
+            end_boost: sim.boost(),
+            duration: sim.time(),
+        }
+    }
+}
+
+impl WallToCeiling {
+    /// Drive along the line on the map, then take it back to whichever surface
+    /// that puts us on.
+    fn model(&self, t: f32) -> CarState {
+        let mut sim = Car1D::new()
+            .with_speed(self.flat_start.vel.norm())
+            .with_boost(self.flat_start.boost);
+        sim.advance(t, 1.0, true);
+
+        let flat_span = self.flat_end_loc - self.flat_start.loc;
+        let flat_dir = flat_span.normalize();
+        let flat_loc = self.flat_start.loc + flat_dir * sim.distance().min(flat_span.norm());
+        let flat_rot = CAR_LOCAL_FORWARD_AXIS_2D.rotation_to(&flat_dir.to_axis());
+        let unflatten = self.chart.unflatten(flat_loc);
+        CarState {
+            loc: unflatten * flat_loc.to_3d(rl::OCTANE_NEUTRAL_Z),
+            rot: unflatten.rotation * flat_rot.around_z_axis(),
+            vel: unflatten * (flat_dir * sim.speed()).to_3d(0.0),
+            boost: sim.boost(),
+        }
+    }
+}
+
+impl SegmentPlan for WallToCeiling {
+    fn name(&self) -> &str {
+        name_of_type!(WallToCeiling)
+    }
+
+    fn start(&self) -> CarState {
+        self.start.clone()
+    }
+
+    fn end(&self) -> CarState {
+        let flat_to_ceiling = self.chart.ceiling_to_flat.inverse();
+        let flat_rot = CAR_LOCAL_FORWARD_AXIS_2D.rotation_to(&self.flat_end_vel.to_axis());
+        CarState {
+            loc: flat_to_ceiling * self.flat_end_loc.to_3d(rl::OCTANE_NEUTRAL_Z),
+            rot: flat_to_ceiling.rotation * flat_rot.around_z_axis(),
+            vel: flat_to_ceiling * self.flat_end_vel.to_3d(0.0),
+            boost: self.end_boost,
+        }
+    }
+
+    fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    fn sample(&self, t: f32) -> CarState {
+        if self.duration == 0.0 {
+            return self.start.clone();
+        }
+        let t = t.max(0.0).min(self.duration);
+        self.model(t)
+            .with_start_error(&self.start, &self.model(0.0), t / self.duration)
+    }
+
+    fn run(&self) -> Box<dyn SegmentRunner> {
+        Box::new(WallToCeilingRunner::new(self.clone()))
+    }
+
+    fn draw(&self, ctx: &mut Context<'_>) {
+        let end = self.end();
+        ctx.eeg
+            .draw(Drawable::GhostCar(end.loc, end.rot.to_rotation_matrix()));
+    }
+}
+
+struct WallToCeilingRunner {
+    plan: WallToCeiling,
+}
+
+impl WallToCeilingRunner {
+    /// Call it done once we're on the ceiling and this close to the target.
+    const ARRIVAL_DISTANCE: f32 = 100.0;
+
+    pub fn new(plan: WallToCeiling) -> Self {
+        Self { plan }
+    }
+}
+
+impl SegmentRunner for WallToCeilingRunner {
+    fn name(&self) -> &str {
+        name_of_type!(WallToCeilingRunner)
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> SegmentRunAction {
+        let me = ctx.me();
+
+        if !me.OnGround {
+            ctx.eeg.log(self.name(), "not on ground");
+            return SegmentRunAction::Failure;
+        }
+
+        let me_loc = me.Physics.loc();
+        let flattener = some_or_else!(self.plan.chart.flattener(ctx.game.pitch(), &me_loc), {
+            ctx.eeg.log(self.name(), "wandered off the chart");
+            return SegmentRunAction::Failure;
+        });
+        let me_flat_loc = flattener * me_loc;
+        let me_flat_forward = flattener * me.Physics.forward_axis();
+        let to_target = self.plan.flat_target_loc - me_flat_loc;
+
+        let on_ceiling = self
+            .plan
+            .chart
+            .is_ceiling(ctx.game.pitch().closest_plane(&me_loc));
+        if on_ceiling
+            && (to_target.norm() < Self::ARRIVAL_DISTANCE || me_flat_forward.dot(&to_target) < 0.0)
+        {
+            return SegmentRunAction::Success;
+        }
+
+        let steer = me_flat_forward.angle_to(&to_target);
+        SegmentRunAction::Yield(common::halfway_house::PlayerInput {
+            Throttle: 1.0,
+            Steer: (steer * 2.0).max(-1.0).min(1.0),
+            Boost: !on_ceiling && me.Physics.vel().norm() < rl::CAR_ALMOST_MAX_SPEED,
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::pitch::DFH_STADIUM;
+    use nalgebra::{UnitComplex, Vector3};
+
+    const CEILING_Z: f32 = rl::FIELD_MAX_Z - rl::OCTANE_NEUTRAL_Z;
+
+    #[test]
+    fn chart_covers_one_side() {
+        let chart = CeilingChart::new(&DFH_STADIUM, 1.0).unwrap();
+        let floor = Point3::new(0.0, 0.0, 0.0);
+        let wall = Point3::new(rl::FIELD_MAX_X, 0.0, 1000.0);
+        let ceiling = Point3::new(3000.0, 0.0, rl::FIELD_MAX_Z);
+        let far_wall = Point3::new(-rl::FIELD_MAX_X, 0.0, 1000.0);
+        assert!(chart.flattener(&DFH_STADIUM, &floor).is_some());
+        assert!(chart.flattener(&DFH_STADIUM, &wall).is_some());
+        assert!(chart.flattener(&DFH_STADIUM, &ceiling).is_some());
+        assert!(chart.flattener(&DFH_STADIUM, &far_wall).is_none());
+        assert!(chart.is_ceiling(DFH_STADIUM.ceiling()));
+        assert!(!chart.is_ceiling(DFH_STADIUM.ground()));
+    }
+
+    #[test]
+    fn chart_unfolds_outward() {
+        let chart = CeilingChart::new(&DFH_STADIUM, 1.0).unwrap();
+        let flatten = |loc: Point3<f32>| chart.flattener(&DFH_STADIUM, &loc).unwrap() * loc;
+
+        let floor = flatten(Point3::new(1000.0, 500.0, 0.0));
+        assert!((floor - Point2::new(1000.0, 500.0)).norm() < 1.0);
+
+        // The wall continues on past the seam, one uu out per uu up.
+        let wall = flatten(Point3::new(rl::FIELD_MAX_X, 500.0, 1000.0));
+        assert!((wall - Point2::new(rl::FIELD_MAX_X + 1000.0, 500.0)).norm() < 1.0);
+
+        // Then the ceiling continues on past the top of the wall.
+        let ceiling_loc = Point3::new(3000.0, 500.0, rl::FIELD_MAX_Z);
+        let expected_x = rl::FIELD_MAX_X + rl::FIELD_MAX_Z + (rl::FIELD_MAX_X - 3000.0);
+        assert!((flatten(ceiling_loc) - Point2::new(expected_x, 500.0)).norm() < 1.0);
+        assert!((chart.flatten_ceiling(ceiling_loc) - flatten(ceiling_loc)).norm() < 1.0);
+    }
+
+    #[test]
+    fn chart_mirrors_for_the_other_side() {
+        let chart = CeilingChart::new(&DFH_STADIUM, -1.0).unwrap();
+        let wall = Point3::new(-rl::FIELD_MAX_X, 0.0, 1000.0);
+        let flat = chart.flattener(&DFH_STADIUM, &wall).unwrap() * wall;
+        assert!((flat - Point2::new(-rl::FIELD_MAX_X - 1000.0, 0.0)).norm() < 1.0);
+    }
+
+    #[test]
+    fn drives_onto_the_ceiling() {
+        let start = CarState {
+            loc: Point3::new(3000.0, 0.0, rl::OCTANE_NEUTRAL_Z),
+            rot: UnitComplex::identity().around_z_axis(),
+            vel: Vector3::new(1000.0, 0.0, 0.0),
+            boost: 50.0,
+        };
+        let target_loc = Point3::new(3000.0, 300.0, CEILING_Z);
+        let chart = CeilingChart::new(&DFH_STADIUM, target_loc.x).unwrap();
+        let flattener = chart.flattener(&DFH_STADIUM, &start.loc).unwrap();
+        let flat_start = start.flatten(&flattener);
+        let segment = WallToCeiling::new(start.clone(), &flat_start, target_loc, chart);
+
+        let end = segment.end();
+        assert!((end.loc - target_loc).norm() < 1.0);
+        // Upside down, and heading back in from the wall.
+        assert!(end.roof_axis().z < -0.99);
+        assert!(end.vel.x < 0.0);
+        assert!(end.vel.z.abs() < 1.0);
+        assert!(segment.duration() > 0.0);
+        assert_eq!(segment.sample(0.0).loc, start.loc);
+        assert!((segment.sample(segment.duration()).loc - end.loc).norm() < 1.0);
+    }
+
+    #[test]
+    fn samples_follow_the_surfaces() {
+        let start = CarState {
+            loc: Point3::new(3000.0, 0.0, rl::OCTANE_NEUTRAL_Z),
+            rot: UnitComplex::identity().around_z_axis(),
+            vel: Vector3::new(1000.0, 0.0, 0.0),
+            boost: 50.0,
+        };
+        let target_loc = Point3::new(3000.0, 300.0, CEILING_Z);
+        let chart = CeilingChart::new(&DFH_STADIUM, target_loc.x).unwrap();
+        let flattener = chart.flattener(&DFH_STADIUM, &start.loc).unwrap();
+        let flat_start = start.flatten(&flattener);
+        let segment = WallToCeiling::new(start, &flat_start, target_loc, chart);
+
+        let wall = DFH_STADIUM.closest_plane(&Point3::new(rl::FIELD_MAX_X, 0.0, 1000.0));
+        let surfaces = [DFH_STADIUM.ground(), wall, DFH_STADIUM.ceiling()];
+        let mut on_wall = false;
+        for i in 0..=20 {
+            let sample = segment.sample(segment.duration() * i as f32 / 20.0);
+            // Riding on a surface, not cutting through the air across a seam.
+            let surface = surfaces
+                .iter()
+                .find(|s| sample.roof_axis().dot(&s.normal) > 0.99)
+                .unwrap();
+            assert!((surface.distance_to_point(&sample.loc) - rl::OCTANE_NEUTRAL_Z).abs() < 1.0);
+            on_wall |= surface == &wall;
+        }
+        assert!(on_wall);
+    }
+}
@@ -0,0 +1,377 @@
+use crate::{
+    eeg::Drawable,
+    routing::models::{CarState, SegmentPlan, SegmentRunAction, SegmentRunner},
+    strategy::{Context, Pitch},
+    utils::geometry::{flattener::Flattener, Plane},
+};
+use common::{physics::CAR_LOCAL_FORWARD_AXIS_2D, prelude::*, rl};
+use nalgebra::{Matrix3, Point2, Point3, Rotation3, Unit, UnitQuaternion, Vector2};
+use nameof::name_of_type;
+use simulate::CarWall1D;
+use std::f32::consts::PI;
+
+/// Drive straight across the curved ramp between the floor and a wall, in
+/// either direction. Like `CeilingChart`, the wall is unfolded down onto the
+/// floor around their seam, so the route is a straight line in the floor's
+/// coordinates.
+#[derive(Clone)]
+pub struct WallTransition {
+    start: CarState,
+    ground: Plane,
+    wall: Plane,
+    wall_to_flat: Flattener,
+    up_wall: bool,
+    flat_start_loc: Point2<f32>,
+    flat_target_loc: Point2<f32>,
+    flat_dir: Unit<Vector2<f32>>,
+    start_speed: f32,
+    /// The distance and slope accel of each stretch of the drive, in the
+    /// arena's uu: the start surface, the ramp, then the end surface.
+    legs: [(f32, f32); 3],
+    /// How long the ramp is on the chart, where it's a sharp corner.
+    flat_ramp_dist: f32,
+    duration: f32,
+}
+
+impl WallTransition {
+    /// Crossing the seam at a shallower angle than this means spending ages on
+    /// the ramp, which isn't modeled well.
+    const MIN_CLIMB: f32 = 0.25;
+
+    /// Fails if `start` and `target_loc` aren't on opposite sides of the seam
+    /// between `ground` and `wall`, or if the route crosses the seam at too
+    /// shallow an angle.
+    pub fn new(
+        start: CarState,
+        target_loc: Point3<f32>,
+        ground: Plane,
+        wall: Plane,
+    ) -> Result<Self, ()> {
+        if wall.normal.z != 0.0 {
+            return Err(());
+        }
+        let wall_to_flat = Flattener::new(wall.unfold(&ground)?);
+
+        let up_wall = ground.distance_to_point(&start.loc) < wall.distance_to_point(&start.loc);
+        let target_on_wall =
+            wall.distance_to_point(&target_loc) < ground.distance_to_point(&target_loc);
+        if up_wall != target_on_wall {
+            return Err(());
+        }
+
+        let (start_to_flat, target_to_flat) = if up_wall {
+            (Flattener::identity(), wall_to_flat)
+        } else {
+            (wall_to_flat, Flattener::identity())
+        };
+        let flat_start = start.flatten(&start_to_flat);
+        let flat_target_loc = target_to_flat * target_loc;
+        let total_dist = (flat_target_loc - flat_start.loc).norm();
+        if total_dist < 1.0 {
+            return Err(());
+        }
+        let flat_dir = (flat_target_loc - flat_start.loc).to_axis();
+
+        // On the chart, up the wall is away from the field, against the wall's
+        // normal. This is also the sine of the angle we climb at on the wall.
+        let up = -wall.normal.into_inner().to_2d();
+        let climb = flat_dir.dot(&up);
+        if climb.abs() < Self::MIN_CLIMB || (climb > 0.0) != up_wall {
+            return Err(());
+        }
+
+        // The distance to the seam, measured along the route.
+        let seam_dist = if up_wall {
+            wall.distance_to_point(&start.loc)
+        } else {
+            ground.distance_to_point(&start.loc)
+        } / climb.abs();
+        if seam_dist >= total_dist {
+            return Err(());
+        }
+
+        // On the chart, the ramp is a sharp corner. In the arena it's a quarter
+        // circle, which is a shorter drive, and gravity fades in partway
+        // through, so call it half.
+        let ramp_dist = Pitch::RAMP_RADIUS / climb.abs();
+        let ramp_before = seam_dist.min(ramp_dist);
+        let ramp_after = (total_dist - seam_dist).min(ramp_dist);
+        let wall_accel = CarWall1D::slope_accel(climb);
+        let (start_accel, end_accel) = if up_wall {
+            (0.0, wall_accel)
+        } else {
+            (wall_accel, 0.0)
+        };
+        let legs = [
+            (seam_dist - ramp_before, start_accel),
+            ((ramp_before + ramp_after) * PI / 4.0, wall_accel / 2.0),
+            (total_dist - seam_dist - ramp_after, end_accel),
+        ];
+
+        // Boost on the way up to keep from bleeding speed. On the way down,
+        // gravity does the work.
+        let start_speed = flat_start.vel.norm().min(rl::CAR_MAX_SPEED);
+        let mut sim = CarWall1D::new()
+            .with_speed(start_speed)
+            .with_boost(start.boost);
+        for &(dist, accel) in &legs {
+            if dist > 0.0 {
+                sim.advance_by_distance(dist, accel, up_wall);
+            }
+        }
+
+        Ok(Self {
+            start,
+            ground,
+            wall,
+            wall_to_flat,
+            up_wall,
+            flat_start_loc: flat_start.loc,
+            flat_target_loc,
+            flat_dir,
+            start_speed,
+            legs,
+            flat_ramp_dist: ramp_before + ramp_after,
+            duration: sim.time(),
+        })
+    }
+
+    fn flattener(&self, surface: &Plane) -> Option<Flattener> {
+        if *surface == self.ground {
+            Some(Flattener::identity())
+        } else if *surface == self.wall {
+            Some(self.wall_to_flat)
+        } else {
+            None
+        }
+    }
+
+    fn end_surface(&self) -> &Plane {
+        if self.up_wall {
+            &self.wall
+        } else {
+            &self.ground
+        }
+    }
+
+    /// Where the car is `t` seconds in, following the legs from `new()`.
+    fn model(&self, t: f32) -> CarState {
+        let mut sim = CarWall1D::new()
+            .with_speed(self.start_speed)
+            .with_boost(self.start.boost);
+        for &(dist, accel) in &self.legs {
+            if dist <= 0.0 {
+                continue;
+            }
+            let mut next = sim.clone();
+            next.advance_by_distance(dist, accel, self.up_wall);
+            if next.time() > t {
+                sim.advance(t - sim.time(), accel, self.up_wall);
+                break;
+            }
+            sim = next;
+        }
+
+        // Convert the distance driven back to a distance on the chart. The ramp
+        // is a different length there, and everything else matches.
+        let [(before_dist, _), (ramp_dist, _), _] = self.legs;
+        let dist = sim.distance();
+        let flat_dist = if dist <= before_dist {
+            dist
+        } else if dist <= before_dist + ramp_dist {
+            before_dist + (dist - before_dist) / ramp_dist * self.flat_ramp_dist
+        } else {
+            dist - ramp_dist + self.flat_ramp_dist
+        };
+        let flat_loc = self.flat_start_loc + self.flat_dir.into_inner() * flat_dist;
+
+        // How far past the seam we are, measured straight up the wall.
+        let past_seam = -self.wall.distance_to_point(&flat_loc.to_3d(0.0));
+        if past_seam.abs() >= Pitch::RAMP_RADIUS {
+            let surface = if past_seam < 0.0 {
+                &self.ground
+            } else {
+                &self.wall
+            };
+            return self.on_surface(surface, flat_loc, sim.speed(), sim.boost());
+        }
+
+        // On the ramp. Bend the chart around a quarter circle that meets the
+        // ground and the wall one radius out from the seam.
+        let ground_normal = self.ground.normal.into_inner();
+        let wall_normal = self.wall.normal.into_inner();
+        let seam_loc = flat_loc.to_3d(0.0) + wall_normal * past_seam;
+        let center = seam_loc + (ground_normal + wall_normal) * Pitch::RAMP_RADIUS;
+        let angle = (past_seam + Pitch::RAMP_RADIUS) / (2.0 * Pitch::RAMP_RADIUS) * PI / 2.0;
+        let normal = ground_normal * angle.cos() + wall_normal * angle.sin();
+        let up_ramp = ground_normal * angle.sin() - wall_normal * angle.cos();
+
+        let up = -wall_normal.to_2d();
+        let climb = self.flat_dir.dot(&up);
+        let along_seam = (self.flat_dir.into_inner() - up * climb).to_3d(0.0);
+        let forward = (along_seam + up_ramp * climb).normalize();
+        let right = normal.cross(&forward);
+        let rot =
+            Rotation3::from_matrix_unchecked(Matrix3::from_columns(&[forward, right, normal]));
+        CarState {
+            loc: center - normal * (Pitch::RAMP_RADIUS - rl::OCTANE_NEUTRAL_Z),
+            rot: UnitQuaternion::from_rotation_matrix(&rot),
+            vel: forward * sim.speed(),
+            boost: sim.boost(),
+        }
+    }
+
+    fn on_surface(
+        &self,
+        surface: &Plane,
+        flat_loc: Point2<f32>,
+        speed: f32,
+        boost: f32,
+    ) -> CarState {
+        let flat_to_surface = self.flattener(surface).unwrap().inverse();
+        let flat_rot = CAR_LOCAL_FORWARD_AXIS_2D.rotation_to(&self.flat_dir);
+        CarState {
+            loc: flat_to_surface * flat_loc.to_3d(rl::OCTANE_NEUTRAL_Z),
+            rot: flat_to_surface.rotation * flat_rot.around_z_axis(),
+            vel: flat_to_surface * (self.flat_dir.into_inner() * speed).to_3d(0.0),
+            boost,
+        }
+    }
+}
+
+impl SegmentPlan for WallTransition {
+    fn name(&self) -> &str {
+        name_of_type!(WallTransition)
+    }
+
+    fn start(&self) -> CarState {
+        self.start.clone()
+    }
+
+    fn end(&self) -> CarState {
+        self.model(self.duration)
+    }
+
+    fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    fn sample(&self, t: f32) -> CarState {
+        if self.duration == 0.0 {
+            return self.start.clone();
+        }
+        let t = t.max(0.0).min(self.duration);
+        self.model(t)
+            .with_start_error(&self.start, &self.model(0.0), t / self.duration)
+    }
+
+    fn run(&self) -> Box<dyn SegmentRunner> {
+        Box::new(WallTransitionRunner::new(self.clone()))
+    }
+
+    fn draw(&self, ctx: &mut Context<'_>) {
+        let end = self.end();
+        ctx.eeg
+            .draw(Drawable::GhostCar(end.loc, end.rot.to_rotation_matrix()));
+    }
+}
+
+struct WallTransitionRunner {
+    plan: WallTransition,
+}
+
+impl WallTransitionRunner {
+    /// Call it done once we're past the seam and this close to the target.
+    const ARRIVAL_DISTANCE: f32 = 100.0;
+
+    pub fn new(plan: WallTransition) -> Self {
+        Self { plan }
+    }
+}
+
+impl SegmentRunner for WallTransitionRunner {
+    fn name(&self) -> &str {
+        name_of_type!(WallTransitionRunner)
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> SegmentRunAction {
+        let me = ctx.me();
+
+        if !me.OnGround {
+            ctx.eeg.log(self.name(), "not on ground");
+            return SegmentRunAction::Failure;
+        }
+
+        let me_loc = me.Physics.loc();
+        let surface = ctx.game.pitch().closest_plane(&me_loc);
+        let flattener = some_or_else!(self.plan.flattener(surface), {
+            ctx.eeg.log(self.name(), "wandered off the chart");
+            return SegmentRunAction::Failure;
+        });
+        let me_flat_loc = flattener * me_loc;
+        let me_flat_forward = flattener * me.Physics.forward_axis();
+        let to_target = self.plan.flat_target_loc - me_flat_loc;
+
+        let past_seam = *surface == *self.plan.end_surface();
+        if past_seam
+            && (to_target.norm() < Self::ARRIVAL_DISTANCE || me_flat_forward.dot(&to_target) < 0.0)
+        {
+            return SegmentRunAction::Success;
+        }
+
+        let steer = me_flat_forward.angle_to(&to_target);
+        SegmentRunAction::Yield(common::halfway_house::PlayerInput {
+            Throttle: 1.0,
+            Steer: (steer * 2.0).max(-1.0).min(1.0),
+            Boost: self.plan.up_wall && me.Physics.vel().norm() < rl::CAR_ALMOST_MAX_SPEED,
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::pitch::DFH_STADIUM;
+    use nalgebra::{UnitComplex, Vector3};
+
+    #[test]
+    fn samples_follow_the_ramp() {
+        let start = CarState {
+            loc: Point3::new(3000.0, 0.0, rl::OCTANE_NEUTRAL_Z),
+            rot: UnitComplex::new(0.5).around_z_axis(),
+            vel: Vector3::new(900.0, 500.0, 0.0),
+            boost: 50.0,
+        };
+        let target_loc = Point3::new(rl::FIELD_MAX_X, 1000.0, 800.0);
+        let ground = *DFH_STADIUM.ground();
+        let wall = *DFH_STADIUM.closest_plane(&target_loc);
+        let segment = WallTransition::new(start.clone(), target_loc, ground, wall).unwrap();
+
+        let end = segment.end();
+        assert_eq!(segment.sample(0.0).loc, start.loc);
+        assert!((segment.sample(segment.duration()).loc - end.loc).norm() < 1.0);
+
+        let radius = Pitch::RAMP_RADIUS;
+        let mut on_ramp = false;
+        let mut prev = segment.sample(0.0);
+        for i in 1..=40 {
+            let sample = segment.sample(segment.duration() * i as f32 / 40.0);
+            let above_ground = ground.distance_to_point(&sample.loc);
+            let off_wall = wall.distance_to_point(&sample.loc);
+            // Riding on the surface, not cutting through the air across the
+            // seam.
+            let clearance = if above_ground < radius && off_wall < radius {
+                on_ramp = true;
+                radius - (radius - above_ground).hypot(radius - off_wall)
+            } else {
+                above_ground.min(off_wall)
+            };
+            assert!((clearance - rl::OCTANE_NEUTRAL_Z).abs() < 1.0);
+            assert!(sample.forward_axis().dot(&sample.roof_axis()).abs() < 0.01);
+            assert!((sample.loc - prev.loc).norm() < 100.0);
+            prev = sample;
+        }
+        assert!(on_ramp);
+    }
+}
@@ -58,6 +58,20 @@ impl WallTurn {
             result
         }
     }
+
+    /// Where the car is after sweeping `frac` of the way around the turn.
+    fn model(&self, frac: f32) -> CarState {
+        let extrude = self.flattener.inverse();
+        let sweep = UnitComplex::new(self.sweep * frac);
+        let flat_start_loc = self.flattener * self.start.loc;
+        let flat_loc = self.flat_center + sweep * (flat_start_loc - self.flat_center);
+        CarState {
+            loc: extrude * flat_loc.to_3d(rl::OCTANE_NEUTRAL_Z),
+            rot: extrude.rotation * (sweep * (self.flattener * self.start.rot)).around_z_axis(),
+            vel: extrude * (sweep * (self.flattener * self.start.vel)).to_3d(0.0),
+            boost: self.start.boost,
+        }
+    }
 }
 
 impl SegmentPlan for WallTurn {
@@ -85,6 +99,19 @@ impl SegmentPlan for WallTurn {
         self.radius * self.sweep.abs() / assume_speed
     }
 
+    fn sample(&self, t: f32) -> CarState {
+        let duration = self.duration();
+        let frac = if duration > 0.0 {
+            (t / duration).max(0.0).min(1.0)
+        } else {
+            1.0
+        };
+        // A start that's still coming off the ramp isn't quite on the surface,
+        // so the model can't start exactly there.
+        self.model(frac)
+            .with_start_error(&self.start, &self.model(0.0), frac)
+    }
+
     fn run(&self) -> Box<dyn SegmentRunner> {
         Box::new(WallTurnRunner::new(self.clone()))
     }
@@ -1,13 +1,20 @@
 use crate::{
+    eeg::EEG,
+    helpers::ball::{BallPredictor, ChipBallPrediction},
     routing::{
         behavior::FollowRoute,
         models::{
-            PlanningContext, PlanningDump, RoutePlan, RoutePlanError, RoutePlanner, SegmentPlan,
+            CarState, PlanningContext, PlanningDump, RoutePlan, RoutePlanError, RoutePlanner,
+            SegmentPlan,
         },
     },
-    strategy::Behavior,
+    strategy::{Behavior, Context, Game, QuickChat, Scenario, TeamIntents},
+    utils::Rng,
 };
+use common::{halfway_house, prelude::*, rl};
 use derive_new::new;
+use nalgebra::{Point2, Point3, UnitComplex, Vector2, Vector3};
+use std::iter;
 
 pub fn route_planner_tester(planner: impl RoutePlanner + 'static) -> impl Behavior {
     FollowRoute::new(planner)
@@ -40,3 +47,137 @@ where
         })
     }
 }
+
+/// Call `f` with a `PlanningContext` for a soccar game where our car is at
+/// `start`, and the ball is the only other thing on the field. This needs no
+/// running game, so planners can be tested directly.
+pub fn with_offline_planning_context<R>(
+    start: &CarState,
+    ball_loc: Point3<f32>,
+    ball_vel: Vector3<f32>,
+    f: impl FnOnce(&PlanningContext<'_, '_>) -> R,
+) -> R {
+    with_offline_planning_context_and_enemies(start, &[], ball_loc, ball_vel, f)
+}
+
+/// Like `with_offline_planning_context`, but with `enemies` on the field too.
+pub fn with_offline_planning_context_and_enemies<R>(
+    start: &CarState,
+    enemies: &[CarState],
+    ball_loc: Point3<f32>,
+    ball_vel: Vector3<f32>,
+    f: impl FnOnce(&PlanningContext<'_, '_>) -> R,
+) -> R {
+    let packet = offline_packet(start, enemies, ball_loc, ball_vel);
+    let game = Game::offline_soccar(&packet, 0);
+    let ball_prediction = ChipBallPrediction::new().predict(&packet);
+    f(&PlanningContext {
+        game: &game,
+        start: start.clone(),
+        ball_prediction: &ball_prediction,
+    })
+}
+
+/// Call `f` with a full behavior `Context` for a soccar game where our car is
+/// at `car`, at game time `time`. Call it once per tick to drive a behavior
+/// through a made-up scenario without a running game.
+pub fn with_offline_context<R>(
+    car: &CarState,
+    ball_loc: Point3<f32>,
+    ball_vel: Vector3<f32>,
+    time: f32,
+    f: impl FnOnce(&mut Context<'_>) -> R,
+) -> R {
+    with_offline_context_and_enemies(car, &[], ball_loc, ball_vel, time, f)
+}
+
+/// Like `with_offline_context`, but with `enemies` on the field too.
+pub fn with_offline_context_and_enemies<R>(
+    car: &CarState,
+    enemies: &[CarState],
+    ball_loc: Point3<f32>,
+    ball_vel: Vector3<f32>,
+    time: f32,
+    f: impl FnOnce(&mut Context<'_>) -> R,
+) -> R {
+    let mut packet = offline_packet(car, enemies, ball_loc, ball_vel);
+    packet.GameInfo.TimeSeconds = time;
+    let game = Game::offline_soccar(&packet, 0);
+    let ball_predictor = ChipBallPrediction::new();
+    let scenario = Scenario::new(&game, &ball_predictor, &packet);
+    let intents = TeamIntents::new();
+    let mut eeg = EEG::new();
+    let mut chat = QuickChat::new();
+    let mut rng = Rng::new(0);
+    f(&mut Context::new(
+        &game, &packet, &scenario, &intents, &mut eeg, &mut chat, &mut rng,
+    ))
+}
+
+/// A car sitting level on the floor at `loc`, facing `yaw` and driving
+/// forward at `speed`.
+pub fn car_on_floor(loc: Point2<f32>, yaw: f32, speed: f32, boost: f32) -> CarState {
+    let rot = UnitComplex::new(yaw);
+    CarState {
+        loc: loc.to_3d(rl::OCTANE_NEUTRAL_Z),
+        rot: rot.around_z_axis(),
+        vel: (rot * Vector2::x() * speed).to_3d(0.0),
+        boost,
+    }
+}
+
+fn offline_packet(
+    car: &CarState,
+    enemies: &[CarState],
+    ball_loc: Point3<f32>,
+    ball_vel: Vector3<f32>,
+) -> halfway_house::LiveDataPacket {
+    let cars: Vec<_> = iter::once(offline_player(car, 0))
+        .chain(enemies.iter().map(|enemy| offline_player(enemy, 1)))
+        .collect();
+    halfway_house::LiveDataPacket {
+        NumCars: cars.len() as i32,
+        GameCars: cars.into_iter().collect(),
+        GameBall: halfway_house::BallInfo {
+            Physics: halfway_house::Physics {
+                Location: vector3(ball_loc.coords),
+                Velocity: vector3(ball_vel),
+                ..Default::default()
+            },
+        },
+        GameInfo: halfway_house::GameInfo {
+            RoundActive: true,
+            ..Default::default()
+        },
+        Teams: Default::default(),
+        NumTeams: 0,
+    }
+}
+
+fn offline_player(car: &CarState, team: u8) -> halfway_house::PlayerInfo {
+    let (pitch, yaw, roll) = car.rot.to_rotation_matrix().to_unreal_angles();
+    halfway_house::PlayerInfo {
+        Physics: halfway_house::Physics {
+            Location: vector3(car.loc.coords),
+            Rotation: halfway_house::Rotator {
+                Pitch: pitch,
+                Yaw: yaw,
+                Roll: roll,
+            },
+            Velocity: vector3(car.vel),
+            AngularVelocity: Default::default(),
+        },
+        OnGround: true,
+        Boost: car.boost as i32,
+        Team: team,
+        ..Default::default()
+    }
+}
+
+fn vector3(v: Vector3<f32>) -> halfway_house::Vector3 {
+    halfway_house::Vector3 {
+        X: v.x,
+        Y: v.y,
+        Z: v.z,
+    }
+}
@@ -1,15 +1,23 @@
 use crate::{
     eeg::EEG,
-    strategy::{game::Game, scenario::Scenario, Team},
+    strategy::{
+        game::Game,
+        intents::{Intent, TeamIntents},
+        quick_chat::{Channel, QuickChat},
+        scenario::Scenario,
+        Team,
+    },
+    utils::Rng,
 };
-use common::prelude::*;
 
 pub struct Context<'a> {
     pub packet: &'a common::halfway_house::LiveDataPacket,
     pub game: &'a Game<'a>,
     pub scenario: &'a Scenario<'a>,
+    pub intents: &'a TeamIntents,
     pub eeg: &'a mut EEG,
-    pub last_quick_chat: &'a mut f32,
+    pub chat: &'a mut QuickChat,
+    pub rng: &'a mut Rng,
 }
 
 impl<'a> Context<'a> {
@@ -17,15 +25,19 @@ impl<'a> Context<'a> {
         game: &'a Game<'_>,
         packet: &'a common::halfway_house::LiveDataPacket,
         scenario: &'a Scenario<'a>,
+        intents: &'a TeamIntents,
         eeg: &'a mut EEG,
-        last_quick_chat: &'a mut f32,
+        chat: &'a mut QuickChat,
+        rng: &'a mut Rng,
     ) -> Self {
         Self {
             packet,
             game,
             scenario,
+            intents,
             eeg,
-            last_quick_chat,
+            chat,
+            rng,
         }
     }
 
@@ -54,30 +66,16 @@ impl<'a> Context<'a> {
     }
 
     pub fn quick_chat(&mut self, probability: f32, choices: &[rlbot::flat::QuickChatSelection]) {
-        // Use physics data to keep things deterministic :)
-        let random1 = self.me().Physics.loc_2d().x.abs().fract();
-        let random2 = self.me().Physics.loc_2d().y.abs().fract();
-        if random1 < probability {
-            let choice = choices[(random2 * choices.len() as f32) as usize];
-            self.eeg.quick_chat(choice);
+        if self.rng.gen_bool(probability) {
+            let choice = *self.rng.choose(choices);
+            self.chat
+                .send(self.packet.GameInfo.TimeSeconds, choice, Channel::Everyone);
         }
     }
 
-    /// Use this to get a "deterministic" "random" number during kickoffs, when
-    /// the cars' locations are fixed.
-    pub fn time_based_random(&self) -> f32 {
-        self.packet.GameInfo.TimeSeconds.fract()
-    }
-
-    /// Use this to get two "deterministic" "random" numbers during kickoffs,
-    /// when the cars' locations are fixed.
-    pub fn time_based_randoms(&self) -> [f32; 4] {
-        [
-            (self.packet.GameInfo.TimeSeconds / 5.0).fract(),
-            self.packet.GameInfo.TimeSeconds.fract(),
-            (self.packet.GameInfo.TimeSeconds * 5.0).fract(),
-            (self.packet.GameInfo.TimeSeconds * 25.0).fract(),
-        ]
+    /// Let our teammates know what we're doing.
+    pub fn declare_intent(&mut self, intent: Intent) {
+        self.chat.declare(self.packet.GameInfo.TimeSeconds, intent);
     }
 }
 
@@ -23,22 +23,51 @@ impl<'a> Game<'a> {
         field_info: rlbot::flat::FieldInfo<'_>,
         packet: &'a common::halfway_house::LiveDataPacket,
         player_index: usize,
+    ) -> Self {
+        let boost_dollars = vector_iter(field_info.boostPads().unwrap())
+            .filter(|info| info.isFullBoost())
+            .map(|info| BoostPickup {
+                loc: point3(info.location().unwrap()).to_2d(),
+            })
+            .collect();
+        Self::with_field(
+            infer_game_mode(field_info),
+            boost_dollars,
+            packet,
+            player_index,
+        )
+    }
+
+    /// A soccar game on the standard field, for running code outside a match
+    /// where there's no `FieldInfo` to read the field layout from.
+    pub fn offline_soccar(
+        packet: &'a common::halfway_house::LiveDataPacket,
+        player_index: usize,
+    ) -> Self {
+        let boost_dollars = rl::SOCCAR_BOOST_DOLLAR_LOCS
+            .iter()
+            .map(|&(x, y)| BoostPickup {
+                loc: Point2::new(x, y),
+            })
+            .collect();
+        Self::with_field(rlbot::GameMode::Soccer, boost_dollars, packet, player_index)
+    }
+
+    fn with_field(
+        mode: rlbot::GameMode,
+        boost_dollars: Vec<BoostPickup>,
+        packet: &'a common::halfway_house::LiveDataPacket,
+        player_index: usize,
     ) -> Self {
         let team = Team::from_ffi(packet.GameCars[player_index].Team);
         Self {
             packet,
-            mode: infer_game_mode(field_info),
+            mode,
             pitch: &*DFH_STADIUM,
             player_index,
             team,
             enemy_team: team.opposing(),
-            boost_dollars: vector_iter(field_info.boostPads().unwrap())
-                .filter(|info| info.isFullBoost())
-                .map(|info| BoostPickup {
-                    loc: point3(info.location().unwrap()).to_2d(),
-                })
-                .collect::<Vec<_>>()
-                .into_boxed_slice(),
+            boost_dollars: boost_dollars.into_boxed_slice(),
             me_vehicle: &OCTANE,
         }
     }
@@ -73,6 +102,10 @@ impl<'a> Game<'a> {
         &self.packet.GameCars[self.player_index]
     }
 
+    pub fn player_index(&self) -> usize {
+        self.player_index
+    }
+
     pub fn me_vehicle(&self) -> &Vehicle {
         &self.me_vehicle
     }
@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+/// What each car on our team has declared it is doing this tick.
+///
+/// In hivemind mode the hivemind fills this in before any drone runs, so every
+/// drone sees the same picture. Otherwise it is pieced together from the quick
+/// chats our teammates have sent recently.
+#[derive(Default)]
+pub struct TeamIntents {
+    intents: HashMap<usize, Intent>,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Intent {
+    /// This car has claimed the ball.
+    GoingForBall,
+    /// This car is staying out of the way of whoever has the ball.
+    Supporting,
+    /// This car is falling back to protect the goal.
+    Defending,
+    /// This car is low on boost and is going to get some.
+    NeedBoost,
+    /// This car wants a teammate to shoot.
+    TakeTheShot,
+}
+
+impl TeamIntents {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn set(&mut self, player_index: usize, intent: Intent) {
+        self.intents.insert(player_index, intent);
+    }
+
+    pub fn get(&self, player_index: usize) -> Option<Intent> {
+        self.intents.get(&player_index).cloned()
+    }
+
+    /// Returns true if a car other than `me_index` has claimed the ball. If we
+    /// claimed it too, the car with the lower index keeps it, so the two of us
+    /// don't both back off.
+    pub fn teammate_going_for_ball(&self, me_index: usize) -> bool {
+        let we_claimed = self.get(me_index) == Some(Intent::GoingForBall);
+        self.intents.iter().any(|(&index, &intent)| {
+            index != me_index && intent == Intent::GoingForBall && (!we_claimed || index < me_index)
+        })
+    }
+
+    pub fn teammate_has_intent(&self, me_index: usize, intent: Intent) -> bool {
+        self.intents
+            .iter()
+            .any(|(&index, &i)| index != me_index && i == intent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::strategy::intents::{Intent, TeamIntents};
+
+    #[test]
+    fn empty_means_nobody() {
+        let intents = TeamIntents::new();
+        assert!(!intents.teammate_going_for_ball(0));
+    }
+
+    #[test]
+    fn ignores_own_claim() {
+        let mut intents = TeamIntents::new();
+        intents.set(0, Intent::GoingForBall);
+        intents.set(2, Intent::Supporting);
+        assert!(!intents.teammate_going_for_ball(0));
+        assert!(intents.teammate_going_for_ball(2));
+        assert_eq!(intents.get(2), Some(Intent::Supporting));
+    }
+
+    #[test]
+    fn lower_index_wins_double_claim() {
+        let mut intents = TeamIntents::new();
+        intents.set(1, Intent::GoingForBall);
+        intents.set(3, Intent::GoingForBall);
+        assert!(!intents.teammate_going_for_ball(1));
+        assert!(intents.teammate_going_for_ball(3));
+    }
+}
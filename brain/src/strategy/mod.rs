@@ -6,7 +6,9 @@ pub use crate::strategy::{
         infer_game_mode, BoostPickup, Game, Goal, Team, Vehicle, SOCCAR_GOAL_BLUE,
         SOCCAR_GOAL_ORANGE,
     },
+    intents::{Intent, TeamIntents},
     pitch::Pitch,
+    quick_chat::{Channel, OutgoingQuickChat, QuickChat},
     runner::Runner,
     scenario::Scenario,
     soccar::Soccar,
@@ -16,9 +18,11 @@ mod behavior;
 mod context;
 mod dropshot;
 mod game;
+mod intents;
 #[cfg(test)]
 pub mod null;
-mod pitch;
+pub mod pitch;
+mod quick_chat;
 mod runner;
 mod scenario;
 mod soccar;
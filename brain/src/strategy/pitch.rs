@@ -9,6 +9,9 @@ pub struct Pitch {
 }
 
 impl Pitch {
+    /// Roughly the radius of the curved ramps where two surfaces meet.
+    pub const RAMP_RADIUS: f32 = 256.0;
+
     pub fn closest_plane(&self, point: &Point3<f32>) -> &Plane {
         self.planes
             .iter()
@@ -19,6 +22,31 @@ impl Pitch {
     pub fn ground(&self) -> &Plane {
         &self.planes[0]
     }
+
+    pub fn ceiling(&self) -> &Plane {
+        &self.planes[1]
+    }
+
+    /// The normal of the surface nearest `point`. Near a seam between two
+    /// planes, the normals are blended to roughly follow the curved ramp
+    /// joining them.
+    pub fn surface_normal(&self, point: &Point3<f32>) -> Unit<Vector3<f32>> {
+        let mut planes: Vec<_> = self
+            .planes
+            .iter()
+            .map(|plane| (plane, plane.distance_to_point(point)))
+            .collect();
+        planes.sort_by_key(|&(_, dist)| NotNan::new(dist).unwrap());
+        let (closest, closest_dist) = planes[0];
+        let (next, next_dist) = planes[1];
+        if next_dist >= Self::RAMP_RADIUS {
+            return closest.normal;
+        }
+        Unit::new_normalize(
+            closest.normal.into_inner() * (Self::RAMP_RADIUS - closest_dist)
+                + next.normal.into_inner() * (Self::RAMP_RADIUS - next_dist),
+        )
+    }
 }
 
 const CORNER_WALL_X: f32 = 3518.0;
@@ -58,3 +86,23 @@ lazy_static! {
         ],
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::strategy::pitch::DFH_STADIUM;
+    use nalgebra::{Point3, Vector3};
+    use std::f32::consts::PI;
+
+    #[test]
+    fn surface_normal_floor() {
+        let normal = DFH_STADIUM.surface_normal(&Point3::new(0.0, 0.0, 20.0));
+        assert_eq!(normal, Vector3::z_axis());
+    }
+
+    #[test]
+    fn surface_normal_ramp() {
+        let normal = DFH_STADIUM.surface_normal(&Point3::new(4000.0, 0.0, 96.0));
+        assert!((normal.angle(&Vector3::z()) - PI / 4.0).abs() < 0.01);
+        assert!(normal.x < 0.0);
+    }
+}
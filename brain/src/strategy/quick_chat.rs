@@ -0,0 +1,401 @@
+use crate::strategy::{Intent, TeamIntents};
+use rlbot::flat::QuickChatSelection;
+use std::collections::HashMap;
+
+/// Who gets to see a quick chat.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Channel {
+    Team,
+    Everyone,
+}
+
+/// The groups the game sorts quick chats into. Each one has its own cooldown,
+/// so a burst of banter can't crowd out something useful.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Category {
+    Information,
+    Compliments,
+    Reactions,
+    Apologies,
+    PostGame,
+    Custom,
+}
+
+impl Category {
+    pub fn of(selection: QuickChatSelection) -> Self {
+        match selection {
+            QuickChatSelection::Information_IGotIt
+            | QuickChatSelection::Information_NeedBoost
+            | QuickChatSelection::Information_TakeTheShot
+            | QuickChatSelection::Information_Defending
+            | QuickChatSelection::Information_GoForIt
+            | QuickChatSelection::Information_Centering
+            | QuickChatSelection::Information_AllYours
+            | QuickChatSelection::Information_InPosition
+            | QuickChatSelection::Information_Incoming => Category::Information,
+            QuickChatSelection::Compliments_NiceShot
+            | QuickChatSelection::Compliments_GreatPass
+            | QuickChatSelection::Compliments_Thanks
+            | QuickChatSelection::Compliments_WhatASave
+            | QuickChatSelection::Compliments_NiceOne
+            | QuickChatSelection::Compliments_WhatAPlay
+            | QuickChatSelection::Compliments_GreatClear
+            | QuickChatSelection::Compliments_NiceBlock => Category::Compliments,
+            QuickChatSelection::Reactions_OMG
+            | QuickChatSelection::Reactions_Noooo
+            | QuickChatSelection::Reactions_Wow
+            | QuickChatSelection::Reactions_CloseOne
+            | QuickChatSelection::Reactions_NoWay
+            | QuickChatSelection::Reactions_HolyCow
+            | QuickChatSelection::Reactions_Whew
+            | QuickChatSelection::Reactions_Siiiick
+            | QuickChatSelection::Reactions_Calculated
+            | QuickChatSelection::Reactions_Savage
+            | QuickChatSelection::Reactions_Okay => Category::Reactions,
+            QuickChatSelection::Apologies_Cursing
+            | QuickChatSelection::Apologies_NoProblem
+            | QuickChatSelection::Apologies_Whoops
+            | QuickChatSelection::Apologies_Sorry
+            | QuickChatSelection::Apologies_MyBad
+            | QuickChatSelection::Apologies_Oops
+            | QuickChatSelection::Apologies_MyFault => Category::Apologies,
+            QuickChatSelection::PostGame_Gg
+            | QuickChatSelection::PostGame_WellPlayed
+            | QuickChatSelection::PostGame_ThatWasFun
+            | QuickChatSelection::PostGame_Rematch
+            | QuickChatSelection::PostGame_OneMoreGame
+            | QuickChatSelection::PostGame_WhatAGame
+            | QuickChatSelection::PostGame_NiceMoves
+            | QuickChatSelection::PostGame_EverybodyDance => Category::PostGame,
+            QuickChatSelection::MaxPysonixQuickChatPresets
+            | QuickChatSelection::Custom_Toxic_WasteCPU
+            | QuickChatSelection::Custom_Toxic_GitGut
+            | QuickChatSelection::Custom_Toxic_DeAlloc
+            | QuickChatSelection::Custom_Toxic_404NoSkill
+            | QuickChatSelection::Custom_Toxic_CatchVirus
+            | QuickChatSelection::Custom_Useful_Passing
+            | QuickChatSelection::Custom_Useful_Faking
+            | QuickChatSelection::Custom_Useful_Demoing
+            | QuickChatSelection::Custom_Useful_Bumping
+            | QuickChatSelection::Custom_Compliments_TinyChances
+            | QuickChatSelection::Custom_Compliments_SkillLevel
+            | QuickChatSelection::Custom_Compliments_proud
+            | QuickChatSelection::Custom_Compliments_GC
+            | QuickChatSelection::Custom_Compliments_Pro => Category::Custom,
+        }
+    }
+
+    fn cooldown(self) -> f32 {
+        match self {
+            Category::Information => 3.0,
+            Category::PostGame => 0.5,
+            Category::Compliments
+            | Category::Reactions
+            | Category::Apologies
+            | Category::Custom => 0.75,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct OutgoingQuickChat {
+    pub selection: QuickChatSelection,
+    pub team_only: bool,
+}
+
+#[derive(Default)]
+pub struct QuickChat {
+    last_sent: HashMap<Category, f32>,
+    outgoing: Option<OutgoingQuickChat>,
+    inbox: Vec<(usize, QuickChatSelection)>,
+    heard: HashMap<usize, (Intent, f32)>,
+    declared: Option<(Intent, f32)>,
+    said: Option<(Intent, f32)>,
+    unsaid: Option<Intent>,
+}
+
+impl QuickChat {
+    /// How long we keep believing a teammate's chat after they send it. This
+    /// outlasts the information cooldown, so a repeated intent never lapses.
+    const INTENT_MEMORY: f32 = 4.0;
+
+    /// Repeat an unchanged intent this often, so teammates keep believing it
+    /// without hearing it every tick. This has to be longer than the
+    /// information cooldown to matter, and shorter than `INTENT_MEMORY` so the
+    /// repeat lands before teammates forget.
+    const REDECLARE_INTERVAL: f32 = 3.75;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a chat to be sent at the end of this tick. Returns false if the
+    /// category is still cooling down or something else was already queued.
+    pub fn send(&mut self, now: f32, selection: QuickChatSelection, channel: Channel) -> bool {
+        if self.outgoing.is_some() {
+            return false;
+        }
+        let category = Category::of(selection);
+        if let Some(&last) = self.last_sent.get(&category) {
+            if now - last < category.cooldown() {
+                return false;
+            }
+        }
+        self.last_sent.insert(category, now);
+        self.outgoing = Some(OutgoingQuickChat {
+            selection,
+            team_only: channel == Channel::Team,
+        });
+        true
+    }
+
+    /// Tell our teammates (and only our teammates) what we're up to. The
+    /// intent counts as ours right away; the chat itself waits for the
+    /// information cooldown if it has to, and goes out from `update` once the
+    /// cooldown ends. Returns true if a chat was queued this tick.
+    #[allow(clippy::float_cmp)]
+    pub fn declare(&mut self, now: f32, intent: Intent) -> bool {
+        self.declared = Some((intent, now));
+        if let Some((said_intent, said_time)) = self.said {
+            if said_intent == intent && now - said_time < Self::REDECLARE_INTERVAL {
+                self.unsaid = None;
+                return false;
+            }
+            // If we change our mind in the same tick, swap out the chat we just
+            // queued instead of waiting out the cooldown it started.
+            if said_time == now {
+                if let Some(outgoing) = &mut self.outgoing {
+                    if outgoing.team_only && outgoing.selection == selection_for_intent(said_intent)
+                    {
+                        outgoing.selection = selection_for_intent(intent);
+                        self.said = Some((intent, now));
+                        self.unsaid = None;
+                        return true;
+                    }
+                }
+            }
+        }
+        if self.say(now, intent) {
+            return true;
+        }
+        self.unsaid = Some(intent);
+        false
+    }
+
+    fn say(&mut self, now: f32, intent: Intent) -> bool {
+        if !self.send(now, selection_for_intent(intent), Channel::Team) {
+            return false;
+        }
+        self.said = Some((intent, now));
+        self.unsaid = None;
+        true
+    }
+
+    pub fn take_outgoing(&mut self) -> Option<OutgoingQuickChat> {
+        self.outgoing.take()
+    }
+
+    /// Record a chat received from the game. It is held until the next
+    /// `update`, which knows who is on our team.
+    pub fn hear(&mut self, player_index: usize, selection: QuickChatSelection) {
+        self.inbox.push((player_index, selection));
+    }
+
+    /// Fold newly heard chats into what we know about our teammates, send any
+    /// intent that was waiting on the cooldown, and return the intents that are
+    /// still fresh, including our own under `me_index`.
+    pub fn update(
+        &mut self,
+        now: f32,
+        me_index: usize,
+        is_teammate: impl Fn(usize) -> bool,
+    ) -> TeamIntents {
+        for (player_index, selection) in self.inbox.drain(..) {
+            if !is_teammate(player_index) {
+                continue;
+            }
+            if let Some(intent) = intent_for_selection(selection) {
+                self.heard.insert(player_index, (intent, now));
+            }
+        }
+        self.heard
+            .retain(|_, &mut (_, time)| now - time < Self::INTENT_MEMORY);
+
+        if let Some(intent) = self.unsaid {
+            self.say(now, intent);
+        }
+
+        let mut intents = TeamIntents::new();
+        for (&player_index, &(intent, _)) in &self.heard {
+            intents.set(player_index, intent);
+        }
+        if let Some((intent, time)) = self.declared {
+            if now - time < Self::INTENT_MEMORY {
+                intents.set(me_index, intent);
+            }
+        }
+        intents
+    }
+}
+
+fn selection_for_intent(intent: Intent) -> QuickChatSelection {
+    match intent {
+        Intent::GoingForBall => QuickChatSelection::Information_IGotIt,
+        Intent::Supporting => QuickChatSelection::Information_AllYours,
+        Intent::Defending => QuickChatSelection::Information_Defending,
+        Intent::NeedBoost => QuickChatSelection::Information_NeedBoost,
+        Intent::TakeTheShot => QuickChatSelection::Information_TakeTheShot,
+    }
+}
+
+fn intent_for_selection(selection: QuickChatSelection) -> Option<Intent> {
+    match selection {
+        QuickChatSelection::Information_IGotIt => Some(Intent::GoingForBall),
+        QuickChatSelection::Information_AllYours | QuickChatSelection::Information_GoForIt => {
+            Some(Intent::Supporting)
+        }
+        QuickChatSelection::Information_Defending => Some(Intent::Defending),
+        QuickChatSelection::Information_NeedBoost => Some(Intent::NeedBoost),
+        QuickChatSelection::Information_TakeTheShot => Some(Intent::TakeTheShot),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::strategy::{
+        quick_chat::{Category, Channel, QuickChat},
+        Intent,
+    };
+    use rlbot::flat::QuickChatSelection;
+
+    #[test]
+    fn categories() {
+        assert_eq!(
+            Category::of(QuickChatSelection::Information_IGotIt),
+            Category::Information,
+        );
+        assert_eq!(
+            Category::of(QuickChatSelection::Reactions_Okay),
+            Category::Reactions,
+        );
+        assert_eq!(
+            Category::of(QuickChatSelection::Custom_Toxic_WasteCPU),
+            Category::Custom,
+        );
+    }
+
+    #[test]
+    fn cooldown_is_per_category() {
+        let mut chat = QuickChat::new();
+        assert!(chat.send(10.0, QuickChatSelection::Reactions_Okay, Channel::Everyone));
+        chat.take_outgoing();
+        assert!(!chat.send(10.1, QuickChatSelection::Reactions_Wow, Channel::Everyone));
+        assert!(chat.send(10.1, QuickChatSelection::PostGame_Gg, Channel::Everyone));
+        chat.take_outgoing();
+        assert!(chat.send(11.0, QuickChatSelection::Reactions_Wow, Channel::Everyone));
+    }
+
+    #[test]
+    fn one_chat_per_tick() {
+        let mut chat = QuickChat::new();
+        assert!(chat.send(10.0, QuickChatSelection::Reactions_Okay, Channel::Everyone));
+        assert!(!chat.send(10.0, QuickChatSelection::PostGame_Gg, Channel::Everyone));
+        let outgoing = chat.take_outgoing().unwrap();
+        assert_eq!(outgoing.selection, QuickChatSelection::Reactions_Okay);
+        // The chat that was turned away should not have started a cooldown.
+        assert!(chat.send(10.0, QuickChatSelection::PostGame_Gg, Channel::Everyone));
+    }
+
+    #[test]
+    fn intents_go_to_team() {
+        let mut chat = QuickChat::new();
+        assert!(chat.declare(10.0, Intent::NeedBoost));
+        let outgoing = chat.take_outgoing().unwrap();
+        assert_eq!(
+            outgoing.selection,
+            QuickChatSelection::Information_NeedBoost
+        );
+        assert!(outgoing.team_only);
+    }
+
+    #[test]
+    fn hears_teammates_only() {
+        let mut chat = QuickChat::new();
+        chat.hear(1, QuickChatSelection::Information_IGotIt);
+        chat.hear(2, QuickChatSelection::Information_IGotIt);
+        chat.hear(3, QuickChatSelection::Reactions_Okay);
+        let intents = chat.update(10.0, 0, |i| i == 2 || i == 3);
+        assert_eq!(intents.get(1), None);
+        assert_eq!(intents.get(2), Some(Intent::GoingForBall));
+        assert_eq!(intents.get(3), None);
+    }
+
+    #[test]
+    fn heard_intents_expire() {
+        let mut chat = QuickChat::new();
+        chat.hear(2, QuickChatSelection::Information_Defending);
+        let intents = chat.update(10.0, 0, |_| true);
+        assert_eq!(intents.get(2), Some(Intent::Defending));
+        let intents = chat.update(11.0, 0, |_| true);
+        assert_eq!(intents.get(2), Some(Intent::Defending));
+        let intents = chat.update(20.0, 0, |_| true);
+        assert_eq!(intents.get(2), None);
+    }
+
+    #[test]
+    fn unchanged_intent_is_not_repeated() {
+        let mut chat = QuickChat::new();
+        assert!(chat.declare(10.0, Intent::GoingForBall));
+        chat.take_outgoing();
+        assert!(!chat.declare(13.5, Intent::GoingForBall));
+        assert!(chat.declare(13.5, Intent::Supporting));
+        chat.take_outgoing();
+        assert!(!chat.declare(17.0, Intent::Supporting));
+        assert!(chat.declare(17.5, Intent::Supporting));
+    }
+
+    #[test]
+    fn changed_intent_replaces_queued_chat() {
+        let mut chat = QuickChat::new();
+        assert!(chat.declare(10.0, Intent::GoingForBall));
+        assert!(chat.declare(10.0, Intent::Supporting));
+        let outgoing = chat.take_outgoing().unwrap();
+        assert_eq!(outgoing.selection, QuickChatSelection::Information_AllYours);
+        assert!(chat.take_outgoing().is_none());
+        let intents = chat.update(10.0, 1, |_| false);
+        assert_eq!(intents.get(1), Some(Intent::Supporting));
+    }
+
+    #[test]
+    fn changed_intent_waits_for_cooldown() {
+        let mut chat = QuickChat::new();
+        assert!(chat.declare(10.0, Intent::GoingForBall));
+        chat.take_outgoing();
+        // The cooldown holds back the chat, but not the intent itself.
+        assert!(!chat.declare(11.0, Intent::Supporting));
+        assert!(chat.take_outgoing().is_none());
+        let intents = chat.update(11.0, 1, |_| false);
+        assert_eq!(intents.get(1), Some(Intent::Supporting));
+        chat.update(12.9, 1, |_| false);
+        assert!(chat.take_outgoing().is_none());
+        // Once the cooldown ends, the new intent goes out on its own.
+        chat.update(13.0, 1, |_| false);
+        let outgoing = chat.take_outgoing().unwrap();
+        assert_eq!(outgoing.selection, QuickChatSelection::Information_AllYours);
+        assert!(!chat.declare(13.0, Intent::Supporting));
+        assert!(chat.take_outgoing().is_none());
+    }
+
+    #[test]
+    fn own_intent_is_included() {
+        let mut chat = QuickChat::new();
+        chat.declare(10.0, Intent::GoingForBall);
+        chat.hear(2, QuickChatSelection::Information_IGotIt);
+        let intents = chat.update(10.5, 1, |i| i == 2);
+        assert_eq!(intents.get(1), Some(Intent::GoingForBall));
+        assert_eq!(intents.get(2), Some(Intent::GoingForBall));
+        let intents = chat.update(20.0, 1, |i| i == 2);
+        assert_eq!(intents.get(1), None);
+    }
+}
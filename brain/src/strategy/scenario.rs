@@ -1,7 +1,9 @@
 use crate::{
+    behavior::offense::{Shoot, Shot},
     helpers::{
         ball::{BallFrame, BallPredictor, BallTrajectory},
         intercept::{naive_intercept_penalty, NaiveIntercept},
+        intercept_optimizer::RankedIntercept,
     },
     strategy::{game::Game, Goal},
     utils::{Wall, WallRayCalculator},
@@ -21,6 +23,7 @@ pub struct Scenario<'a> {
     me_intercept: LazyCell<Option<NaiveIntercept>>,
     enemy_intercept: LazyCell<Option<(&'a common::halfway_house::PlayerInfo, NaiveIntercept)>>,
     possession: LazyCell<f32>,
+    me_shots: LazyCell<Vec<RankedIntercept<Shot>>>,
     push_wall: LazyCell<Wall>,
     impending_score_conservative: LazyCell<Option<BallFrame>>,
     impending_concede: LazyCell<Option<BallFrame>>,
@@ -46,6 +49,7 @@ impl<'a> Scenario<'a> {
             me_intercept: LazyCell::new(),
             enemy_intercept: LazyCell::new(),
             possession: LazyCell::new(),
+            me_shots: LazyCell::new(),
             push_wall: LazyCell::new(),
             impending_concede: LazyCell::new(),
             impending_score_conservative: LazyCell::new(),
@@ -111,6 +115,14 @@ impl<'a> Scenario<'a> {
         self.possession.fill(possession).ok().unwrap();
     }
 
+    /// Every intercept I could shoot from, best first. Ranking simulates the
+    /// drive to every frame of the prediction, so do it once per tick and
+    /// share it between deciding to shoot, aiming, and driving there.
+    pub fn me_shots(&self) -> &[RankedIntercept<Shot>] {
+        self.me_shots
+            .borrow_with(|| Shoot::rank_shots(self.game, self, self.game.me()))
+    }
+
     /// If I blitz to the ball and hit it straight-on, where will it go?
     pub fn push_wall(&self) -> Wall {
         *self.push_wall.borrow_with(|| {
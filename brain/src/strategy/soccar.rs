@@ -1,9 +1,9 @@
 use crate::{
     behavior::{
-        defense::Defense,
+        defense::{Defense, EnemyDribbling, Shadow},
         higher_order::{Chain, Predicate, TryChoose, While},
         movement::{GetToFlatGround, Land, Yielder},
-        offense::Offense,
+        offense::{Offense, Support, TeammateHasBall},
         strike::{FiftyFifty, WallHit},
         taunt::{PodiumBlastoff, PodiumSpew, SaltWhileDemolished, TurtleSpin},
         PreKickoff,
@@ -67,6 +67,11 @@ impl Strategy for Soccar {
             return Box::new(Defense::new());
         }
 
+        if ctx.intents.teammate_going_for_ball(ctx.game.player_index()) {
+            ctx.eeg.log(name_of_type!(Soccar), "teammate has the ball");
+            return Box::new(While::new(TeammateHasBall, Support::new()));
+        }
+
         Box::new(Offense::new())
     }
 
@@ -77,8 +82,7 @@ impl Strategy for Soccar {
     ) -> Option<Box<dyn Behavior>> {
         if ctx.packet.GameInfo.MatchEnded {
             if current.priority() < Priority::Taunt {
-                let rand = ctx.time_based_random();
-                let celebrate = if rand < 0.5 {
+                let celebrate = if ctx.rng.gen_bool(0.5) {
                     While::new(MatchIsEnded, PodiumSpew::new())
                 } else {
                     While::new(MatchIsEnded, PodiumBlastoff::new())
@@ -132,6 +136,18 @@ impl Strategy for Soccar {
             )])));
         }
 
+        if current.priority() < Priority::Defense
+            && EnemyDribbling.evaluate(ctx)
+            && GetToFlatGround::on_flat_ground(ctx.me())
+            && ctx.scenario.possession() < -Scenario::POSSESSION_CONTESTABLE
+        {
+            ctx.eeg
+                .log(name_of_type!(Soccar), "enemy is dribbling, shadowing");
+            return Some(Box::new(Chain::new(Priority::Defense, vec![Box::new(
+                Shadow::new(),
+            )])));
+        }
+
         if current.priority() < Priority::Defense
             && Defense::enemy_can_shoot(ctx)
             && GetToFlatGround::on_flat_ground(ctx.me())
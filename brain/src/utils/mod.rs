@@ -1,5 +1,6 @@
 pub use crate::utils::{
     fps_counter::FPSCounter,
+    rng::Rng,
     stopwatch::Stopwatch,
     wall_ray_calculator::{Wall, WallRayCalculator},
 };
@@ -7,5 +8,6 @@ pub use crate::utils::{
 mod fps_counter;
 pub mod geometry;
 pub mod intercept_memory;
+mod rng;
 mod stopwatch;
 mod wall_ray_calculator;
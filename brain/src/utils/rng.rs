@@ -0,0 +1,92 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A small seedable random number stream (SplitMix64).
+///
+/// Everything the bot does "at random" should pull from here, so that a run
+/// can be reproduced by reusing its seed.
+pub struct Rng {
+    seed: u64,
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { seed, state: seed }
+    }
+
+    /// Seed from the clock, for when nobody asked for anything in particular.
+    pub fn from_time() -> Self {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        Self::new(now.as_secs() ^ (u64::from(now.subsec_nanos()) << 32))
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in `[0, 1)`.
+    pub fn gen_f32(&mut self) -> f32 {
+        // Keep only as many bits as fit in the mantissa.
+        (self.next_u64() >> 40) as f32 / (1 << 24) as f32
+    }
+
+    /// Returns a number in `[low, high)`.
+    pub fn gen_range(&mut self, low: f32, high: f32) -> f32 {
+        low + (high - low) * self.gen_f32()
+    }
+
+    /// Returns true with the given probability.
+    pub fn gen_bool(&mut self, probability: f32) -> bool {
+        self.gen_f32() < probability
+    }
+
+    pub fn choose<'a, T>(&mut self, choices: &'a [T]) -> &'a T {
+        assert!(!choices.is_empty());
+        let index = (self.next_u64() % choices.len() as u64) as usize;
+        &choices[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::Rng;
+
+    #[test]
+    fn same_seed_same_stream() {
+        let mut a = Rng::new(1234);
+        let mut b = Rng::new(1234);
+        for _ in 0..100 {
+            assert_eq!(a.gen_f32().to_bits(), b.gen_f32().to_bits());
+        }
+    }
+
+    #[test]
+    fn different_seed_different_stream() {
+        let mut a = Rng::new(1);
+        let mut b = Rng::new(2);
+        let a: Vec<_> = (0..10).map(|_| a.gen_f32().to_bits()).collect();
+        let b: Vec<_> = (0..10).map(|_| b.gen_f32().to_bits()).collect();
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn in_range() {
+        let mut rng = Rng::new(0);
+        for _ in 0..1000 {
+            let x = rng.gen_f32();
+            assert!(x >= 0.0 && x < 1.0);
+            let y = rng.gen_range(-1.0, 1.0);
+            assert!(y >= -1.0 && y < 1.0);
+        }
+    }
+}
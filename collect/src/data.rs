@@ -77,11 +77,11 @@ fn csv_input<'a>(it: &mut impl Iterator<Item = &'a str>) -> Result<RecordingPlay
     })
 }
 
-fn csv_vector3<'a>(it: &mut impl Iterator<Item = &'a str>) -> Result<Vector3<f32>, ()> {
+pub(crate) fn csv_vector3<'a>(it: &mut impl Iterator<Item = &'a str>) -> Result<Vector3<f32>, ()> {
     Ok(Vector3::new(csv_f32(it)?, csv_f32(it)?, csv_f32(it)?))
 }
 
-fn csv_quat<'a>(it: &mut impl Iterator<Item = &'a str>) -> Result<UnitQuaternion<f32>, ()> {
+pub(crate) fn csv_quat<'a>(it: &mut impl Iterator<Item = &'a str>) -> Result<UnitQuaternion<f32>, ()> {
     let x = csv_f32(it)?;
     let y = csv_f32(it)?;
     let z = csv_f32(it)?;
@@ -97,6 +97,6 @@ fn csv_bool<'a>(it: &mut impl Iterator<Item = &'a str>) -> Result<bool, ()> {
     }
 }
 
-fn csv_f32<'a>(it: &mut impl Iterator<Item = &'a str>) -> Result<f32, ()> {
+pub(crate) fn csv_f32<'a>(it: &mut impl Iterator<Item = &'a str>) -> Result<f32, ()> {
     it.next().ok_or(())?.parse().map_err(|_| ())
 }
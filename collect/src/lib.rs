@@ -5,9 +5,13 @@
 pub use crate::{
     collector::Collector,
     data::{RecordingPlayerInput, RecordingPlayerTick, RecordingRigidBodyState, RecordingTick},
+    plan::{
+        PlanCollector, RecordingCarState, RecordingPlan, RecordingPlanError, RecordingPlanSegment,
+    },
     rlbot_ext::get_packet_and_inject_rigid_body_tick,
 };
 
 mod collector;
 mod data;
+mod plan;
 mod rlbot_ext;
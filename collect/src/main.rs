@@ -22,6 +22,7 @@ pub fn main() -> Result<(), Box<dyn Error>> {
     start_match(&rlbot)?;
 
     run_scenario(&rlbot, scenarios::Jump::new())?;
+    run_scenario(&rlbot, scenarios::Reverse::new())?;
 
     Ok(())
 }
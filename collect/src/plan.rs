@@ -0,0 +1,288 @@
+use crate::data::{csv_f32, csv_quat, csv_vector3};
+use nalgebra::{Point3, UnitQuaternion, Vector3};
+use std::{fs::File, io::Read, iter::once};
+
+/// A route plan, as it looked when it was made. This is everything needed to
+/// draw the plan later, without any of the planner machinery.
+pub struct RecordingPlan {
+    /// The game time when the plan was made.
+    pub time: f32,
+    pub player_index: usize,
+    pub segments: Vec<RecordingPlanSegment>,
+    /// Set if planning failed partway through. Any segments before the
+    /// failure are still listed.
+    pub error: Option<RecordingPlanError>,
+}
+
+pub struct RecordingPlanSegment {
+    pub planner: String,
+    pub segment: String,
+    pub duration: f32,
+    pub start: RecordingCarState,
+    pub end: RecordingCarState,
+}
+
+pub struct RecordingPlanError {
+    pub planner: String,
+    pub error: String,
+}
+
+#[derive(Clone)]
+pub struct RecordingCarState {
+    pub loc: Point3<f32>,
+    pub rot: UnitQuaternion<f32>,
+    pub vel: Vector3<f32>,
+    pub boost: f32,
+}
+
+impl RecordingPlan {
+    pub fn duration(&self) -> f32 {
+        self.segments.iter().map(|s| s.duration).sum()
+    }
+
+    pub fn parse(r: impl Read) -> Vec<RecordingPlan> {
+        let mut r = csv::Reader::from_reader(r);
+        let mut result: Vec<RecordingPlan> = Vec::new();
+        let mut last_id = None;
+
+        for row in r.records().map(Result::unwrap) {
+            // Each plan is written as a run of rows with the same id. (Time and
+            // player aren't enough, since we can plan more than once a tick.)
+            let id = row[0].to_string();
+            if last_id.as_ref() != Some(&id) {
+                result.push(RecordingPlan {
+                    time: row[1].parse().unwrap(),
+                    player_index: row[2].parse().unwrap(),
+                    segments: Vec::new(),
+                    error: None,
+                });
+                last_id = Some(id);
+            }
+
+            let mut it = row.iter().skip(3);
+            let planner = it.next().unwrap().to_string();
+            let segment = it.next().unwrap().to_string();
+            let plan = result.last_mut().unwrap();
+
+            if segment.is_empty() {
+                plan.error = Some(RecordingPlanError {
+                    planner,
+                    error: row[row.len() - 1].to_string(),
+                });
+                continue;
+            }
+
+            plan.segments.push(RecordingPlanSegment {
+                planner,
+                segment,
+                duration: csv_f32(&mut it).unwrap(),
+                start: RecordingCarState::from_csv(&mut it).unwrap(),
+                end: RecordingCarState::from_csv(&mut it).unwrap(),
+            });
+        }
+        result
+    }
+}
+
+impl RecordingCarState {
+    fn from_csv<'a>(it: &mut impl Iterator<Item = &'a str>) -> Result<Self, ()> {
+        Ok(Self {
+            loc: Point3::from(csv_vector3(it)?),
+            rot: csv_quat(it)?,
+            vel: csv_vector3(it)?,
+            boost: csv_f32(it)?,
+        })
+    }
+}
+
+/// Writes route plans to a CSV, the same way `Collector` does for game ticks.
+pub struct PlanCollector {
+    w: csv::Writer<File>,
+    wrote_header: bool,
+    /// Sequence number of the next plan written, so `parse` can tell plans
+    /// apart even when they share a time and player.
+    next_id: usize,
+}
+
+impl PlanCollector {
+    pub fn new(file: File) -> Self {
+        let w = csv::Writer::from_writer(file);
+        Self {
+            w,
+            wrote_header: false,
+            next_id: 0,
+        }
+    }
+
+    pub fn write(&mut self, plan: &RecordingPlan) -> csv::Result<()> {
+        if !self.wrote_header {
+            self.wrote_header = true;
+            self.w.write_record(
+                ["plan", "time", "player", "planner", "segment", "duration"]
+                    .iter()
+                    .map(|&s| s.to_string())
+                    .chain(car_state_header("start"))
+                    .chain(car_state_header("end"))
+                    .chain(once("error".to_string())),
+            )?;
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+
+        for segment in &plan.segments {
+            self.w.write_record(
+                vec![
+                    id.to_string(),
+                    plan.time.to_string(),
+                    plan.player_index.to_string(),
+                    segment.planner.clone(),
+                    segment.segment.clone(),
+                    segment.duration.to_string(),
+                ]
+                .into_iter()
+                .chain(car_state(&segment.start))
+                .chain(car_state(&segment.end))
+                .chain(once(String::new())),
+            )?;
+        }
+
+        if let Some(error) = &plan.error {
+            self.w.write_record(
+                vec![
+                    id.to_string(),
+                    plan.time.to_string(),
+                    plan.player_index.to_string(),
+                    error.planner.clone(),
+                    String::new(),
+                    String::new(),
+                ]
+                .into_iter()
+                .chain(car_state_header("start").map(|_| String::new()))
+                .chain(car_state_header("end").map(|_| String::new()))
+                .chain(once(error.error.clone())),
+            )?;
+        }
+
+        // Plans are few and far between compared to ticks, and they're most
+        // interesting right before a crash, so don't let them sit in a buffer.
+        self.w.flush()?;
+        Ok(())
+    }
+}
+
+fn car_state_header(prefix: &'static str) -> impl Iterator<Item = String> {
+    [
+        "_loc_x", "_loc_y", "_loc_z", "_rot_x", "_rot_y", "_rot_z", "_rot_w", "_vel_x", "_vel_y",
+        "_vel_z", "_boost",
+    ]
+    .iter()
+    .map(move |s| format!("{}{}", prefix, s))
+}
+
+fn car_state(state: &RecordingCarState) -> impl Iterator<Item = String> {
+    vec![
+        state.loc.x.to_string(),
+        state.loc.y.to_string(),
+        state.loc.z.to_string(),
+        state.rot.coords.x.to_string(),
+        state.rot.coords.y.to_string(),
+        state.rot.coords.z.to_string(),
+        state.rot.coords.w.to_string(),
+        state.vel.x.to_string(),
+        state.vel.y.to_string(),
+        state.vel.z.to_string(),
+        state.boost.to_string(),
+    ]
+    .into_iter()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::plan::{
+        PlanCollector, RecordingCarState, RecordingPlan, RecordingPlanError, RecordingPlanSegment,
+    };
+    use nalgebra::{Point3, UnitQuaternion, Vector3};
+    use std::{env, fs, fs::File};
+
+    fn car_state(y: f32) -> RecordingCarState {
+        RecordingCarState {
+            loc: Point3::new(0.0, y, 17.0),
+            rot: UnitQuaternion::from_euler_angles(0.0, 0.0, 1.0),
+            vel: Vector3::new(0.0, 1000.0, 0.0),
+            boost: 50.0,
+        }
+    }
+
+    fn segment(planner: &str, start_y: f32, end_y: f32) -> RecordingPlanSegment {
+        RecordingPlanSegment {
+            planner: planner.to_string(),
+            segment: "Straight".to_string(),
+            duration: (end_y - start_y) / 1000.0,
+            start: car_state(start_y),
+            end: car_state(end_y),
+        }
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn round_trip() {
+        let plans = vec![
+            RecordingPlan {
+                time: 10.0,
+                player_index: 0,
+                segments: vec![segment("A", 0.0, 500.0), segment("B", 500.0, 1500.0)],
+                error: None,
+            },
+            // A second plan in the same tick, which must not merge into the
+            // first.
+            RecordingPlan {
+                time: 10.0,
+                player_index: 0,
+                segments: vec![segment("C", 0.0, 250.0)],
+                error: Some(RecordingPlanError {
+                    planner: "D".to_string(),
+                    error: "oops, no route".to_string(),
+                }),
+            },
+            RecordingPlan {
+                time: 10.0,
+                player_index: 1,
+                segments: Vec::new(),
+                error: Some(RecordingPlanError {
+                    planner: "E".to_string(),
+                    error: "nope".to_string(),
+                }),
+            },
+        ];
+
+        let path = env::temp_dir().join(format!("plans-round-trip-{}.csv", std::process::id()));
+        let mut collector = PlanCollector::new(File::create(&path).unwrap());
+        for plan in &plans {
+            collector.write(plan).unwrap();
+        }
+        let parsed = RecordingPlan::parse(File::open(&path).unwrap());
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(parsed.len(), plans.len());
+        for (parsed, plan) in parsed.iter().zip(&plans) {
+            assert_eq!(parsed.time, plan.time);
+            assert_eq!(parsed.player_index, plan.player_index);
+            assert_eq!(parsed.segments.len(), plan.segments.len());
+            for (parsed, segment) in parsed.segments.iter().zip(&plan.segments) {
+                assert_eq!(parsed.planner, segment.planner);
+                assert_eq!(parsed.segment, segment.segment);
+                assert_eq!(parsed.duration, segment.duration);
+                assert_eq!(parsed.start.loc, segment.start.loc);
+                assert!(parsed.start.rot.angle_to(&segment.start.rot) < 1e-6);
+                assert_eq!(parsed.start.vel, segment.start.vel);
+                assert_eq!(parsed.start.boost, segment.start.boost);
+                assert_eq!(parsed.end.loc, segment.end.loc);
+            }
+            assert_eq!(
+                parsed.error.as_ref().map(|e| (&e.planner, &e.error)),
+                plan.error.as_ref().map(|e| (&e.planner, &e.error)),
+            );
+        }
+    }
+}
@@ -189,6 +189,58 @@ impl SimpleScenario for Coast {
     }
 }
 
+/// Like `Throttle`, but backwards. The car faces +y, so the recorded location
+/// and velocity come out negative.
+pub struct Reverse;
+
+impl Reverse {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl SimpleScenario for Reverse {
+    fn name(&self) -> String {
+        "reverse".to_string()
+    }
+
+    /// Face -y, so backing up travels towards +y and the table reads the same
+    /// way as `throttle`.
+    fn initial_state(&self) -> rlbot::DesiredGameState {
+        let mut state = game_state_default();
+        state.car_states[0]
+            .as_mut()
+            .unwrap()
+            .physics
+            .as_mut()
+            .unwrap()
+            .rotation = Some(
+            rlbot::RotatorPartial::new()
+                .pitch(0.0)
+                .yaw(-PI / 2.0)
+                .roll(0.0),
+        );
+        state
+    }
+
+    fn step(
+        &mut self,
+        time: f32,
+        _packet: &common::halfway_house::LiveDataPacket,
+    ) -> SimpleScenarioStepResult {
+        if time < 2.0 {
+            SimpleScenarioStepResult::Ignore(Default::default())
+        } else if time < 5.0 {
+            SimpleScenarioStepResult::Write(common::halfway_house::PlayerInput {
+                Throttle: -1.0,
+                ..Default::default()
+            })
+        } else {
+            SimpleScenarioStepResult::Finish
+        }
+    }
+}
+
 pub struct Turn {
     start_speed: f32,
     start_time: Option<f32>,
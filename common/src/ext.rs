@@ -270,6 +270,7 @@ pub trait ExtendRLBot {
         &self,
         selection: rlbot::flat::QuickChatSelection,
         player_index: i32,
+        team_only: bool,
     ) -> Result<(), ()>;
 }
 
@@ -278,9 +279,10 @@ impl ExtendRLBot for rlbot::RLBot {
         &self,
         selection: rlbot::flat::QuickChatSelection,
         player_index: i32,
+        team_only: bool,
     ) -> Result<(), ()> {
         self.interface()
-            .send_quick_chat(build_quick_chat(selection, player_index).finished_data())
+            .send_quick_chat(build_quick_chat(selection, player_index, team_only).finished_data())
             .map_err(|_| ())
     }
 }
@@ -288,12 +290,13 @@ impl ExtendRLBot for rlbot::RLBot {
 fn build_quick_chat(
     selection: rlbot::flat::QuickChatSelection,
     player_index: i32,
+    team_only: bool,
 ) -> flatbuffers::FlatBufferBuilder<'static> {
     let mut builder = flatbuffers::FlatBufferBuilder::new_with_capacity(32);
     let root = rlbot::flat::QuickChat::create(&mut builder, &rlbot::flat::QuickChatArgs {
         quickChatSelection: selection,
         playerIndex: player_index,
-        teamOnly: false,
+        teamOnly: team_only,
     });
     builder.finish(root, None);
     builder
@@ -92,11 +92,24 @@ pub const CAR_JUMP_IMPULSE_SPEED: f32 = CAR_JUMP_IMPULSE / CAR_MASS;
 /// The additional acceleration during a jump while holding the button down.
 pub const CAR_JUMP_ACCEL: f32 = CAR_JUMP_FORCE / CAR_MASS;
 
+/// The acceleration from boosting while airborne.
+///
+/// Source: RLBot wiki, "Useful game values"
+pub const CAR_AIR_BOOST_ACCEL: f32 = 991.666;
+
 /// Boost depletion per second.
 ///
 /// This value was determined using data from `collect`.
 pub const BOOST_DEPLETION: f32 = 100.0 / 3.0;
 
+/// The deceleration from holding reverse while driving forward.
+///
+/// Source: RLBot wiki, "Useful game values"
+pub const CAR_BRAKE_ACCEL: f32 = 3500.0;
+
+/// How long after the first jump a car can still dodge.
+pub const CAR_DODGE_TIME_LIMIT: f32 = 1.25;
+
 /// The velocity increase when dodging forward.
 pub const DODGE_FORWARD_IMPULSE: f32 = 500.0;
 
@@ -110,6 +123,19 @@ pub const BOOST_DOLLAR_RADIUS: f32 = 208.0;
 /// Source: https://youtu.be/xgfa-qZyInw?t=31
 pub const BOOST_DOLLAR_HEIGHT: f32 = 168.0;
 
+/// The x and y coordinates of the full boost pickups on a standard soccar
+/// field.
+///
+/// Source: https://github.com/RLBot/RLBot/wiki/Useful-Game-Values
+pub const SOCCAR_BOOST_DOLLAR_LOCS: [(f32, f32); 6] = [
+    (-3584.0, 0.0),
+    (3584.0, 0.0),
+    (-3072.0, 4096.0),
+    (3072.0, 4096.0),
+    (-3072.0, -4096.0),
+    (3072.0, -4096.0),
+];
+
 /// The radius of the small boost pickup's cylindrical hitbox.
 ///
 /// Source: https://youtu.be/xgfa-qZyInw?t=31
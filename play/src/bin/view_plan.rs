@@ -0,0 +1,145 @@
+//! Replay saved route plans over a top-down view of the field, next to where
+//! the car actually went.
+//!
+//! Usage: `view_plan [plans.csv] [play.csv] [start_time]`
+//!
+//! The files default to the ones from the most recent game. With a start
+//! time, skip any plans made before then.
+
+#![warn(future_incompatible, rust_2018_compatibility, rust_2018_idioms, unused)]
+#![cfg_attr(feature = "strict", deny(warnings))]
+#![warn(clippy::all)]
+
+use brain::{color, Drawable, EEG};
+use collect::{RecordingPlan, RecordingRigidBodyState, RecordingTick};
+use common::{halfway_house, prelude::*};
+use nalgebra::Vector3;
+use std::{env, fs::File, thread::sleep, time::Duration};
+
+/// Keep watching for a bit after the plan should have finished, since the
+/// interesting failures are often the ones that run long.
+const LINGER_TIME: f32 = 1.0;
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let plans_path = args.get(1).map_or("logs/plans.csv", String::as_str);
+    let recording_path = args.get(2).map_or("logs/play.csv", String::as_str);
+    let start_time: f32 = args
+        .get(3)
+        .map(|t| t.parse().expect("Error parsing start time"))
+        .unwrap_or(0.0);
+
+    let plans = RecordingPlan::parse(File::open(plans_path).expect("Error opening plans"));
+    let ticks: Vec<_> =
+        RecordingTick::parse(File::open(recording_path).expect("Error opening recording"))
+            .collect();
+
+    let mut eeg = EEG::new();
+    eeg.show_window();
+    for plan in plans.iter().filter(|p| p.time >= start_time) {
+        replay(&mut eeg, plan, &ticks);
+    }
+}
+
+fn replay(eeg: &mut EEG, plan: &RecordingPlan, ticks: &[RecordingTick]) {
+    let end_time = plan.time + plan.duration() + LINGER_TIME;
+    let ticks = ticks
+        .iter()
+        .skip_while(|t| t.time < plan.time)
+        .take_while(|t| t.time <= end_time);
+
+    let mut trace = Vec::new();
+    let mut prev_time = plan.time;
+    for tick in ticks {
+        let packet = packet_from_tick(tick);
+        eeg.begin(&packet);
+
+        if let Some(player) = tick.players.get(plan.player_index) {
+            trace.push(player.state.loc.to_2d());
+        }
+        draw_plan(eeg, plan);
+        eeg.print_time("elapsed", tick.time - plan.time);
+        for (&a, &b) in trace.iter().zip(trace.iter().skip(1)) {
+            eeg.draw(Drawable::Line(a, b, color::ORANGE));
+        }
+
+        eeg.show(&packet);
+        sleep(Duration::from_micros(
+            ((tick.time - prev_time) * 1e6) as u64,
+        ));
+        prev_time = tick.time;
+    }
+}
+
+fn draw_plan(eeg: &mut EEG, plan: &RecordingPlan) {
+    eeg.print_time("plan time", plan.time);
+    for segment in &plan.segments {
+        eeg.draw(Drawable::print(
+            format!(
+                "{} / {} ({:.2}s)",
+                segment.planner, segment.segment, segment.duration,
+            ),
+            color::YELLOW,
+        ));
+        eeg.draw(Drawable::Line(
+            segment.start.loc.to_2d(),
+            segment.end.loc.to_2d(),
+            color::BLUE,
+        ));
+        eeg.draw(Drawable::GhostCar(
+            segment.end.loc,
+            segment.end.rot.to_rotation_matrix(),
+        ));
+    }
+    if let Some(error) = &plan.error {
+        eeg.draw(Drawable::print(
+            format!("{} from {}", error.error, error.planner),
+            color::RED,
+        ));
+    }
+}
+
+fn packet_from_tick(tick: &RecordingTick) -> halfway_house::LiveDataPacket {
+    halfway_house::LiveDataPacket {
+        GameCars: tick
+            .players
+            .iter()
+            .map(|p| halfway_house::PlayerInfo {
+                Physics: physics(&p.state),
+                ..Default::default()
+            })
+            .collect(),
+        NumCars: tick.players.len() as i32,
+        GameBall: halfway_house::BallInfo {
+            Physics: physics(&tick.ball),
+        },
+        GameInfo: halfway_house::GameInfo {
+            TimeSeconds: tick.time,
+            ..Default::default()
+        },
+        Teams: Default::default(),
+        NumTeams: 0,
+    }
+}
+
+fn physics(state: &RecordingRigidBodyState) -> halfway_house::Physics {
+    let (pitch, yaw, roll) = state.rot.to_rotation_matrix().to_unreal_angles();
+    halfway_house::Physics {
+        Location: vector3(state.loc.coords),
+        Rotation: halfway_house::Rotator {
+            Pitch: pitch,
+            Yaw: yaw,
+            Roll: roll,
+        },
+        Velocity: vector3(state.vel),
+        AngularVelocity: vector3(state.ang_vel),
+    }
+}
+
+fn vector3(v: Vector3<f32>) -> halfway_house::Vector3 {
+    halfway_house::Vector3 {
+        X: v.x,
+        Y: v.y,
+        Z: v.z,
+    }
+}
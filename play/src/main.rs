@@ -3,14 +3,14 @@
 #![warn(clippy::all)]
 
 use crate::{banner::Banner, rlbot_ext::PacketeerExt};
-use brain::{Brain, EEG};
+use brain::{Brain, DroneOutput, Hivemind, EEG};
 use chrono::Local;
-use collect::Collector;
+use collect::{Collector, PlanCollector};
 use common::{
     ext::ExtendRLBot,
     halfway_house::{deserialize_game_tick_packet, translate_player_input},
 };
-use std::{error::Error, fs, panic, path::PathBuf, thread::sleep, time::Duration};
+use std::{env, error::Error, fs, panic, path::PathBuf, thread::sleep, time::Duration};
 
 mod banner;
 mod built;
@@ -35,6 +35,8 @@ fn main() {
         log_to_stdout,
         show_window,
         player_index,
+        hivemind,
+        seed,
     } = parse_args().expect("Error parsing command-line arguments");
 
     let rlbot = rlbot::init_with_options(init_options).expect("Could not initialize RLBot");
@@ -48,6 +50,8 @@ fn main() {
         run_bot(
             rlbot,
             player_index,
+            hivemind,
+            seed,
             log_game_data,
             log_to_stdout,
             show_window,
@@ -64,6 +68,14 @@ fn main() {
 }
 
 fn parse_args() -> Result<StartArgs, ()> {
+    // Only set this if the match config launches a single process for the whole
+    // team, otherwise the other processes will fight us for control of the cars.
+    let hivemind = env::var_os("FORMULA_NONE_HIVEMIND").is_some();
+    // Fix the seed to replay a match's random choices.
+    let seed = match env::var("FORMULA_NONE_SEED") {
+        Ok(seed) => Some(seed.parse().map_err(|_| ())?),
+        Err(_) => None,
+    };
     match rlbot::parse_framework_args()? {
         // If we're running in the framework:
         Some(args) => Ok(StartArgs {
@@ -74,6 +86,8 @@ fn parse_args() -> Result<StartArgs, ()> {
             log_game_data: false,
             log_to_stdout: false,
             show_window: false,
+            hivemind,
+            seed,
         }),
         // If we're running standalone:
         None => Ok(StartArgs {
@@ -84,6 +98,8 @@ fn parse_args() -> Result<StartArgs, ()> {
             log_to_stdout: true,
             show_window: true,
             player_index: 0,
+            hivemind,
+            seed,
         }),
     }
 }
@@ -96,6 +112,8 @@ struct StartArgs {
     log_to_stdout: bool,
     show_window: bool,
     player_index: i32,
+    hivemind: bool,
+    seed: Option<u64>,
 }
 
 fn start_match(rlbot: &rlbot::RLBot) -> Result<(), Box<dyn Error>> {
@@ -125,33 +143,60 @@ fn deny_climate_change<R>(f: impl Fn() -> R) -> R {
 fn run_bot(
     rlbot: &'static rlbot::RLBot,
     player_index: i32,
+    hivemind: bool,
+    seed: Option<u64>,
     log_game_data: bool,
     log_to_stdout: bool,
     show_window: bool,
 ) {
     let field_info = wait_for_field_info(rlbot);
-    let brain = match Brain::infer_game_mode(field_info) {
-        rlbot::GameMode::Soccer => Brain::soccar(),
-        rlbot::GameMode::Dropshot => Brain::dropshot(rlbot),
-        rlbot::GameMode::Hoops => Brain::hoops(rlbot),
-        mode => panic!("unexpected game mode {:?}", mode),
+    let pilot = if hivemind {
+        let player_indices = team_player_indices(rlbot, player_index);
+        log::info!("hivemind controlling {:?}", player_indices);
+        let mut hivemind = match Brain::infer_game_mode(field_info) {
+            rlbot::GameMode::Soccer => Hivemind::soccar(&player_indices),
+            rlbot::GameMode::Dropshot => Hivemind::dropshot(rlbot, &player_indices),
+            rlbot::GameMode::Hoops => Hivemind::hoops(rlbot, &player_indices),
+            mode => panic!("unexpected game mode {:?}", mode),
+        };
+        if let Some(seed) = seed {
+            hivemind.set_seed(seed);
+        }
+        Pilot::Hivemind(hivemind)
+    } else {
+        let mut brain = match Brain::infer_game_mode(field_info) {
+            rlbot::GameMode::Soccer => Brain::soccar(),
+            rlbot::GameMode::Dropshot => Brain::dropshot(rlbot),
+            rlbot::GameMode::Hoops => Brain::hoops(rlbot),
+            mode => panic!("unexpected game mode {:?}", mode),
+        };
+        brain.set_player_index(player_index);
+        if let Some(seed) = seed {
+            brain.set_seed(seed);
+        }
+        Pilot::Solo {
+            player_index,
+            brain: Box::new(brain),
+        }
     };
 
     let collector = if log_game_data {
-        Some(create_collector())
+        Some(Collector::new(create_log_file("play")))
     } else {
         None
     };
     let mut eeg = EEG::new();
+    if log_game_data {
+        eeg.record_plans(PlanCollector::new(create_log_file("plans")));
+    }
     if log_to_stdout {
         eeg.log_to_stdout();
     }
     if show_window {
         eeg.show_window();
     }
-    let mut bot = FormulaNone::new(rlbot, field_info, collector, eeg, brain);
-    bot.set_player_index(player_index);
-    bot_loop(&rlbot, player_index, &mut bot);
+    let mut bot = FormulaNone::new(rlbot, field_info, collector, eeg, pilot);
+    bot_loop(&rlbot, &mut bot);
 }
 
 fn wait_for_field_info(rlbot: &rlbot::RLBot) -> rlbot::flat::FieldInfo<'_> {
@@ -166,39 +211,57 @@ fn wait_for_field_info(rlbot: &rlbot::RLBot) -> rlbot::flat::FieldInfo<'_> {
     }
 }
 
-fn bot_loop(rlbot: &rlbot::RLBot, player_index: i32, bot: &mut FormulaNone<'_>) {
+/// Every car on the same team as `player_index`, which is what the hivemind
+/// takes control of.
+fn team_player_indices(rlbot: &rlbot::RLBot, player_index: i32) -> Vec<i32> {
+    let mut packeteer = rlbot.packeteer();
+    let packet_flat = packeteer.next_flatbuffer_without_timeout().unwrap();
+    let packet = deserialize_game_tick_packet(packet_flat);
+    let team = packet.GameCars[player_index as usize].Team;
+    (0..packet.NumCars)
+        .filter(|&i| packet.GameCars[i as usize].Team == team)
+        .collect()
+}
+
+fn bot_loop(rlbot: &rlbot::RLBot, bot: &mut FormulaNone<'_>) {
     let mut packeteer = rlbot.packeteer();
     loop {
         let packet_flat = packeteer.next_flatbuffer_without_timeout().unwrap();
         let packet = deserialize_game_tick_packet(packet_flat);
         let rigid_body_tick = None; // No longer supported in the latest RLBot version.
-        let (input, quick_chat) = bot.tick(rigid_body_tick, &packet);
-        rlbot
-            .update_player_input(player_index, &translate_player_input(&input))
-            .unwrap();
-        if let Some(chat) = quick_chat {
-            if let Err(_) = rlbot.quick_chat(chat, player_index) {
-                log::warn!("could not quick chat {:?}", chat);
+        for output in bot.tick(rigid_body_tick, &packet) {
+            let DroneOutput {
+                player_index,
+                input,
+                quick_chat,
+            } = output;
+            rlbot
+                .update_player_input(player_index, &translate_player_input(&input))
+                .unwrap();
+            if let Some(chat) = quick_chat {
+                if let Err(_) = rlbot.quick_chat(chat.selection, player_index, chat.team_only) {
+                    log::warn!("could not quick chat {:?}", chat.selection);
+                }
             }
         }
     }
 }
 
-fn create_collector() -> Collector {
+fn create_log_file(name: &str) -> fs::File {
     let directory = "logs";
 
     let now = Local::now().format("%Y-%m-%d_%H.%M.%S").to_string();
-    let filename = format!("{}/play-{}.csv", directory, now);
+    let filename = format!("{}/{}-{}.csv", directory, name, now);
     let file = fs::File::create(&filename).expect("Error creating game log file");
 
     // Link a consistently-named file, for convenience.
-    let link = PathBuf::from(format!("{}/play.csv", directory));
+    let link = PathBuf::from(format!("{}/{}.csv", directory, name));
     if link.exists() {
         fs::remove_file(&link).expect("Error deleting old game log hard link");
     }
     fs::hard_link(filename, &link).expect("Error creating game log hard link");
 
-    Collector::new(file)
+    file
 }
 
 struct FormulaNone<'a> {
@@ -206,45 +269,57 @@ struct FormulaNone<'a> {
     field_info: rlbot::flat::FieldInfo<'a>,
     collector: Option<collect::Collector>,
     eeg: EEG,
-    brain: Brain,
+    pilot: Pilot,
     banner: Banner,
 }
 
+/// Either one brain driving one car, or a hivemind driving the whole team.
+enum Pilot {
+    Solo { player_index: i32, brain: Box<Brain> },
+    Hivemind(Hivemind),
+}
+
 impl<'a> FormulaNone<'a> {
     fn new(
         rlbot: &'static rlbot::RLBot,
         field_info: rlbot::flat::FieldInfo<'a>,
         collector: Option<collect::Collector>,
         eeg: brain::EEG,
-        brain: brain::Brain,
+        pilot: Pilot,
     ) -> Self {
         Self {
             rlbot,
             field_info,
             collector,
             eeg,
-            brain,
+            pilot,
             banner: Banner::new(),
         }
     }
 
-    fn set_player_index(&mut self, player_index: i32) {
-        self.brain.set_player_index(player_index)
-    }
-
     fn tick(
         &mut self,
         rigid_body_tick: Option<rlbot::flat::RigidBodyTick<'_>>,
         packet: &common::halfway_house::LiveDataPacket,
-    ) -> (
-        common::halfway_house::PlayerInput,
-        Option<rlbot::flat::QuickChatSelection>,
-    ) {
+    ) -> Vec<DroneOutput> {
         logging::STATE.lock().unwrap().game_time = Some(packet.GameInfo.TimeSeconds);
         self.banner.run(self.rlbot, packet);
         self.eeg.begin(&packet);
 
-        let input = self.brain.tick(self.field_info, packet, &mut self.eeg);
+        let outputs = match &mut self.pilot {
+            Pilot::Solo {
+                player_index,
+                brain,
+            } => {
+                let input = brain.tick(self.field_info, packet, &mut self.eeg);
+                vec![DroneOutput {
+                    player_index: *player_index,
+                    input,
+                    quick_chat: brain.take_quick_chat(),
+                }]
+            }
+            Pilot::Hivemind(hivemind) => hivemind.tick(self.field_info, packet, &mut self.eeg),
+        };
 
         if let Some(collector) = &mut self.collector {
             if let Some(rigid_body_tick) = rigid_body_tick {
@@ -253,6 +328,6 @@ impl<'a> FormulaNone<'a> {
         }
         self.eeg.show(&packet);
 
-        (input, self.eeg.quick_chat)
+        outputs
     }
 }
@@ -6,11 +6,13 @@ use oven::data;
 
 const EPS: f32 = 1e-3;
 
+#[derive(Clone)]
 pub struct Car1D {
     time: f32,
     distance: f32,
     speed: f32,
     boost: f32,
+    reverse: bool,
 }
 
 impl Default for Car1D {
@@ -20,6 +22,7 @@ impl Default for Car1D {
             distance: 0.0,
             speed: 0.0,
             boost: 100.0,
+            reverse: false,
         }
     }
 }
@@ -46,6 +49,20 @@ impl Car1D {
         self
     }
 
+    /// Drive backwards. Speed and distance are still measured in the direction
+    /// of travel, so a throttle of `1.0` means full reverse (`-1.0` in the
+    /// game's terms) and `-1.0` brakes. Boost only pushes forwards, so it's
+    /// not allowed.
+    ///
+    /// There is no reverse table yet, so this is an approximation: reversing
+    /// follows the forward throttle curve and tops out at the same speed. To
+    /// replace it, run `collect` (which records `oven/data/reverse.csv`) and
+    /// look up that table here instead.
+    pub fn reversing(mut self) -> Self {
+        self.reverse = true;
+        self
+    }
+
     pub fn time(&self) -> f32 {
         self.time
     }
@@ -64,6 +81,7 @@ impl Car1D {
 
     pub fn advance(&mut self, dt: f32, throttle: f32, mut boost: bool) {
         assert!(dt >= 0.0);
+        assert!(!(self.reverse && boost));
         if dt < EPS {
             return; // Rigorously simulate zero time elapsing.
         }
@@ -76,6 +94,7 @@ impl Car1D {
             Curve::Throttle => self.calc_throttle_by_time(dt),
             Curve::Boost => self.calc_boost_by_time(dt),
             Curve::Coast => self.calc_coast_by_time(dt),
+            Curve::Brake => self.calc_brake_by_time(dt),
             Curve::ConstantSpeed => self.calc_constant_speed_by_time(dt, boost),
         };
 
@@ -100,6 +119,7 @@ impl Car1D {
 
     pub fn advance_by_distance(&mut self, distance: f32, throttle: f32, mut boost: bool) {
         assert!(distance > 0.0);
+        assert!(!(self.reverse && boost));
 
         if boost && self.boost <= EPS {
             boost = false;
@@ -108,7 +128,7 @@ impl Car1D {
         let curve = match Self::next_curve(self.speed, throttle, boost) {
             Curve::Throttle => self.calc_throttle_by_distance(distance),
             Curve::Boost => self.calc_boost_by_distance(distance),
-            Curve::Coast => panic!("unsupported inputs"),
+            Curve::Coast | Curve::Brake => panic!("unsupported inputs"),
             Curve::ConstantSpeed => self.calc_constant_speed_by_distance(distance, boost),
         };
 
@@ -144,6 +164,10 @@ impl Car1D {
             Curve::Boost
         } else if throttle == 1.0 && boost {
             Curve::ConstantSpeed
+        } else if throttle == -1.0 && !boost && speed > 0.0 {
+            Curve::Brake
+        } else if throttle == -1.0 && !boost {
+            Curve::ConstantSpeed
         } else {
             panic!("unsupported inputs")
         }
@@ -159,6 +183,23 @@ impl Car1D {
         }
     }
 
+    /// Braking is a constant deceleration until the car stops.
+    fn calc_brake_by_time(&self, dt: f32) -> CurveResult {
+        let stop_time = self.speed / rl::CAR_BRAKE_ACCEL;
+        let (dt, new_speed) = if dt >= stop_time {
+            // Land on exactly zero, so the next curve is `ConstantSpeed`.
+            (stop_time, 0.0)
+        } else {
+            (dt, self.speed - rl::CAR_BRAKE_ACCEL * dt)
+        };
+        CurveResult {
+            dt,
+            distance: (self.speed + new_speed) / 2.0 * dt,
+            new_speed,
+            boost_used: 0.0,
+        }
+    }
+
     fn calc_throttle_by_time(&self, dt: f32) -> CurveResult {
         let (dt, distance, new_speed) = Self::lookup_advance_by_time(dt, self.speed, 1.0, false);
         CurveResult {
@@ -171,7 +212,8 @@ impl Car1D {
 
     fn calc_boost_by_time(&self, dt: f32) -> CurveResult {
         let dt = dt.min(self.boost / rl::BOOST_DEPLETION);
-        let boost_used = dt * rl::BOOST_DEPLETION;
+        // Clamp, since dividing and multiplying back can round up past what's left.
+        let boost_used = (dt * rl::BOOST_DEPLETION).min(self.boost);
         let (dt, distance, new_speed) = Self::lookup_advance_by_time(dt, self.speed, 1.0, true);
         CurveResult {
             dt,
@@ -333,6 +375,7 @@ enum Curve {
     Throttle,
     Boost,
     Coast,
+    Brake,
     ConstantSpeed,
 }
 
@@ -491,6 +534,42 @@ mod tests {
         assert_eq!(car.boost(), 100.0);
     }
 
+    #[test]
+    fn advance_brake() {
+        let mut car = Car1D::new().with_speed(1000.0);
+        car.advance(0.1, -1.0, false);
+        assert!((car.speed() - (1000.0 - rl::CAR_BRAKE_ACCEL * 0.1)).abs() < EPS);
+        assert_eq!(car.boost(), 100.0);
+    }
+
+    #[test]
+    fn advance_brake_to_stop() {
+        let mut car = Car1D::new().with_speed(1000.0);
+        car.advance(1.0, -1.0, false);
+        let stop_dist = 1000.0 * 1000.0 / (2.0 * rl::CAR_BRAKE_ACCEL);
+        assert_eq!(car.speed(), 0.0);
+        assert!((car.distance() - stop_dist).abs() < 0.1);
+        assert!((car.time() - 1.0).abs() < EPS);
+    }
+
+    #[test]
+    fn advance_reverse() {
+        let mut forward = Car1D::new();
+        forward.advance(1.0, 1.0, false);
+        let mut reverse = Car1D::new().reversing();
+        reverse.advance(1.0, 1.0, false);
+        assert_eq!(reverse.distance(), forward.distance());
+        assert_eq!(reverse.speed(), forward.speed());
+        assert!(reverse.speed() <= rl::CAR_NORMAL_SPEED);
+    }
+
+    #[test]
+    #[should_panic]
+    fn reverse_boost() {
+        let mut car = Car1D::new().reversing();
+        car.advance(1.0, 1.0, true);
+    }
+
     #[test]
     fn advance_coast_slow() {
         let mut car = Car1D::new().with_speed(100.0);
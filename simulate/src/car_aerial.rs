@@ -0,0 +1,263 @@
+//! A closed-form model of a simple aerial: a single jump held for the full
+//! duration, followed by a constant boost towards the target.
+
+#![allow(clippy::float_cmp)]
+
+use common::{physics, rl};
+use nalgebra::{Point3, Unit, UnitQuaternion, Vector3};
+use std::f32::consts::PI;
+
+/// How long the jump button is held before we start boosting.
+pub const JUMP_DURATION: f32 = rl::CAR_JUMP_FORCE_TIME;
+
+/// Only plan to use this fraction of the available boost acceleration, so
+/// there is some slack left to correct errors mid-flight.
+const ACCEL_MARGIN: f32 = 0.9;
+
+#[derive(Copy, Clone, Debug)]
+pub struct AerialPlan {
+    /// How long to hold jump.
+    pub jump_duration: f32,
+    /// The direction the nose should point while boosting.
+    pub boost_direction: Unit<Vector3<f32>>,
+    /// The average acceleration boost needs to supply, in uu/s².
+    pub required_accel: f32,
+    /// The estimated amount of boost spent, on the same 0–100 scale as the
+    /// game.
+    pub boost_used: f32,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum AerialPlanError {
+    /// The model assumes a takeoff from flat ground.
+    NotOnFlatGround,
+    /// The target is too soon to even finish the jump and turn.
+    NotEnoughTime,
+    /// Reaching the target would take more acceleration than boost can give.
+    NotEnoughAccel,
+    /// The car would run out of boost before it got there.
+    NotEnoughBoost,
+}
+
+/// Plan an aerial from a grounded car so that it arrives at `target_loc`
+/// exactly `time` seconds from now.
+pub fn plan_aerial(
+    start_loc: Point3<f32>,
+    start_vel: Vector3<f32>,
+    start_rot: UnitQuaternion<f32>,
+    boost: f32,
+    target_loc: Point3<f32>,
+    time: f32,
+) -> Result<AerialPlan, AerialPlanError> {
+    let start_forward = physics::car_forward_axis(start_rot);
+    if physics::car_roof_axis(start_rot).angle(&Vector3::z()) >= PI / 12.0 {
+        return Err(AerialPlanError::NotOnFlatGround);
+    }
+    if time <= JUMP_DURATION {
+        return Err(AerialPlanError::NotEnoughTime);
+    }
+
+    let (loc, vel) = after_jump(start_loc, start_vel);
+    plan_boost(
+        start_forward,
+        loc,
+        vel,
+        boost,
+        target_loc,
+        time - JUMP_DURATION,
+        JUMP_DURATION,
+    )
+}
+
+/// Plan the rest of a flight for a car that is already in the air, so that it
+/// arrives at `target_loc` exactly `time` seconds from now.
+pub fn plan_airborne(
+    start_loc: Point3<f32>,
+    start_vel: Vector3<f32>,
+    start_rot: UnitQuaternion<f32>,
+    boost: f32,
+    target_loc: Point3<f32>,
+    time: f32,
+) -> Result<AerialPlan, AerialPlanError> {
+    if time <= 0.0 {
+        return Err(AerialPlanError::NotEnoughTime);
+    }
+    let start_forward = physics::car_forward_axis(start_rot);
+    plan_boost(
+        start_forward,
+        start_loc,
+        start_vel,
+        boost,
+        target_loc,
+        time,
+        0.0,
+    )
+}
+
+/// The shared tail end of both plans: starting from `loc` and `vel`, with
+/// `coast_time` seconds of flight remaining after the jump.
+fn plan_boost(
+    start_forward: Unit<Vector3<f32>>,
+    loc: Point3<f32>,
+    vel: Vector3<f32>,
+    boost: f32,
+    target_loc: Point3<f32>,
+    coast_time: f32,
+    jump_duration: f32,
+) -> Result<AerialPlan, AerialPlanError> {
+    let time = coast_time + jump_duration;
+    let coast_loc = coast(loc, vel, coast_time);
+    let gap = target_loc - coast_loc;
+    let boost_direction = Unit::try_new(gap, 1e-3).unwrap_or_else(Vector3::z_axis);
+
+    // No useful boost happens until the nose is pointed the right way.
+    let boost_time = time - turn_duration(start_forward, boost_direction);
+    if boost_time <= 0.0 {
+        return Err(AerialPlanError::NotEnoughTime);
+    }
+
+    let required_accel = 2.0 * gap.norm() / (boost_time * boost_time);
+    if required_accel > rl::CAR_AIR_BOOST_ACCEL * ACCEL_MARGIN {
+        return Err(AerialPlanError::NotEnoughAccel);
+    }
+
+    // Feathering the boost saves some, so only charge for the duty cycle.
+    let boost_used = required_accel / rl::CAR_AIR_BOOST_ACCEL * boost_time * rl::BOOST_DEPLETION;
+    if boost_used > boost {
+        return Err(AerialPlanError::NotEnoughBoost);
+    }
+
+    Ok(AerialPlan {
+        jump_duration,
+        boost_direction,
+        required_accel,
+        boost_used,
+    })
+}
+
+/// A rough estimate of how long it takes to swing the nose from `from` to
+/// `to`, ignoring the time spent spinning up and slowing down.
+fn turn_duration(from: Unit<Vector3<f32>>, to: Unit<Vector3<f32>>) -> f32 {
+    from.angle(&to.into_inner()) / rl::CAR_MAX_ANGULAR_VELOCITY
+}
+
+/// For a car already in the air, what constant acceleration (not counting
+/// gravity) will put it at `target_loc` after `time_remaining` seconds?
+pub fn steering_accel(
+    loc: Point3<f32>,
+    vel: Vector3<f32>,
+    target_loc: Point3<f32>,
+    time_remaining: f32,
+) -> Vector3<f32> {
+    let time_remaining = time_remaining.max(1.0 / 120.0);
+    let coast_loc = coast(loc, vel, time_remaining);
+    2.0 * (target_loc - coast_loc) / (time_remaining * time_remaining)
+}
+
+/// The state of the car at the end of the jump, assuming it starts flat on the
+/// ground.
+fn after_jump(loc: Point3<f32>, vel: Vector3<f32>) -> (Point3<f32>, Vector3<f32>) {
+    let t = JUMP_DURATION;
+    let vel = vel + Vector3::z() * rl::CAR_JUMP_IMPULSE_SPEED;
+    let accel = Vector3::z() * (rl::CAR_JUMP_ACCEL + rl::GRAVITY);
+    (loc + vel * t + 0.5 * accel * t * t, vel + accel * t)
+}
+
+fn coast(loc: Point3<f32>, vel: Vector3<f32>, t: f32) -> Point3<f32> {
+    loc + vel * t + Vector3::z() * (0.5 * rl::GRAVITY * t * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::car_aerial::{plan_aerial, plan_airborne, steering_accel, AerialPlanError};
+    use common::physics;
+    use nalgebra::{Point3, UnitQuaternion, Vector3};
+
+    fn flat() -> UnitQuaternion<f32> {
+        UnitQuaternion::identity()
+    }
+
+    #[test]
+    fn straight_up() {
+        let start = Point3::new(0.0, 0.0, 17.0);
+        let target = Point3::new(0.0, 0.0, 800.0);
+        let plan = plan_aerial(start, Vector3::zeros(), flat(), 100.0, target, 1.7).unwrap();
+        assert!(plan.boost_direction.z > 0.99);
+        assert!(plan.boost_used > 0.0);
+        assert!(plan.boost_used < 100.0);
+    }
+
+    #[test]
+    fn too_high_too_soon() {
+        let start = Point3::new(0.0, 0.0, 17.0);
+        let target = Point3::new(0.0, 0.0, 1800.0);
+        assert_eq!(
+            plan_aerial(start, Vector3::zeros(), flat(), 100.0, target, 0.8).unwrap_err(),
+            AerialPlanError::NotEnoughAccel,
+        );
+    }
+
+    #[test]
+    fn not_enough_boost() {
+        let start = Point3::new(0.0, 0.0, 17.0);
+        let target = Point3::new(800.0, 0.0, 900.0);
+        assert!(plan_aerial(start, Vector3::zeros(), flat(), 100.0, target, 2.0).is_ok());
+        assert_eq!(
+            plan_aerial(start, Vector3::zeros(), flat(), 5.0, target, 2.0).unwrap_err(),
+            AerialPlanError::NotEnoughBoost,
+        );
+    }
+
+    #[test]
+    fn turning_takes_time() {
+        let start = Point3::new(0.0, 0.0, 17.0);
+        let target = Point3::new(0.0, 0.0, 17.0);
+        assert_eq!(
+            plan_aerial(start, Vector3::zeros(), flat(), 100.0, target, 0.25).unwrap_err(),
+            AerialPlanError::NotEnoughTime,
+        );
+    }
+
+    #[test]
+    fn must_start_flat() {
+        let start = Point3::new(0.0, 0.0, 17.0);
+        let target = Point3::new(0.0, 0.0, 800.0);
+        let tilted = UnitQuaternion::from_euler_angles(1.0, 0.0, 0.0);
+        assert_eq!(
+            plan_aerial(start, Vector3::zeros(), tilted, 100.0, target, 1.7).unwrap_err(),
+            AerialPlanError::NotOnFlatGround,
+        );
+    }
+
+    #[test]
+    fn steering_accel_lands_on_target() {
+        let loc = Point3::new(0.0, 0.0, 300.0);
+        let vel = Vector3::new(0.0, 500.0, 300.0);
+        let target = Point3::new(100.0, 800.0, 700.0);
+        let t = 1.0;
+        let accel = steering_accel(loc, vel, target, t);
+        let total_accel = accel + Vector3::z() * common::rl::GRAVITY;
+        let end = loc + vel * t + 0.5 * total_accel * t * t;
+        assert!((end - target).norm() < 1.0);
+    }
+
+    #[test]
+    fn airborne_skips_the_jump() {
+        // Already pointed straight up, so there's no turn to wait for.
+        let nose_up = UnitQuaternion::rotation_between_axis(
+            &*physics::CAR_LOCAL_FORWARD_AXIS,
+            &Vector3::z_axis(),
+        )
+        .unwrap();
+        let loc = Point3::new(0.0, 0.0, 500.0);
+        let vel = Vector3::new(0.0, 0.0, 300.0);
+        let target = Point3::new(0.0, 0.0, 800.0);
+        let plan = plan_airborne(loc, vel, nose_up, 100.0, target, 1.0).unwrap();
+        assert_eq!(plan.jump_duration, 0.0);
+        assert!(plan.boost_direction.z > 0.99);
+        assert_eq!(
+            plan_airborne(loc, vel, nose_up, 100.0, target, 0.0).unwrap_err(),
+            AerialPlanError::NotEnoughTime,
+        );
+    }
+}
@@ -0,0 +1,307 @@
+#![allow(clippy::float_cmp)]
+
+use crate::car1d::Car1D;
+use common::rl;
+
+/// Speed changes that take longer than this aren't worth planning.
+const MAX_PHASE_TIME: f32 = 5.0;
+/// Close enough when matching a target speed.
+const SPEED_TOLERANCE: f32 = 1.0;
+/// Close enough when matching the target distance.
+const DISTANCE_TOLERANCE: f32 = 5.0;
+/// Binary search iterations. Each one halves the remaining interval.
+const ITERATIONS: usize = 24;
+
+pub struct CarArrive;
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum CarArriveError {
+    /// We can't cover the distance (or reach the end speed) in time.
+    TooFar,
+    /// Even braking, we'd get there too early (or too fast).
+    TooFast,
+}
+
+impl CarArrive {
+    /// Plan the throttle for driving `distance` in a straight line so that we
+    /// get there in exactly `time` seconds, and at `end_speed` if it's given.
+    ///
+    /// The plan changes speed once at the start, cruises, and then changes
+    /// speed again at the end to match `end_speed`.
+    pub fn calc_1d(
+        start_speed: f32,
+        start_boost: f32,
+        distance: f32,
+        time: f32,
+        end_speed: Option<f32>,
+    ) -> Result<CarArrive1D, CarArriveError> {
+        assert!(distance >= 0.0);
+        if time <= 0.0 {
+            return Err(CarArriveError::TooFar);
+        }
+
+        let plan =
+            |cruise_speed| Self::plan(start_speed, start_boost, cruise_speed, time, end_speed);
+
+        // Cruising at a speed between the start and end speeds means only one
+        // speed change, which is the best case for fitting in the time window.
+        let direct = plan(start_speed).ok_or(match end_speed {
+            Some(end_speed) if end_speed > start_speed => CarArriveError::TooFar,
+            _ => CarArriveError::TooFast,
+        })?;
+
+        // Find the range of cruise speeds whose speed changes fit in the time
+        // window.
+        let max_speed = if start_boost > 0.0 {
+            rl::CAR_MAX_SPEED
+        } else {
+            rl::CAR_NORMAL_SPEED.max(start_speed)
+        };
+        let fastest = bisect(start_speed, max_speed, |speed| plan(speed).is_some());
+        let slowest = bisect(start_speed, 0.0, |speed| plan(speed).is_some());
+        let fastest = plan(fastest).unwrap_or_else(|| direct.clone());
+        let slowest = plan(slowest).unwrap_or(direct);
+
+        if fastest.distance() < distance - DISTANCE_TOLERANCE {
+            return Err(CarArriveError::TooFar);
+        }
+        if slowest.distance() > distance + DISTANCE_TOLERANCE {
+            return Err(CarArriveError::TooFast);
+        }
+
+        // More cruise speed means more distance, so search for the cruise
+        // speed that covers exactly the right distance.
+        let cruise_speed = bisect(slowest.cruise_speed, fastest.cruise_speed, |speed| {
+            plan(speed)
+                .map(|p| p.distance() <= distance)
+                .unwrap_or(false)
+        });
+        Ok(plan(cruise_speed).unwrap_or(slowest))
+    }
+
+    fn plan(
+        start_speed: f32,
+        start_boost: f32,
+        cruise_speed: f32,
+        time: f32,
+        end_speed: Option<f32>,
+    ) -> Option<CarArrive1D> {
+        let change = CarArrivePhase::calc(start_speed, start_boost, cruise_speed)?;
+        let cruise_speed = change.end_speed;
+        let finish = CarArrivePhase::calc(
+            cruise_speed,
+            // Float error can leave this a hair below zero.
+            (change.start_boost - change.boost_used).max(0.0),
+            end_speed.unwrap_or(cruise_speed),
+        )?;
+        let cruise_duration = time - change.duration - finish.duration;
+        if cruise_duration < 0.0 {
+            return None;
+        }
+        Some(CarArrive1D {
+            change,
+            cruise_speed,
+            cruise_duration,
+            finish,
+        })
+    }
+}
+
+/// Returns the last value between `good` and `bad` (in either order) where
+/// `predicate` holds, assuming it holds at `good` and flips at most once.
+fn bisect(mut good: f32, mut bad: f32, predicate: impl Fn(f32) -> bool) -> f32 {
+    if predicate(bad) {
+        return bad;
+    }
+    for _ in 0..ITERATIONS {
+        let mid = (good + bad) / 2.0;
+        if predicate(mid) {
+            good = mid;
+        } else {
+            bad = mid;
+        }
+    }
+    good
+}
+
+#[derive(Clone)]
+pub struct CarArrive1D {
+    pub change: CarArrivePhase,
+    pub cruise_speed: f32,
+    pub cruise_duration: f32,
+    pub finish: CarArrivePhase,
+}
+
+impl CarArrive1D {
+    pub fn duration(&self) -> f32 {
+        self.change.duration + self.cruise_duration + self.finish.duration
+    }
+
+    pub fn distance(&self) -> f32 {
+        self.change.distance + self.cruise_speed * self.cruise_duration + self.finish.distance
+    }
+
+    pub fn end_speed(&self) -> f32 {
+        self.finish.end_speed
+    }
+
+    pub fn end_boost(&self) -> f32 {
+        (self.finish.start_boost - self.finish.boost_used).max(0.0)
+    }
+
+    /// The phase in effect at time `t`, or `None` while cruising.
+    pub fn phase_at(&self, t: f32) -> Option<&CarArrivePhase> {
+        if t < self.change.duration {
+            Some(&self.change)
+        } else if t < self.change.duration + self.cruise_duration {
+            None
+        } else {
+            Some(&self.finish)
+        }
+    }
+
+    /// Returns the distance traveled and the speed at time `t`.
+    pub fn at(&self, t: f32) -> (f32, f32) {
+        let t = t.max(0.0).min(self.duration());
+        if t <= self.change.duration {
+            return self.change.at(t);
+        }
+
+        let t = t - self.change.duration;
+        if t <= self.cruise_duration {
+            return (
+                self.change.distance + self.cruise_speed * t,
+                self.cruise_speed,
+            );
+        }
+
+        let t = t - self.cruise_duration;
+        let (distance, speed) = self.finish.at(t);
+        (
+            self.change.distance + self.cruise_speed * self.cruise_duration + distance,
+            speed,
+        )
+    }
+}
+
+/// A stretch of constant inputs that takes the car from one speed to another.
+#[derive(Copy, Clone)]
+pub struct CarArrivePhase {
+    pub throttle: f32,
+    pub boost: bool,
+    pub start_speed: f32,
+    pub start_boost: f32,
+    pub end_speed: f32,
+    pub duration: f32,
+    pub distance: f32,
+    pub boost_used: f32,
+}
+
+impl CarArrivePhase {
+    fn calc(start_speed: f32, start_boost: f32, end_speed: f32) -> Option<Self> {
+        let (throttle, boost) = if end_speed < start_speed - SPEED_TOLERANCE {
+            (-1.0, false)
+        } else if end_speed > start_speed + SPEED_TOLERANCE {
+            (1.0, end_speed > rl::CAR_NORMAL_SPEED)
+        } else {
+            return Some(Self::zero(start_speed, start_boost));
+        };
+
+        let sim = |time| {
+            let mut car = Car1D::new().with_speed(start_speed).with_boost(start_boost);
+            car.advance(time, throttle, boost);
+            car
+        };
+
+        let duration = if throttle < 0.0 {
+            (start_speed - end_speed) / rl::CAR_BRAKE_ACCEL
+        } else {
+            // Throttle alone tops out at `CAR_NORMAL_SPEED`, and boost can run
+            // out, so make sure the speed is reachable at all.
+            if sim(MAX_PHASE_TIME).speed() < end_speed - SPEED_TOLERANCE {
+                return None;
+            }
+            bisect(MAX_PHASE_TIME, 0.0, |t| {
+                sim(t).speed() >= end_speed - SPEED_TOLERANCE
+            })
+        };
+
+        let car = sim(duration);
+        Some(Self {
+            throttle,
+            boost,
+            start_speed,
+            start_boost,
+            end_speed: car.speed(),
+            duration,
+            distance: car.distance(),
+            boost_used: start_boost - car.boost(),
+        })
+    }
+
+    fn zero(speed: f32, boost: f32) -> Self {
+        Self {
+            throttle: 0.0,
+            boost: false,
+            start_speed: speed,
+            start_boost: boost,
+            end_speed: speed,
+            duration: 0.0,
+            distance: 0.0,
+            boost_used: 0.0,
+        }
+    }
+
+    /// Returns the distance traveled and the speed at time `t`.
+    pub fn at(&self, t: f32) -> (f32, f32) {
+        let mut car = Car1D::new()
+            .with_speed(self.start_speed)
+            .with_boost(self.start_boost);
+        car.advance(t.max(0.0).min(self.duration), self.throttle, self.boost);
+        (car.distance(), car.speed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::car_arrive::{CarArrive, CarArriveError};
+    use common::rl;
+
+    #[test]
+    fn cruise_at_constant_speed() {
+        let arrive = CarArrive::calc_1d(1000.0, 0.0, 1000.0, 1.0, None).unwrap();
+        assert!((arrive.cruise_speed - 1000.0).abs() < 10.0);
+        assert!((arrive.duration() - 1.0).abs() < 0.01);
+        assert!((arrive.distance() - 1000.0).abs() < 10.0);
+    }
+
+    #[test]
+    fn slow_down_for_end_speed() {
+        let arrive = CarArrive::calc_1d(1400.0, 0.0, 2000.0, 2.0, Some(500.0)).unwrap();
+        assert_eq!(arrive.finish.throttle, -1.0);
+        assert!((arrive.end_speed() - 500.0).abs() < 10.0);
+        assert!((arrive.distance() - 2000.0).abs() < 10.0);
+        let (distance, speed) = arrive.at(arrive.duration());
+        assert!((distance - arrive.distance()).abs() < 1.0);
+        assert!((speed - arrive.end_speed()).abs() < 1.0);
+    }
+
+    #[test]
+    fn boost_when_late() {
+        let arrive = CarArrive::calc_1d(0.0, 100.0, 3000.0, 2.0, None).unwrap();
+        assert!(arrive.change.boost);
+        assert!(arrive.cruise_speed > rl::CAR_NORMAL_SPEED);
+    }
+
+    #[test]
+    fn too_far() {
+        let result = CarArrive::calc_1d(0.0, 0.0, 3000.0, 1.0, None);
+        assert_eq!(result.err(), Some(CarArriveError::TooFar));
+    }
+
+    #[test]
+    fn too_fast() {
+        let result = CarArrive::calc_1d(2000.0, 0.0, 100.0, 2.0, Some(2000.0));
+        assert_eq!(result.err(), Some(CarArriveError::TooFast));
+    }
+}
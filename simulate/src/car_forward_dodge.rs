@@ -38,4 +38,16 @@ impl CarForwardDodge1D {
     pub fn duration(&self) -> f32 {
         self.jump_duration + self.wait_duration + self.dodge_duration
     }
+
+    /// The distance covered and the speed, `t` seconds after the jump.
+    pub fn sample(&self, t: f32) -> (f32, f32) {
+        let t = t.max(0.0).min(self.duration());
+        let dodge_time = self.jump_duration + self.wait_duration;
+        if t < dodge_time {
+            return (self.start_speed * t, self.start_speed);
+        }
+        // Count back from the end, so the end lines up exactly.
+        let remaining = self.duration() - t;
+        (self.end_dist - self.end_speed * remaining, self.end_speed)
+    }
 }
@@ -0,0 +1,107 @@
+#![allow(clippy::float_cmp)]
+
+use common::rl;
+
+const JUMP_TIME: f32 = 6.0 / 120.0;
+const WAIT_TIME: f32 = 6.0 / 120.0;
+/// How long the backflip runs before it is cancelled.
+const FLIP_TIME: f32 = 12.0 / 120.0;
+/// Time spent holding the cancel and rolling back upright.
+const CANCEL_TIME: f32 = 0.45;
+const LANDING_TIME: f32 = 0.25;
+
+pub struct CarHalfFlip;
+
+impl CarHalfFlip {
+    /// `start_speed` is the car's velocity along its forward axis (negative if
+    /// it's rolling backwards). All outputs are measured along the same axis,
+    /// so a successful half-flip ends with a negative `end_dist` and
+    /// `end_speed`, with the car facing the opposite way.
+    pub fn calc_1d(start_speed: f32) -> CarHalfFlip1D {
+        let dodge_loc = start_speed * (JUMP_TIME + WAIT_TIME);
+        let dodge_vel = (start_speed - rl::DODGE_FORWARD_IMPULSE).max(-rl::CAR_MAX_SPEED);
+        let land_loc = dodge_loc + dodge_vel * (FLIP_TIME + CANCEL_TIME + LANDING_TIME);
+
+        CarHalfFlip1D {
+            start_speed,
+            end_dist: land_loc,
+            end_speed: dodge_vel,
+            jump_duration: JUMP_TIME,
+            wait_duration: WAIT_TIME,
+            flip_duration: FLIP_TIME,
+            cancel_duration: CANCEL_TIME,
+            landing_duration: LANDING_TIME,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct CarHalfFlip1D {
+    pub start_speed: f32,
+    pub end_dist: f32,
+    pub end_speed: f32,
+    pub jump_duration: f32,
+    pub wait_duration: f32,
+    pub flip_duration: f32,
+    pub cancel_duration: f32,
+    pub landing_duration: f32,
+}
+
+impl CarHalfFlip1D {
+    pub fn duration(&self) -> f32 {
+        self.jump_duration
+            + self.wait_duration
+            + self.flip_duration
+            + self.cancel_duration
+            + self.landing_duration
+    }
+
+    /// The distance covered and the speed, `t` seconds after the jump.
+    pub fn sample(&self, t: f32) -> (f32, f32) {
+        let t = t.max(0.0).min(self.duration());
+        let dodge_time = self.jump_duration + self.wait_duration;
+        if t < dodge_time {
+            return (self.start_speed * t, self.start_speed);
+        }
+        // Count back from the end, so the end lines up exactly.
+        let remaining = self.duration() - t;
+        (self.end_dist - self.end_speed * remaining, self.end_speed)
+    }
+
+    /// How far the car has turned around `t` seconds after the jump, from 0
+    /// (still facing forward) to 1 (facing back the way it came).
+    pub fn turn_fraction(&self, t: f32) -> f32 {
+        let turn_start = self.jump_duration + self.wait_duration;
+        let turn_duration = self.flip_duration + self.cancel_duration;
+        ((t - turn_start) / turn_duration).max(0.0).min(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::car_half_flip::CarHalfFlip;
+
+    #[test]
+    fn from_rest() {
+        let flip = CarHalfFlip::calc_1d(0.0);
+        assert_eq!(flip.end_speed, -500.0);
+        assert!(flip.end_dist < -300.0);
+        assert!(flip.duration() < 1.0);
+    }
+
+    #[test]
+    fn already_reversing() {
+        let flip = CarHalfFlip::calc_1d(-2000.0);
+        assert!(flip.end_speed >= -common::rl::CAR_MAX_SPEED);
+    }
+
+    #[test]
+    fn sample_matches_the_ends() {
+        let flip = CarHalfFlip::calc_1d(500.0);
+        assert_eq!(flip.sample(0.0), (0.0, flip.start_speed));
+        assert_eq!(
+            flip.sample(flip.duration()),
+            (flip.end_dist, flip.end_speed)
+        );
+    }
+}
@@ -1,5 +1,6 @@
 #![allow(clippy::float_cmp)]
 
+use crate::math::{linear_interpolate_find_index, linear_interpolate_use_index};
 use common::{
     physics::{car_forward_axis_2d, CAR_LOCAL_FORWARD_AXIS_2D},
     prelude::*,
@@ -21,6 +22,66 @@ pub struct CarPowerslideTurnBlueprint {
     pub end_rot: UnitComplex<f32>,
     pub end_vel: Vector2<f32>,
     pub duration: f32,
+    /// The recorded slide this was looked up from, for `sample`.
+    path: ReferencePath,
+}
+
+/// One recorded slide, from the start up to `end_index`, along with what it
+/// takes to move it into the caller's coordinates.
+#[derive(Clone)]
+struct ReferencePath {
+    time: &'static [f32],
+    loc_2d: &'static [Point2<f32>],
+    rot_2d_angle_cum: &'static [f32],
+    vel_2d: &'static [Vector2<f32>],
+    end_index: usize,
+    /// Takes the caller's coordinates to the reference's.
+    transform: UnitComplex<f32>,
+}
+
+impl CarPowerslideTurnBlueprint {
+    /// Where the car is, which way it's facing and how fast it's going, `t`
+    /// seconds into the slide.
+    pub fn sample(&self, t: f32) -> (Point2<f32>, UnitComplex<f32>, Vector2<f32>) {
+        let path = &self.path;
+        let t = t.max(0.0).min(self.duration);
+        let index = linear_interpolate_find_index(&path.time[..=path.end_index], path.time[0] + t);
+
+        let mut offset = interpolate_point(path.loc_2d, index) - path.loc_2d[0];
+        offset.x *= self.steer;
+        let mut vel = interpolate_vector(path.vel_2d, index);
+        vel.x *= self.steer;
+
+        // Scale the recorded rotation so it lands exactly on `end_rot`.
+        let rot_by = self.start_rot.angle_to(&self.end_rot);
+        let rot_start = path.rot_2d_angle_cum[0];
+        let rot_span = path.rot_2d_angle_cum[path.end_index] - rot_start;
+        let rot_frac = if rot_span > 0.0 {
+            (linear_interpolate_use_index(path.rot_2d_angle_cum, index) - rot_start) / rot_span
+        } else {
+            1.0
+        };
+
+        (
+            self.start_loc + path.transform.inverse() * offset,
+            UnitComplex::new(self.start_rot.angle() + rot_by * rot_frac),
+            path.transform.inverse() * vel,
+        )
+    }
+}
+
+fn interpolate_point(ys: &[Point2<f32>], (i, ratio): (usize, f32)) -> Point2<f32> {
+    if ratio == 0.0 {
+        return ys[i];
+    }
+    ys[i] + (ys[i + 1] - ys[i]) * ratio
+}
+
+fn interpolate_vector(ys: &[Vector2<f32>], (i, ratio): (usize, f32)) -> Vector2<f32> {
+    if ratio == 0.0 {
+        return ys[i];
+    }
+    ys[i] + (ys[i + 1] - ys[i]) * ratio
 }
 
 impl CarPowerslideTurn {
@@ -62,6 +123,10 @@ impl CarPowerslideTurn {
             end_rot: UnitComplex::new(start_rot.angle() + target_rot_by),
             end_vel: reference.end_vel,
             duration: reference.duration,
+            path: ReferencePath {
+                transform,
+                ..reference.path
+            },
         })
     }
 
@@ -108,6 +173,14 @@ impl CarPowerslideTurn {
             end_rot,
             end_vel,
             duration: end_time - start_time,
+            path: ReferencePath {
+                time: lower.time,
+                loc_2d: lower.loc_2d,
+                rot_2d_angle_cum: lower.rot_2d_angle_cum,
+                vel_2d: lower.vel_2d,
+                end_index: index,
+                transform: UnitComplex::identity(),
+            },
         })
     }
 }
@@ -0,0 +1,105 @@
+#![allow(clippy::float_cmp)]
+
+use common::rl;
+
+const JUMP_TIME: f32 = 4.0 / 120.0;
+const WAIT_TIME: f32 = 2.0 / 120.0;
+/// From the dodge until the wheels are back on the ground, with the flip
+/// cancelled so the car stays nose-forward. The dodge damps out what's left of
+/// the jump's vertical speed, so the car is only a few uu up and comes right
+/// back down.
+const FLIGHT_TIME: f32 = 0.4;
+
+pub struct CarSpeedFlip;
+
+impl CarSpeedFlip {
+    /// A diagonal dodge, cancelled so the car stays level, while holding boost
+    /// the whole way.
+    pub fn calc_1d(start_speed: f32) -> CarSpeedFlip1D {
+        let (dodge_loc, dodge_vel) = boost_for(0.0, start_speed, JUMP_TIME + WAIT_TIME);
+        let dodge_vel = (dodge_vel + rl::DODGE_FORWARD_IMPULSE).min(rl::CAR_MAX_SPEED);
+        let (land_loc, land_vel) = boost_for(dodge_loc, dodge_vel, FLIGHT_TIME);
+
+        CarSpeedFlip1D {
+            start_speed,
+            end_dist: land_loc,
+            end_speed: land_vel,
+            boost_used: (JUMP_TIME + WAIT_TIME + FLIGHT_TIME) * rl::BOOST_DEPLETION,
+            jump_duration: JUMP_TIME,
+            wait_duration: WAIT_TIME,
+            flight_duration: FLIGHT_TIME,
+        }
+    }
+}
+
+/// Airborne boost, capped at max speed.
+fn boost_for(loc: f32, speed: f32, time: f32) -> (f32, f32) {
+    let accel = rl::CAR_AIR_BOOST_ACCEL;
+    let accel_time = ((rl::CAR_MAX_SPEED - speed) / accel).max(0.0).min(time);
+    let speed_after = speed + accel * accel_time;
+    let loc = loc
+        + speed * accel_time
+        + 0.5 * accel * accel_time * accel_time
+        + speed_after * (time - accel_time);
+    (loc, speed_after)
+}
+
+#[derive(Clone)]
+pub struct CarSpeedFlip1D {
+    pub start_speed: f32,
+    pub end_dist: f32,
+    pub end_speed: f32,
+    pub boost_used: f32,
+    pub jump_duration: f32,
+    pub wait_duration: f32,
+    pub flight_duration: f32,
+}
+
+impl CarSpeedFlip1D {
+    pub fn duration(&self) -> f32 {
+        self.jump_duration + self.wait_duration + self.flight_duration
+    }
+
+    /// The distance covered and the speed, `t` seconds after the jump.
+    pub fn sample(&self, t: f32) -> (f32, f32) {
+        let t = t.max(0.0).min(self.duration());
+        let dodge_time = self.jump_duration + self.wait_duration;
+        if t < dodge_time {
+            return boost_for(0.0, self.start_speed, t);
+        }
+        let (dodge_loc, dodge_vel) = boost_for(0.0, self.start_speed, dodge_time);
+        let dodge_vel = (dodge_vel + rl::DODGE_FORWARD_IMPULSE).min(rl::CAR_MAX_SPEED);
+        boost_for(dodge_loc, dodge_vel, t - dodge_time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::car_speed_flip::CarSpeedFlip;
+    use common::rl;
+
+    #[test]
+    fn from_rest() {
+        let flip = CarSpeedFlip::calc_1d(0.0);
+        assert!(flip.end_speed > 900.0);
+        assert!(flip.end_dist > 250.0);
+        assert!(flip.boost_used < 33.0);
+    }
+
+    #[test]
+    fn caps_at_max_speed() {
+        let flip = CarSpeedFlip::calc_1d(2000.0);
+        assert_eq!(flip.end_speed, rl::CAR_MAX_SPEED);
+        assert!(flip.end_dist <= rl::CAR_MAX_SPEED * flip.duration());
+    }
+
+    #[test]
+    fn sample_matches_the_ends() {
+        let flip = CarSpeedFlip::calc_1d(500.0);
+        assert_eq!(flip.sample(0.0), (0.0, flip.start_speed));
+        assert_eq!(
+            flip.sample(flip.duration()),
+            (flip.end_dist, flip.end_speed)
+        );
+    }
+}
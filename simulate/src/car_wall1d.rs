@@ -0,0 +1,212 @@
+#![allow(clippy::float_cmp)]
+
+use crate::car1d::Car1D;
+use common::rl;
+
+/// A car driving at full throttle in a straight line across a surface that
+/// might be tilted, such as a wall. Gravity speeds the car up going down, and
+/// slows it down going up.
+///
+/// This steps `Car1D` one physics tick at a time and adds gravity on top, so
+/// it's much slower than `Car1D`. Prefer that when the whole route is on flat
+/// ground.
+#[derive(Clone)]
+pub struct CarWall1D {
+    time: f32,
+    distance: f32,
+    speed: f32,
+    boost: f32,
+}
+
+impl Default for CarWall1D {
+    fn default() -> Self {
+        Self {
+            time: 0.0,
+            distance: 0.0,
+            speed: 0.0,
+            boost: 100.0,
+        }
+    }
+}
+
+impl CarWall1D {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        assert!(speed >= 0.0);
+        assert!(speed < rl::CAR_MAX_SPEED + 1.0); // Allow slight rounding errors
+        self.speed = speed.min(rl::CAR_MAX_SPEED);
+        self
+    }
+
+    pub fn with_boost(mut self, boost: f32) -> Self {
+        assert!(boost >= 0.0);
+        assert!(boost <= 100.0);
+        self.boost = boost;
+        self
+    }
+
+    /// The acceleration gravity adds along the direction of travel. `climb`
+    /// is the sine of the angle between the direction of travel and the
+    /// horizon, so it's positive going up and negative going down.
+    pub fn slope_accel(climb: f32) -> f32 {
+        rl::GRAVITY * climb
+    }
+
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    pub fn distance(&self) -> f32 {
+        self.distance
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    pub fn boost(&self) -> f32 {
+        self.boost
+    }
+
+    /// Drive for `time` seconds while gravity pushes along the direction of
+    /// travel with `slope_accel` (see `slope_accel()`).
+    pub fn advance(&mut self, time: f32, slope_accel: f32, boost: bool) {
+        assert!(time >= 0.0);
+        let target = self.time + time;
+
+        while self.time < target {
+            let (new_speed, new_boost, step) = self.tick(slope_accel, boost);
+
+            let remaining = target - self.time;
+            if rl::PHYSICS_DT >= remaining {
+                // Stop partway through the tick, right on the target.
+                let frac = remaining / rl::PHYSICS_DT;
+                self.time = target;
+                self.distance += step * frac;
+                self.speed += (new_speed - self.speed) * frac;
+                self.boost -= (self.boost - new_boost) * frac;
+                break;
+            }
+
+            self.time += rl::PHYSICS_DT;
+            self.distance += step;
+            self.speed = new_speed;
+            self.boost = new_boost;
+        }
+    }
+
+    /// Drive `distance` forward while gravity pushes along the direction of
+    /// travel with `slope_accel` (see `slope_accel()`).
+    pub fn advance_by_distance(&mut self, distance: f32, slope_accel: f32, boost: bool) {
+        assert!(distance >= 0.0);
+        let target = self.distance + distance;
+
+        while self.distance < target {
+            let (new_speed, new_boost, step) = self.tick(slope_accel, boost);
+
+            if step <= 0.0 {
+                // Going nowhere. Full throttle beats gravity even straight up
+                // a wall, so this shouldn't happen, but don't spin forever.
+                break;
+            }
+
+            let remaining = target - self.distance;
+            if step >= remaining {
+                // Stop partway through the tick, right on the target.
+                let frac = remaining / step;
+                self.time += rl::PHYSICS_DT * frac;
+                self.distance = target;
+                self.speed += (new_speed - self.speed) * frac;
+                self.boost -= (self.boost - new_boost) * frac;
+                break;
+            }
+
+            self.time += rl::PHYSICS_DT;
+            self.distance += step;
+            self.speed = new_speed;
+            self.boost = new_boost;
+        }
+    }
+
+    /// Simulates one physics tick and returns the new speed, the new boost,
+    /// and the distance covered.
+    fn tick(&self, slope_accel: f32, boost: bool) -> (f32, f32, f32) {
+        const DT: f32 = rl::PHYSICS_DT;
+
+        let mut engine = Car1D::new()
+            .with_speed(self.speed)
+            .with_boost(self.boost.max(0.0));
+        engine.advance(DT, 1.0, boost);
+        let new_speed = (engine.speed() + slope_accel * DT)
+            .max(0.0)
+            .min(rl::CAR_MAX_SPEED);
+        let step = (self.speed + new_speed) / 2.0 * DT;
+        (new_speed, engine.boost(), step)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{car1d::Car1D, car_wall1d::CarWall1D};
+
+    #[test]
+    fn flat_matches_car1d() {
+        let mut car = CarWall1D::new().with_speed(500.0);
+        car.advance_by_distance(2000.0, 0.0, true);
+
+        let mut expected = Car1D::new().with_speed(500.0);
+        expected.advance_by_distance(2000.0, 1.0, true);
+
+        assert!((car.time() - expected.time()).abs() < 0.02);
+        assert!((car.speed() - expected.speed()).abs() < 20.0);
+        assert_eq!(car.distance(), 2000.0);
+    }
+
+    #[test]
+    fn uphill_is_slower() {
+        let mut flat = CarWall1D::new().with_speed(1000.0).with_boost(0.0);
+        flat.advance_by_distance(1000.0, 0.0, false);
+
+        let mut up = CarWall1D::new().with_speed(1000.0).with_boost(0.0);
+        up.advance_by_distance(1000.0, CarWall1D::slope_accel(1.0), false);
+
+        assert!(up.time() > flat.time());
+        assert!(up.speed() < flat.speed());
+    }
+
+    #[test]
+    fn downhill_is_faster() {
+        let mut flat = CarWall1D::new().with_speed(1000.0).with_boost(0.0);
+        flat.advance_by_distance(1000.0, 0.0, false);
+
+        let mut down = CarWall1D::new().with_speed(1000.0).with_boost(0.0);
+        down.advance_by_distance(1000.0, CarWall1D::slope_accel(-1.0), false);
+
+        assert!(down.time() < flat.time());
+        assert!(down.speed() > flat.speed());
+    }
+
+    #[test]
+    fn advance_matches_advance_by_distance() {
+        let mut by_distance = CarWall1D::new().with_speed(800.0);
+        by_distance.advance_by_distance(1500.0, CarWall1D::slope_accel(0.7), true);
+
+        let mut by_time = CarWall1D::new().with_speed(800.0);
+        by_time.advance(by_distance.time(), CarWall1D::slope_accel(0.7), true);
+
+        assert_eq!(by_time.time(), by_distance.time());
+        assert!((by_time.distance() - 1500.0).abs() < 1.0);
+        assert!((by_time.speed() - by_distance.speed()).abs() < 1.0);
+    }
+
+    #[test]
+    fn climb_from_standstill() {
+        let mut car = CarWall1D::new().with_boost(0.0);
+        car.advance_by_distance(500.0, CarWall1D::slope_accel(1.0), false);
+        assert_eq!(car.distance(), 500.0);
+        assert!(car.speed() > 0.0);
+    }
+}
@@ -6,17 +6,26 @@
 pub use crate::{
     car::{Car, CarSimulateError},
     car1d::Car1D,
+    car_arrive::{CarArrive, CarArrive1D, CarArriveError, CarArrivePhase},
     car_forward_dodge::{CarForwardDodge, CarForwardDodge1D},
+    car_half_flip::{CarHalfFlip, CarHalfFlip1D},
     car_powerslide_turn::{CarPowerslideTurn, CarPowerslideTurnBlueprint},
+    car_speed_flip::{CarSpeedFlip, CarSpeedFlip1D},
+    car_wall1d::CarWall1D,
     collision::ball_car_distance,
     math::linear_interpolate,
 };
 
 mod car;
 mod car1d;
+pub mod car_aerial;
+mod car_arrive;
 mod car_forward_dodge;
+mod car_half_flip;
 pub mod car_jump;
 mod car_powerslide_turn;
 pub mod car_single_jump;
+mod car_speed_flip;
+mod car_wall1d;
 mod collision;
 mod math;